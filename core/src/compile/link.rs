@@ -45,6 +45,17 @@ impl<Y, R> Tape<Y, R> {
     fn new_range(&self, idx: usize) -> Range<usize> {
         self.slices.get(idx).unwrap().start..self.buffer.len()
     }
+
+    fn truncate(&mut self, len: usize) {
+        if len < self.buffer.len() {
+            self.buffer.truncate(len);
+            // Views must not read the discarded states
+            self.slices.iter_mut().for_each(|slice| {
+                slice.generation += 1;
+                slice.start = slice.start.min(len);
+            });
+        }
+    }
 }
 
 pub type SharedTape<Y, R> = Rc<RefCell<Tape<Y, R>>>;
@@ -73,8 +84,9 @@ impl<Y, R> SliceRef<Y, R> {
     pub(super) fn new_view(&self) -> TapeView<Y, R> {
         TapeView(TapeViewImpl {
             slice: self.clone(),
-            generation: 0,
+            generation: None,
             range: Range::default(),
+            cycle: true,
         })
     }
 
@@ -150,6 +162,10 @@ pub struct Recorder<G, Y, R>(pub(super) RecorderImpl<G, Y, R>);
 pub type GeneratorRecorder<G: Generator> = Recorder<G, G::Yield, G::Return>;
 
 impl<G, Y, R> Recorder<G, Y, R> {
+    pub(super) fn tape(&self) -> SharedTape<Y, R> {
+        self.0.tape.clone()
+    }
+
     pub(super) fn new_slice(&self) -> SliceRef<Y, R> {
         self.0.new_slice()
     }
@@ -171,19 +187,59 @@ where
     }
 }
 
+/// The tapes of the [`Recorder`](Recorder)s of a node's descendants, which can be rolled back
+/// when the value generated by the node is discarded.
+///
+/// This is what keeps references consistent when a node (like that of `unique_together`)
+/// regenerates values: otherwise the values recorded while generating the discarded value would
+/// still be seen by the views of its descendants.
+pub struct Journal<Y, R> {
+    tapes: Vec<(SharedTape<Y, R>, usize)>,
+}
+
+impl<Y, R> Journal<Y, R> {
+    pub(super) fn new<I: IntoIterator<Item = SharedTape<Y, R>>>(tapes: I) -> Self {
+        let tapes = tapes
+            .into_iter()
+            .map(|tape| {
+                let len = tape.borrow().buffer.len();
+                (tape, len)
+            })
+            .collect();
+        Self { tapes }
+    }
+
+    /// Keep everything recorded so far.
+    pub fn commit(&mut self) {
+        for (tape, len) in self.tapes.iter_mut() {
+            *len = tape.borrow().buffer.len();
+        }
+    }
+
+    /// Discard everything recorded since the last commit.
+    pub fn rollback(&mut self) {
+        for (tape, len) in self.tapes.iter() {
+            tape.borrow_mut().truncate(*len);
+        }
+    }
+}
+
 pub(super) struct TapeViewImpl<Y, R> {
     slice: SliceRef<Y, R>,
-    generation: usize,
+    /// The generation of the slice which `range` was read from, if any yet: slices which are
+    /// never reset are read when the view is first used.
+    generation: Option<usize>,
     range: Range<usize>,
+    cycle: bool,
 }
 
 impl<Y, R> TapeViewImpl<Y, R> {
     fn is_obsolete(&self) -> bool {
-        self.slice.get_generation() != self.generation
+        Some(self.slice.get_generation()) != self.generation
     }
 
     fn reset(&mut self) -> bool {
-        self.generation = self.slice.get_generation();
+        self.generation = Some(self.slice.get_generation());
         self.range = self.slice.new_range();
         !self.range.is_empty()
     }
@@ -208,7 +264,7 @@ where
                 .expect("a view's index was out of bound")
                 .clone()
                 .map_complete(Some)
-        } else if !self.reset() {
+        } else if !self.cycle || !self.reset() {
             GeneratorState::Complete(None)
         } else {
             self.next(rng)
@@ -220,6 +276,15 @@ where
 /// [`Recorder`](Recorder).
 pub struct TapeView<Y, R>(pub(super) TapeViewImpl<Y, R>);
 
+impl<Y, R> TapeView<Y, R> {
+    /// Read the slice only once: instead of starting over, the view completes with `None` until
+    /// the slice is reset.
+    pub fn once(mut self) -> Self {
+        self.0.cycle = false;
+        self
+    }
+}
+
 pub(super) struct OrderedImpl<G, Y, R> {
     is_complete: bool,
     scope: Vec<SliceRef<Y, R>>,
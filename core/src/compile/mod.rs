@@ -20,7 +20,10 @@
 //! to compile both [`Namespace`](crate::schema::Namespace) and [`Content`](crate::schema::Content)
 //! into [`Graph`](crate::graph::Graph).
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 
@@ -32,12 +35,19 @@ pub mod address;
 pub use address::Address;
 
 pub mod link;
-pub use link::{FromLink, Link};
-use link::{GeneratorRecorder, GeneratorSliceRef, Ordered, Recorder};
+pub use link::{FromLink, Journal, Link};
+use link::{GeneratorRecorder, GeneratorSliceRef, Ordered, Recorder, SharedTape};
 
-use crate::graph::Graph;
+pub mod validate;
+pub use validate::ValidationError;
+
+use crate::graph::{Graph, IterNode, PinnedLocale, Relation, UniqueNode, Value};
 use crate::schema::{Content, Namespace};
 
+use synth_gen::prelude::Generator;
+
+type GraphTape = SharedTape<<Graph as Generator>::Yield, <Graph as Generator>::Return>;
+
 /// A trait for visitors of the [`Content`](crate::schema::Content) tree.
 ///
 /// Named children are visited (and built) by calling `build` and the value of a node at a
@@ -49,6 +59,93 @@ pub trait Compiler<'a> {
 
     /// Access the built value of the node at address `field`.
     fn get<S: Into<Address>>(&mut self, field: S) -> Result<Graph>;
+
+    /// The [`Partition`](Partition) of the values of the namespace generated by the
+    /// [`Graph`](crate::graph::Graph) being built.
+    fn partition(&self) -> Partition {
+        Partition::default()
+    }
+
+    /// The [`Relation`](Relation) of the array being built or, when building a field of the
+    /// objects of an array, of that array.
+    fn relation(&mut self) -> Relation {
+        Relation::default()
+    }
+
+    /// The locale pinned by the closest enclosing object (or the object being built) which has
+    /// `locales`, if any.
+    fn locale(&self) -> Option<PinnedLocale> {
+        None
+    }
+
+    /// The directory against which relative paths are resolved, if not the working directory.
+    fn directory(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// The share of the values of a namespace generated by one of `count` graphs compiled from the
+/// same namespace and sampled independently (for example in parallel).
+///
+/// Stateful generators use it so that the values generated by different partitions never
+/// collide: `unique` only accepts values whose hash falls in its partition and `id` steps over
+/// the values of the other partitions.
+///
+/// Generators which must agree on a random state across partitions (such as the permutation
+/// walked by `unique`) derive it from `seed`, which is the same for all partitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    pub index: u64,
+    pub count: u64,
+    pub seed: u64,
+}
+
+impl Partition {
+    pub fn new(index: u64, count: u64) -> Result<Self> {
+        if index >= count {
+            return Err(anyhow!(
+                "partition {} is out of bounds for {} partitions",
+                index,
+                count
+            ));
+        }
+        Ok(Self {
+            index,
+            count,
+            seed: 0,
+        })
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The partition of the node at `address`, whose seed differs from that of other nodes.
+    pub fn at(self, address: &Address) -> Self {
+        let mut hasher = DefaultHasher::new();
+        (self.seed, address).hash(&mut hasher);
+        self.with_seed(hasher.finish())
+    }
+
+    pub fn is_whole(&self) -> bool {
+        self.count == 1
+    }
+
+    /// Whether a value with the given hash belongs to this partition.
+    pub fn contains(&self, hash: u64) -> bool {
+        hash % self.count == self.index
+    }
+}
+
+impl Default for Partition {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            count: 1,
+            seed: 0,
+        }
+    }
 }
 
 pub trait Compile {
@@ -58,17 +155,36 @@ pub trait Compile {
 pub struct NamespaceCompiler<'a> {
     state: CompilerState<'a, Graph>,
     vtable: Symbols,
+    partition: Partition,
+    tapes: Vec<(Address, GraphTape)>,
+    relations: BTreeMap<Address, Relation>,
+    locales: BTreeMap<Address, PinnedLocale>,
+    directory: Option<PathBuf>,
+    lengths: BTreeMap<Address, Vec<u64>>,
+    shared: BTreeSet<String>,
 }
 
 impl<'a> NamespaceCompiler<'a> {
     fn new_at(state: CompilerState<'a, Graph>) -> Self {
         let vtable = Symbols::new();
-        Self { state, vtable }
+        Self {
+            state,
+            vtable,
+            partition: Partition::default(),
+            tapes: Vec::new(),
+            relations: BTreeMap::new(),
+            locales: BTreeMap::new(),
+            directory: None,
+            lengths: BTreeMap::new(),
+            shared: BTreeSet::new(),
+        }
     }
 
     pub fn new(namespace: &'a Namespace) -> Self {
         let state = CompilerState::namespace(namespace);
-        Self::new_at(state)
+        let mut compiler = Self::new_at(state);
+        compiler.directory = namespace.directory().map(Path::to_path_buf);
+        compiler
     }
 
     pub fn new_flat(content: &'a Content) -> Self {
@@ -76,11 +192,87 @@ impl<'a> NamespaceCompiler<'a> {
         Self::new_at(state)
     }
 
+    /// Compile a graph which only generates the given partition of the values.
+    pub fn with_partition(mut self, partition: Partition) -> Self {
+        self.partition = partition;
+        self
+    }
+
+    /// Compile a graph whose collections in `lengths`, which must be arrays, take the given
+    /// lengths in successive completions rather than the length from their schema.
+    pub fn with_lengths(mut self, lengths: BTreeMap<String, Vec<u64>>) -> Self {
+        self.lengths = lengths
+            .into_iter()
+            .map(|(collection, lengths)| {
+                let address = Address::new_root().into_at(&collection).into_at("length");
+                (address, lengths)
+            })
+            .collect();
+        self
+    }
+
+    /// Compile a graph in which the values of `collections` stay visible to the references of
+    /// other collections in later completions, rather than only in the completion which generated
+    /// them. Their values do not depend on the partition, so that all partitions see the same ones.
+    pub fn with_shared(mut self, collections: BTreeSet<String>) -> Self {
+        self.shared = collections;
+        self
+    }
+
+    /// The other collections which the values of each collection refer to.
+    pub fn collection_references(mut self) -> Result<BTreeMap<String, BTreeSet<String>>> {
+        let crawler = Crawler {
+            state: &mut self.state,
+            symbols: &mut self.vtable,
+            position: Address::new_root(),
+            directory: self.directory.as_deref(),
+        };
+        crawler.compile()?;
+
+        let mut references = BTreeMap::new();
+        let mut visits = vec![(Address::new_root(), &self.state)];
+        while let Some((address, state)) = visits.pop() {
+            if let Some(collection) = address.iter().next() {
+                for target in state.refs() {
+                    match target.iter().next() {
+                        Some(target) if target != collection => {
+                            references
+                                .entry(collection.to_string())
+                                .or_insert_with(BTreeSet::new)
+                                .insert(target.to_string());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            for (field, child) in state.scope().iter() {
+                visits.push((address.clone().into_at(&field), child));
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// The partition which the nodes at `address` generate, which is the whole of it for the nodes
+    /// of shared collections.
+    fn partition_of(&self, address: &Address) -> Partition {
+        let is_shared = address
+            .iter()
+            .next()
+            .is_some_and(|collection| self.shared.contains(collection));
+        if is_shared {
+            Partition::default().with_seed(self.partition.seed)
+        } else {
+            self.partition
+        }
+    }
+
     pub fn compile(mut self) -> Result<Graph> {
         let crawler = Crawler {
             state: &mut self.state,
             symbols: &mut self.vtable,
             position: Address::new_root(),
+            directory: self.directory.as_deref(),
         };
 
         crawler.compile()?;
@@ -178,6 +370,8 @@ impl<'a> NamespaceCompiler<'a> {
                 }
             }
 
+            let locale = self.pinned_locale(&address)?;
+            let partition = self.partition_of(&address);
             let state = self.state.project_mut(address.clone())?;
             let vtable = &mut self.vtable;
             let mut children = BTreeMap::new();
@@ -186,17 +380,36 @@ impl<'a> NamespaceCompiler<'a> {
                 state,
                 children: &mut children,
                 vtable,
+                partition: partition.at(&address),
+                relations: &mut self.relations,
+                locale,
+                directory: self.directory.as_deref(),
             };
 
             let mut node = content_compiler
                 .compile()
                 .with_context(|| format!("while trying to build `{}`", &address))?;
 
+            if let Some(lengths) = self.lengths.remove(&address) {
+                let lengths = lengths
+                    .into_iter()
+                    .map(|length| Value::Number(length.into()));
+                node = Graph::Iter(IterNode {
+                    iter: Box::new(lengths),
+                });
+            }
+
             if let Some(local_table) = vtable.get(&address) {
-                // `node` must be wrapped in `Ordered`
+                // `node` must be wrapped in `Ordered`, which starts the references over at each
+                // completion except for those to shared collections from other collections
+                let shared = &self.shared;
                 let mut scope = local_table
-                    .values()
-                    .map(|factory| factory.get_source().unwrap())
+                    .iter()
+                    .filter(|(target, _)| {
+                        !address.is_root()
+                            || !target.iter().next().is_some_and(|c| shared.contains(c))
+                    })
+                    .map(|(_, factory)| factory.get_source().unwrap())
                     .collect::<Vec<_>>();
                 let mut ordered_children = Vec::new();
                 for child in state.scope().iter_ordered() {
@@ -208,11 +421,40 @@ impl<'a> NamespaceCompiler<'a> {
                     scope.push(slice_ref);
                     ordered_children.push((child, recorder));
                 }
+                self.tapes.extend(
+                    ordered_children
+                        .iter()
+                        .map(|(child, recorder)| (address.clone().into_at(child), recorder.tape())),
+                );
                 node = Graph::from_link(Link::Ordered(Ordered::new(scope, ordered_children, node)));
             }
 
+            // Objects are made unique together here rather than in their `Compile` implementation,
+            // so that all of their fields, including those wrapped in `Ordered`, are regenerated
+            // with them
+            if let Source::Content(Content::Object(object)) = state.source() {
+                if !object.unique_together.is_empty() {
+                    let journal = Journal::new(
+                        self.tapes
+                            .iter()
+                            .filter(|(child, _)| {
+                                child.as_in(&address).is_some_and(|local| !local.is_root())
+                            })
+                            .map(|(_, tape)| tape.clone()),
+                    );
+                    node = Graph::Unique(UniqueNode::hash_fields_partition(
+                        node,
+                        object.unique_together.clone(),
+                        None,
+                        partition,
+                        journal,
+                    ));
+                }
+            }
+
             let artifact = if vtable.targetted(&address) {
                 let recorder = Recorder::wrap(node);
+                self.tapes.push((address.clone(), recorder.tape()));
                 vtable
                     .paths(&address)
                     .into_iter()
@@ -228,8 +470,36 @@ impl<'a> NamespaceCompiler<'a> {
             state.output_mut().set_output(artifact);
         }
 
+        if let Some(address) = self.lengths.keys().next() {
+            return Err(anyhow!(
+                "cannot set the length of `{}`: it is not the length of an array",
+                address
+            ));
+        }
+
         Ok(self.state.move_output().unwrap().pack())
     }
+
+    /// The locale pinned by the closest object enclosing `address` which has `locales`.
+    fn pinned_locale(&mut self, address: &Address) -> Result<Option<PinnedLocale>> {
+        let mut object = address.clone();
+        loop {
+            if let Source::Content(Content::Object(content)) =
+                self.state.project(object.clone())?.source()
+            {
+                if !content.locales.is_empty() {
+                    let locale = self
+                        .locales
+                        .entry(object)
+                        .or_insert_with(|| PinnedLocale::new(content.locales.clone()));
+                    return Ok(Some(locale.clone()));
+                }
+            }
+            if object.shallower().is_none() {
+                return Ok(None);
+            }
+        }
+    }
 }
 
 pub struct ContentCompiler<'c, 'a: 'c> {
@@ -237,6 +507,10 @@ pub struct ContentCompiler<'c, 'a: 'c> {
     state: &'c mut CompilerState<'a, Graph>,
     children: &'c mut BTreeMap<String, (GeneratorRecorder<Graph>, GeneratorSliceRef<Graph>)>,
     vtable: &'c mut Symbols,
+    partition: Partition,
+    relations: &'c mut BTreeMap<Address, Relation>,
+    locale: Option<PinnedLocale>,
+    directory: Option<&'c Path>,
 }
 
 impl<'c, 'a: 'c> ContentCompiler<'c, 'a> {
@@ -281,12 +555,35 @@ impl<'c, 'a: 'c> Compiler<'a> for ContentCompiler<'c, 'a> {
         })?;
         Ok(Graph::from_link(Link::View(view)))
     }
+
+    fn partition(&self) -> Partition {
+        self.partition
+    }
+
+    fn relation(&mut self) -> Relation {
+        let mut array = self.scope.clone();
+        if !matches!(self.state.source(), Source::Content(Content::Array(_))) {
+            // Fields are at `array.content.field`
+            array.shallower();
+            array.shallower();
+        }
+        self.relations.entry(array).or_default().clone()
+    }
+
+    fn locale(&self) -> Option<PinnedLocale> {
+        self.locale.clone()
+    }
+
+    fn directory(&self) -> Option<&Path> {
+        self.directory
+    }
 }
 
 pub struct Crawler<'t, 'a> {
     state: &'t mut CompilerState<'a, Graph>,
     symbols: &'t mut Symbols,
     position: Address,
+    directory: Option<&'t Path>,
 }
 
 impl<'t, 'a: 't> Crawler<'t, 'a> {
@@ -296,6 +593,7 @@ impl<'t, 'a: 't> Crawler<'t, 'a> {
             state: self.state.entry(field).or_init(content),
             symbols: self.symbols,
             position,
+            directory: self.directory,
         }
     }
 
@@ -327,13 +625,20 @@ impl<'t, 'a: 't> Compiler<'a> for Crawler<'t, 'a> {
         self.state.refs_mut().insert(target);
         Ok(Graph::dummy())
     }
+
+    fn directory(&self) -> Option<&Path> {
+        self.directory
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use super::{NamespaceCompiler, Partition};
     use crate::graph::Value;
     use crate::tests::complete;
 
+    use std::collections::{BTreeMap, BTreeSet};
+
     use synth_gen::prelude::*;
 
     #[test]
@@ -618,4 +923,302 @@ pub mod tests {
             });
         }
     }
+
+    #[test]
+    fn compile_unique_together() {
+        let generator = generator!({
+            "type": "object",
+            "0": {
+                "type": "array",
+                "length": 16,
+                "content": {
+                    "type": "object",
+                    "unique_together": [["a", "b"]],
+                    "id": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "id": {}
+                    },
+                    "a": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "range": {
+                            "low": 0,
+                            "high": 4,
+                            "step": 1
+                        }
+                    },
+                    "b": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "range": {
+                            "low": 0,
+                            "high": 4,
+                            "step": 1
+                        }
+                    },
+                    "c": "@0.content.a"
+                }
+            },
+            "1": {
+                "type": "array",
+                "length": 16,
+                "content": "@0.content.id"
+            }
+        });
+        let value = complete(generator).unwrap();
+        let as_object = value.as_object().unwrap();
+
+        let rows = as_object.get("0").unwrap().as_array().unwrap();
+        let pairs = rows
+            .iter()
+            .map(|row| {
+                let row = row.as_object().unwrap();
+                assert_eq!(row.get("a"), row.get("c"));
+                (row.get("a").unwrap().clone(), row.get("b").unwrap().clone())
+            })
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(pairs.len(), 16);
+
+        // References only see the ids of the objects that were accepted
+        let ids = rows
+            .iter()
+            .map(|row| row.as_object().unwrap().get("id").unwrap())
+            .collect::<Vec<_>>();
+        let refs = as_object.get("1").unwrap().as_array().unwrap();
+        assert_eq!(ids, refs.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn compile_pinned_locale() {
+        let faker = |generator| json!({"type": "string", "faker": {"generator": generator}});
+        let generator = generator!({
+            "type": "object",
+            "0": {
+                "type": "array",
+                "length": 64,
+                "content": {
+                    "type": "object",
+                    "locales": ["DE_DE", "JA_JP"],
+                    "first_name": faker("first_name"),
+                    "phone_number": faker("phone_number"),
+                    "address": {
+                        "type": "object",
+                        "zip_code": faker("zip_code")
+                    }
+                }
+            },
+            "1": {
+                "type": "array",
+                "length": 64,
+                "content": "@0.content.phone_number"
+            }
+        });
+        let value = complete(generator).unwrap();
+        let as_object = value.as_object().unwrap();
+
+        let mut japanese = 0;
+        for row in as_object.get("0").unwrap().as_array().unwrap() {
+            let row = row.as_object().unwrap();
+            let field = |value: &Value| value.as_string().unwrap().clone();
+            let first_name = field(row.get("first_name").unwrap());
+            let phone_number = field(row.get("phone_number").unwrap());
+            let address = row.get("address").unwrap().as_object().unwrap();
+            let zip_code = field(address.get("zip_code").unwrap());
+
+            let is_japanese = zip_code.contains('-');
+            assert_eq!(phone_number.contains('-'), is_japanese);
+            assert_eq!(first_name.chars().all(|c| c > '\u{3000}'), is_japanese);
+            japanese += is_japanese as usize;
+        }
+        assert!(japanese > 0 && japanese < 64);
+    }
+
+    #[test]
+    fn compile_unique_together_exhausted() {
+        let generator = generator!({
+            "type": "array",
+            "length": 5,
+            "content": {
+                "type": "object",
+                "unique_together": [["a", "b"]],
+                "a": {
+                    "type": "bool",
+                    "frequency": 0.5
+                },
+                "b": {
+                    "type": "bool",
+                    "frequency": 0.5
+                }
+            }
+        });
+        let err = complete(generator).unwrap_err();
+        assert!(err.to_string().contains(
+            "Could not generate enough objects whose fields 'a', 'b' are unique together"
+        ));
+
+        let generator = try_generator!({
+            "type": "object",
+            "unique_together": [["a", "c"]],
+            "a": {
+                "type": "bool",
+                "constant": true
+            }
+        });
+        assert!(generator.is_err());
+    }
+
+    #[test]
+    fn compile_with_lengths() {
+        let namespace = schema!({
+            "type": "object",
+            "users": {
+                "type": "array",
+                "length": 5,
+                "content": {
+                    "type": "number",
+                    "subtype": "u64",
+                    "id": {}
+                }
+            },
+            "settings": {
+                "type": "bool",
+                "constant": true
+            }
+        })
+        .into_namespace()
+        .unwrap();
+
+        let lengths = BTreeMap::from([("users".to_string(), vec![2, 0, 3])]);
+        let mut model = NamespaceCompiler::new(&namespace)
+            .with_lengths(lengths)
+            .compile()
+            .unwrap();
+        let mut rng = crate::tests::rng();
+        let users: Vec<_> = (0..3)
+            .map(|_| {
+                let value = model.complete(&mut rng).unwrap();
+                value.as_object().unwrap()["users"]
+                    .as_array()
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        let ids = |ids: &[u64]| {
+            ids.iter()
+                .map(|id| Value::Number((*id).into()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(users, [ids(&[1, 2]), ids(&[]), ids(&[3, 4, 5])]);
+
+        let lengths = BTreeMap::from([("settings".to_string(), vec![1])]);
+        assert!(NamespaceCompiler::new(&namespace)
+            .with_lengths(lengths)
+            .compile()
+            .is_err());
+    }
+
+    #[test]
+    fn compile_with_shared() {
+        let namespace = schema!({
+            "type": "object",
+            "users": {
+                "type": "array",
+                "length": 3,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "id": {}
+                    }
+                }
+            },
+            "events": {
+                "type": "array",
+                "length": 2,
+                "content": {
+                    "type": "object",
+                    "user_id": "@users.content.id"
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "user_id": {
+                        "type": "same_as",
+                        "ref": "users.content.id",
+                        "cardinality": {
+                            "type": "number",
+                            "subtype": "u64",
+                            "constant": 1
+                        }
+                    }
+                }
+            }
+        })
+        .into_namespace()
+        .unwrap();
+
+        let users = BTreeSet::from(["users".to_string()]);
+        assert_eq!(
+            NamespaceCompiler::new(&namespace)
+                .collection_references()
+                .unwrap(),
+            BTreeMap::from([
+                ("events".to_string(), users.clone()),
+                ("posts".to_string(), users.clone())
+            ])
+        );
+
+        // The users are only generated once, with their posts, and referenced by the events of
+        // later completions
+        let lengths = BTreeMap::from([
+            ("users".to_string(), vec![3, 0, 0]),
+            ("events".to_string(), vec![0, 4, 4]),
+        ]);
+        let partition = Partition::new(1, 2).unwrap();
+        let mut model = NamespaceCompiler::new(&namespace)
+            .with_partition(partition)
+            .with_lengths(lengths)
+            .with_shared(users)
+            .compile()
+            .unwrap();
+        let mut rng = crate::tests::rng();
+        let completions: Vec<_> = (0..3)
+            .map(|_| {
+                let value = model.complete(&mut rng).unwrap();
+                let value = value.as_object().unwrap();
+                let field = |collection: &str, field: &str| -> Vec<Value> {
+                    value[collection]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|object| object.as_object().unwrap()[field].clone())
+                        .collect()
+                };
+                (
+                    field("users", "id"),
+                    field("posts", "user_id"),
+                    field("events", "user_id"),
+                )
+            })
+            .collect();
+        let ids = |ids: &[u64]| {
+            ids.iter()
+                .map(|id| Value::Number((*id).into()))
+                .collect::<Vec<_>>()
+        };
+        // Whatever the partition, shared collections generate all of their values
+        assert_eq!(
+            completions,
+            [
+                (ids(&[1, 2, 3]), ids(&[1, 2, 3]), ids(&[])),
+                (ids(&[]), ids(&[]), ids(&[1, 2, 3, 1])),
+                (ids(&[]), ids(&[]), ids(&[2, 3, 1, 2]))
+            ]
+        );
+    }
 }
@@ -467,8 +467,8 @@ where
         self.locals.get(to)
     }
 
-    pub(super) fn values(&self) -> impl Iterator<Item = &ReferenceFactory<G>> {
-        self.locals.values()
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&Address, &ReferenceFactory<G>)> {
+        self.locals.iter()
     }
 
     fn get_mut(&mut self, to: &Address) -> Option<&mut ReferenceFactory<G>> {
@@ -0,0 +1,392 @@
+//! check a [`Namespace`](crate::schema::Namespace) for errors without generating from it.
+//!
+//! [`NamespaceCompiler::compile`](super::NamespaceCompiler::compile) stops at the first error it
+//! encounters. Validation instead crawls the whole tree, recording every node that fails to
+//! compile, every reference to an undefined node and every reference cycle, so that they can all
+//! be reported at once.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::{Address, Compile, Compiler, CompilerState, FromLink, NamespaceCompiler, Source};
+use crate::graph::Graph;
+use crate::schema::Content;
+
+/// An error found while validating a [`Namespace`](crate::schema::Namespace), along with the
+/// address of the node it was found at.
+#[derive(Debug)]
+pub struct ValidationError {
+    address: Address,
+    error: anyhow::Error,
+}
+
+impl ValidationError {
+    fn new(address: Address, error: anyhow::Error) -> Self {
+        Self { address, error }
+    }
+
+    /// The full address of the node at fault.
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// The name of the collection the node at fault belongs to, if any.
+    pub fn collection(&self) -> Option<&str> {
+        self.address.iter().next()
+    }
+
+    /// The address of the node at fault, relative to its collection.
+    pub fn field(&self) -> Address {
+        self.address.iter().skip(1).map(|s| s.to_string()).collect()
+    }
+
+    pub fn error(&self) -> &anyhow::Error {
+        &self.error
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.collection() {
+            None => write!(f, "{:#}", self.error),
+            Some(collection) => {
+                let field = self.field();
+                if field.is_root() {
+                    write!(f, "in collection `{}`: {:#}", collection, self.error)
+                } else {
+                    write!(
+                        f,
+                        "in collection `{}` at `{}`: {:#}",
+                        collection, field, self.error
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl<'a> NamespaceCompiler<'a> {
+    /// Check the whole tree for errors, returning all of them (an empty `Vec` means the tree will
+    /// compile).
+    pub fn validate(mut self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+
+        let validator = Validator {
+            state: &mut self.state,
+            position: Address::new_root(),
+            errors: &mut errors,
+            directory: self.directory.as_deref(),
+        };
+        if let Err(error) = validator.compile() {
+            errors.push(ValidationError::new(Address::new_root(), error));
+        }
+
+        let mut references = Vec::new();
+        collect_references(&self.state, Address::new_root(), &mut references);
+
+        let mut dependencies = BTreeMap::new();
+        collect_children(&self.state, Address::new_root(), &mut dependencies);
+
+        // Only referring nodes are reported as part of a cycle, their ancestors are implied
+        let mut referrers = BTreeSet::new();
+
+        for (from, to) in references {
+            let (mut root, mut relative_to) = to.relativize(&from);
+            if relative_to.is_root() {
+                let error = anyhow!(
+                    "cycle detected: `{}` is a direct ancestor to `{}`",
+                    to,
+                    from
+                );
+                errors.push(ValidationError::new(from, error));
+            } else if self.state.project(to.clone()).is_err() {
+                let error = anyhow!("undefined reference to `{}`", to);
+                errors.push(ValidationError::new(from, error));
+            } else {
+                // A node depends on the first child of the common root of the reference, as this
+                // is the granularity at which siblings get ordered.
+                root.at(&relative_to.deeper().unwrap());
+                dependencies.entry(from.clone()).or_default().insert(root);
+                referrers.insert(from);
+            }
+        }
+
+        for cycle in strongly_connected(&dependencies) {
+            let mut in_cycle = cycle
+                .into_iter()
+                .filter(|address| referrers.contains(address));
+            if let Some(first) = in_cycle.next() {
+                let others = in_cycle
+                    .map(|address| format!("`{}`", address))
+                    .collect::<Vec<_>>();
+                let error = if others.is_empty() {
+                    anyhow!("cycle detected at `{}`", first)
+                } else {
+                    anyhow!(
+                        "cycle detected between `{}` and {}",
+                        first,
+                        others.join(", ")
+                    )
+                };
+                errors.push(ValidationError::new(first, error));
+            }
+        }
+
+        if errors.is_empty() {
+            // Nothing was caught by crawling, so make sure the tree actually compiles.
+            let state = match self.state.source() {
+                Source::Namespace(namespace) => CompilerState::namespace(namespace),
+                Source::Content(content) => CompilerState::content(content),
+            };
+            let mut compiler = NamespaceCompiler::new_at(state);
+            compiler.directory = self.directory.clone();
+            if let Err(error) = compiler.compile() {
+                errors.push(ValidationError::new(Address::new_root(), error));
+            }
+        }
+
+        errors
+    }
+}
+
+/// A [`Compiler`](Compiler) which crawls the tree like the [`Crawler`](super::Crawler) does, but
+/// records the errors of children instead of bailing out on them.
+struct Validator<'t, 'a> {
+    state: &'t mut CompilerState<'a, Graph>,
+    position: Address,
+    errors: &'t mut Vec<ValidationError>,
+    directory: Option<&'t Path>,
+}
+
+impl<'t, 'a: 't> Validator<'t, 'a> {
+    fn as_at(&mut self, field: &str, content: &'a Content) -> Validator<'_, 'a> {
+        let position = self.position.clone().into_at(field);
+        Validator {
+            state: self.state.entry(field).or_init(content),
+            position,
+            errors: self.errors,
+            directory: self.directory,
+        }
+    }
+
+    fn compile(self) -> Result<()> {
+        match self.state.source() {
+            Source::Namespace(namespace) => namespace.compile(self)?,
+            Source::Content(content) => content.compile(self)?,
+        };
+        Ok(())
+    }
+}
+
+impl<'t, 'a: 't> Compiler<'a> for Validator<'t, 'a> {
+    fn build(&mut self, field: &str, content: &'a Content) -> Result<Graph> {
+        if let Err(error) = self.as_at(field, content).compile() {
+            let position = self.position.clone().into_at(field);
+            self.errors.push(ValidationError::new(position, error));
+        }
+        Ok(Graph::dummy())
+    }
+
+    fn get<S: Into<Address>>(&mut self, target: S) -> Result<Graph> {
+        self.state.refs_mut().insert(target.into());
+        Ok(Graph::dummy())
+    }
+
+    fn directory(&self) -> Option<&Path> {
+        self.directory
+    }
+}
+
+fn collect_references(
+    state: &CompilerState<'_, Graph>,
+    position: Address,
+    references: &mut Vec<(Address, Address)>,
+) {
+    references.extend(state.refs().iter().map(|to| (position.clone(), to.clone())));
+    for (child, child_state) in state.scope().iter() {
+        collect_references(child_state, position.clone().into_at(&child), references);
+    }
+}
+
+/// A node depends on all of its children being built first.
+fn collect_children(
+    state: &CompilerState<'_, Graph>,
+    position: Address,
+    dependencies: &mut BTreeMap<Address, BTreeSet<Address>>,
+) {
+    for (child, child_state) in state.scope().iter() {
+        let child_position = position.clone().into_at(&child);
+        dependencies
+            .entry(position.clone())
+            .or_default()
+            .insert(child_position.clone());
+        collect_children(child_state, child_position, dependencies);
+    }
+}
+
+/// Tarjan's algorithm, returning only the components which form a cycle.
+fn strongly_connected(edges: &BTreeMap<Address, BTreeSet<Address>>) -> Vec<Vec<Address>> {
+    #[derive(Default)]
+    struct Tarjan<'e> {
+        index: usize,
+        indices: BTreeMap<&'e Address, usize>,
+        low_links: BTreeMap<&'e Address, usize>,
+        stack: Vec<&'e Address>,
+        on_stack: BTreeSet<&'e Address>,
+        components: Vec<Vec<Address>>,
+    }
+
+    impl<'e> Tarjan<'e> {
+        fn visit(&mut self, edges: &'e BTreeMap<Address, BTreeSet<Address>>, node: &'e Address) {
+            self.indices.insert(node, self.index);
+            self.low_links.insert(node, self.index);
+            self.index += 1;
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            for next in edges.get(node).into_iter().flatten() {
+                if !self.indices.contains_key(next) {
+                    self.visit(edges, next);
+                    let low_link = self.low_links[node].min(self.low_links[next]);
+                    self.low_links.insert(node, low_link);
+                } else if self.on_stack.contains(next) {
+                    let low_link = self.low_links[node].min(self.indices[next]);
+                    self.low_links.insert(node, low_link);
+                }
+            }
+
+            if self.low_links[node] == self.indices[node] {
+                let mut component = Vec::new();
+                while let Some(top) = self.stack.pop() {
+                    self.on_stack.remove(top);
+                    component.push(top.clone());
+                    if top == node {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan::default();
+    for node in edges.keys() {
+        if !tarjan.indices.contains_key(node) {
+            tarjan.visit(edges, node);
+        }
+    }
+    tarjan.components
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::compile::NamespaceCompiler;
+    use crate::Namespace;
+
+    fn validate(namespace: serde_json::Value) -> Vec<String> {
+        let namespace: Namespace = serde_json::from_value(namespace).unwrap();
+        NamespaceCompiler::new(&namespace)
+            .validate()
+            .into_iter()
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn validate_valid() {
+        let errors = validate(json!({
+            "users": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    }
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "user_id": "@users.content.id"
+                }
+            }
+        }));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn validate_reports_all_errors() {
+        let errors = validate(json!({
+            "users": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "name": {
+                        "type": "string",
+                        "faker": {
+                            "generator": "no_such_generator"
+                        }
+                    },
+                    "friend": "@friends.content.id"
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "user_id": "@users.content.id",
+                    "a": "@posts.content.b",
+                    "b": "@posts.content.a"
+                }
+            }
+        }));
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors.iter().any(
+            |e| e.starts_with("in collection `users` at `content.name`: ")
+                && e.contains("no_such_generator")
+        ));
+        assert!(errors.iter().any(|e| e
+            == "in collection `users` at `content.friend`: undefined reference to `friends.content.id`"));
+        assert!(errors.iter().any(|e| e
+            == "in collection `posts` at `content.user_id`: undefined reference to `users.content.id`"));
+        assert!(errors.iter().any(|e| e
+            == "in collection `posts` at `content.a`: cycle detected between `posts.content.a` and `posts.content.b`"));
+    }
+
+    #[test]
+    fn validate_cross_collection_cycle() {
+        let errors = validate(json!({
+            "a": {
+                "type": "object",
+                "x": "@b.y",
+                "z": {
+                    "type": "bool",
+                    "constant": true
+                }
+            },
+            "b": {
+                "type": "object",
+                "y": {
+                    "type": "bool",
+                    "constant": true
+                },
+                "w": "@a.z"
+            }
+        }));
+        assert_eq!(
+            errors,
+            vec!["in collection `a` at `x`: cycle detected between `a.x` and `b.w`"]
+        );
+    }
+}
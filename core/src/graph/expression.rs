@@ -0,0 +1,500 @@
+//! Evaluation of [`Expression`](crate::schema::content::Expression)s.
+//!
+//! The values of the referenced fields are completed first, then the syntax tree is folded
+//! bottom-up. Any operation or function call involving a `null` evaluates to `null`, so that
+//! expressions over optional fields are themselves optional.
+
+use super::prelude::*;
+
+use crate::schema::content::{BinaryOp, Expr, Expression, Function, UnaryOp};
+
+use chrono::Duration;
+
+pub struct ExpressionNode {
+    expression: Expression,
+    arguments: Vec<Graph>,
+}
+
+impl ExpressionNode {
+    pub fn new(expression: Expression, arguments: Vec<Graph>) -> Self {
+        Self {
+            expression,
+            arguments,
+        }
+    }
+}
+
+impl Generator for ExpressionNode {
+    type Yield = Token;
+    type Return = Result<Value, Error>;
+
+    fn next<R: Rng>(&mut self, rng: &mut R) -> GeneratorState<Self::Yield, Self::Return> {
+        let arguments = self
+            .arguments
+            .iter_mut()
+            .map(|argument| argument.complete(rng))
+            .collect::<Result<Vec<_>, _>>();
+        let value = arguments.and_then(|arguments| {
+            evaluate(self.expression.root(), &arguments)
+                .and_then(Operand::into_value)
+                .map_err(|err| {
+                    failed_crate!(
+                        target: Release,
+                        "could not evaluate expression `{}`: {}",
+                        self.expression,
+                        err
+                    )
+                })
+        });
+        GeneratorState::Complete(value)
+    }
+}
+
+/// A value during evaluation, which can also be a duration.
+#[derive(Debug, Clone, PartialEq)]
+enum Operand {
+    Value(Value),
+    Duration(Duration),
+}
+
+/// Integers are computed on `i128`, keeping track of whether any operand was signed to pick
+/// the type of the result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Num {
+    Int { value: i128, signed: bool },
+    Float(f64),
+}
+
+impl Num {
+    fn from_number(number: Number) -> Self {
+        match number {
+            Number::I8(n) => Self::signed(n.into()),
+            Number::I16(n) => Self::signed(n.into()),
+            Number::I32(n) => Self::signed(n.into()),
+            Number::I64(n) => Self::signed(n.into()),
+            Number::I128(n) => Self::signed(n),
+            Number::U8(n) => Self::unsigned(n.into()),
+            Number::U16(n) => Self::unsigned(n.into()),
+            Number::U32(n) => Self::unsigned(n.into()),
+            Number::U64(n) => Self::unsigned(n.into()),
+            Number::U128(n) => match i128::try_from(n) {
+                Ok(n) => Self::unsigned(n),
+                Err(_) => Self::Float(n as f64),
+            },
+            Number::F32(n) => Self::Float(n.into_inner().into()),
+            Number::F64(n) => Self::Float(n.into_inner()),
+        }
+    }
+
+    fn signed(value: i128) -> Self {
+        Self::Int {
+            value,
+            signed: true,
+        }
+    }
+
+    fn unsigned(value: i128) -> Self {
+        Self::Int {
+            value,
+            signed: false,
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Int { value, .. } => value as f64,
+            Self::Float(value) => value,
+        }
+    }
+
+    fn into_number(self) -> Result<Number, Error> {
+        match self {
+            Self::Int {
+                value,
+                signed: false,
+            } if value >= 0 => u64::try_from(value).map(Number::from).ok(),
+            Self::Int { value, .. } => i64::try_from(value).map(Number::from).ok(),
+            Self::Float(value) if value.is_finite() => Some(Number::from(value)),
+            Self::Float(_) => None,
+        }
+        .ok_or_else(|| failed_crate!(target: Release, "numeric overflow"))
+    }
+
+    fn binary(self, op: BinaryOp, other: Self) -> Result<Self, Error> {
+        match (self, other) {
+            (
+                Self::Int {
+                    value: left,
+                    signed: left_signed,
+                },
+                Self::Int {
+                    value: right,
+                    signed: right_signed,
+                },
+            ) => {
+                let value = match op {
+                    BinaryOp::Add => left.checked_add(right),
+                    BinaryOp::Sub => left.checked_sub(right),
+                    BinaryOp::Mul => left.checked_mul(right),
+                    BinaryOp::Div | BinaryOp::Rem if right == 0 => {
+                        return Err(failed_crate!(target: Release, "division by zero"))
+                    }
+                    BinaryOp::Div => left.checked_div(right),
+                    BinaryOp::Rem => left.checked_rem(right),
+                }
+                .ok_or_else(|| failed_crate!(target: Release, "numeric overflow"))?;
+                Ok(Self::Int {
+                    value,
+                    signed: left_signed || right_signed,
+                })
+            }
+            (left, right) => {
+                let (left, right) = (left.as_f64(), right.as_f64());
+                let value = match op {
+                    BinaryOp::Add => left + right,
+                    BinaryOp::Sub => left - right,
+                    BinaryOp::Mul => left * right,
+                    BinaryOp::Div => left / right,
+                    BinaryOp::Rem => left % right,
+                };
+                Ok(Self::Float(value))
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn into_value(self) -> Result<Value, Error> {
+        match self {
+            Self::Value(value) => Ok(value),
+            Self::Duration(_) => Err(failed_crate!(
+                target: Release,
+                "the result is a duration, which is not a value: add it to a date_time instead"
+            )),
+        }
+    }
+
+    fn from_num(num: Num) -> Result<Self, Error> {
+        num.into_number()
+            .map(|number| Self::Value(Value::Number(number)))
+    }
+
+    fn type_(&self) -> &'static str {
+        match self {
+            Self::Value(value) => value.type_(),
+            Self::Duration(_) => "Duration",
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self, Self::Value(Value::Null(_)))
+    }
+
+    fn as_num(&self, function: &str) -> Result<Num, Error> {
+        match self {
+            Self::Value(Value::Number(number)) => Ok(Num::from_number(*number)),
+            otherwise => Err(failed_crate!(
+                target: Release,
+                "`{}` expects a number, found '{}'",
+                function,
+                otherwise.type_()
+            )),
+        }
+    }
+
+    fn into_string(self, function: &str) -> Result<String, Error> {
+        match self {
+            Self::Value(value @ (Value::String(_) | Value::Number(_) | Value::DateTime(_))) => {
+                value.try_into()
+            }
+            Self::Value(Value::Bool(b)) => Ok(b.to_string()),
+            otherwise => Err(failed_crate!(
+                target: Release,
+                "`{}` cannot convert a '{}' to a string",
+                function,
+                otherwise.type_()
+            )),
+        }
+    }
+}
+
+fn evaluate(expr: &Expr, arguments: &[Value]) -> Result<Operand, Error> {
+    match expr {
+        Expr::Literal(value) => Ok(Operand::Value(value.clone())),
+        Expr::Reference(idx) => Ok(Operand::Value(arguments[*idx].clone())),
+        Expr::Unary(UnaryOp::Neg, inner) => match evaluate(inner, arguments)? {
+            Operand::Duration(duration) => Ok(Operand::Duration(-duration)),
+            operand if operand.is_null() => Ok(operand),
+            operand => {
+                let num = operand.as_num("-")?;
+                Operand::from_num(Num::signed(0).binary(BinaryOp::Sub, num)?)
+            }
+        },
+        Expr::Binary(op, left, right) => {
+            let left = evaluate(left, arguments)?;
+            let right = evaluate(right, arguments)?;
+            binary(*op, left, right)
+        }
+        Expr::Call(function, args) => {
+            let args = args
+                .iter()
+                .map(|arg| evaluate(arg, arguments))
+                .collect::<Result<Vec<_>, _>>()?;
+            call(*function, args)
+        }
+    }
+}
+
+fn binary(op: BinaryOp, left: Operand, right: Operand) -> Result<Operand, Error> {
+    if left.is_null() || right.is_null() {
+        return Ok(Operand::Value(Value::Null(())));
+    }
+
+    match (op, left, right) {
+        (_, Operand::Value(Value::Number(left)), Operand::Value(Value::Number(right))) => {
+            Operand::from_num(Num::from_number(left).binary(op, Num::from_number(right))?)
+        }
+        (BinaryOp::Add, left @ Operand::Value(Value::String(_)), right @ Operand::Value(_))
+        | (BinaryOp::Add, left @ Operand::Value(_), right @ Operand::Value(Value::String(_))) => {
+            let mut left = left.into_string("+")?;
+            left.push_str(&right.into_string("+")?);
+            Ok(Operand::Value(Value::String(left)))
+        }
+        (
+            BinaryOp::Add,
+            Operand::Value(Value::DateTime(date_time)),
+            Operand::Duration(duration),
+        )
+        | (
+            BinaryOp::Add,
+            Operand::Duration(duration),
+            Operand::Value(Value::DateTime(date_time)),
+        ) => Ok(Operand::Value(Value::DateTime(ChronoValueAndFormat {
+            value: date_time
+                .value
+                .checked_add_signed(duration)
+                .ok_or_else(date_time_overflow)?,
+            format: date_time.format,
+        }))),
+        (
+            BinaryOp::Sub,
+            Operand::Value(Value::DateTime(date_time)),
+            Operand::Duration(duration),
+        ) => Ok(Operand::Value(Value::DateTime(ChronoValueAndFormat {
+            value: date_time
+                .value
+                .checked_sub_signed(duration)
+                .ok_or_else(date_time_overflow)?,
+            format: date_time.format,
+        }))),
+        (BinaryOp::Add, Operand::Duration(left), Operand::Duration(right)) => Ok(
+            Operand::Duration(left.checked_add(&right).ok_or_else(duration_overflow)?),
+        ),
+        (BinaryOp::Sub, Operand::Duration(left), Operand::Duration(right)) => Ok(
+            Operand::Duration(left.checked_sub(&right).ok_or_else(duration_overflow)?),
+        ),
+        (BinaryOp::Mul, Operand::Duration(duration), Operand::Value(Value::Number(number)))
+        | (BinaryOp::Mul, Operand::Value(Value::Number(number)), Operand::Duration(duration)) => {
+            let factor = Num::from_number(number).as_f64();
+            Ok(Operand::Duration(scale(duration, factor)?))
+        }
+        (op, left, right) => Err(failed_crate!(
+            target: Release,
+            "unsupported operation: '{}' {} '{}'",
+            left.type_(),
+            op,
+            right.type_()
+        )),
+    }
+}
+
+fn scale(duration: Duration, factor: f64) -> Result<Duration, Error> {
+    let milliseconds = duration.num_milliseconds() as f64 * factor;
+    if milliseconds.is_finite() && milliseconds.abs() < i64::MAX as f64 {
+        Ok(Duration::milliseconds(milliseconds.round() as i64))
+    } else {
+        Err(duration_overflow())
+    }
+}
+
+fn duration_overflow() -> Error {
+    failed_crate!(target: Release, "duration overflow")
+}
+
+fn date_time_overflow() -> Error {
+    failed_crate!(target: Release, "date time out of range")
+}
+
+fn call(function: Function, args: Vec<Operand>) -> Result<Operand, Error> {
+    if args.iter().any(Operand::is_null) {
+        return Ok(Operand::Value(Value::Null(())));
+    }
+
+    let name = function.name();
+    let mut args = args.into_iter();
+    // Unary functions have been checked to have exactly one argument when parsing
+    let mut arg = || args.next().unwrap();
+
+    let float = |arg: Operand, f: fn(f64) -> f64| -> Result<Operand, Error> {
+        match arg.as_num(name)? {
+            int @ Num::Int { .. } => Operand::from_num(int),
+            Num::Float(value) => Operand::from_num(Num::Float(f(value))),
+        }
+    };
+
+    let duration = |arg: Operand, unit: f64| -> Result<Operand, Error> {
+        let amount = arg.as_num(name)?.as_f64();
+        Ok(Operand::Duration(scale(
+            Duration::seconds(1),
+            amount * unit,
+        )?))
+    };
+
+    match function {
+        Function::Abs => match arg().as_num(name)? {
+            Num::Int { value, signed } => Operand::from_num(Num::Int {
+                value: value.abs(),
+                signed,
+            }),
+            Num::Float(value) => Operand::from_num(Num::Float(value.abs())),
+        },
+        Function::Round => float(arg(), f64::round),
+        Function::Floor => float(arg(), f64::floor),
+        Function::Ceil => float(arg(), f64::ceil),
+        Function::Min | Function::Max => {
+            let mut best: Option<Num> = None;
+            for arg in args {
+                let num = arg.as_num(name)?;
+                best = match best {
+                    Some(best)
+                        if (function == Function::Min) == (best.as_f64() <= num.as_f64()) =>
+                    {
+                        Some(best)
+                    }
+                    _ => Some(num),
+                };
+            }
+            Operand::from_num(best.unwrap())
+        }
+        Function::Lower => Ok(Operand::Value(Value::String(
+            arg().into_string(name)?.to_lowercase(),
+        ))),
+        Function::Upper => Ok(Operand::Value(Value::String(
+            arg().into_string(name)?.to_uppercase(),
+        ))),
+        Function::Trim => Ok(Operand::Value(Value::String(
+            arg().into_string(name)?.trim().to_string(),
+        ))),
+        Function::Concat => Ok(Operand::Value(Value::String(
+            args.map(|arg| arg.into_string(name))
+                .collect::<Result<String, _>>()?,
+        ))),
+        Function::Seconds => duration(arg(), 1.),
+        Function::Minutes => duration(arg(), 60.),
+        Function::Hours => duration(arg(), 3600.),
+        Function::Days => duration(arg(), 86400.),
+        Function::Weeks => duration(arg(), 604800.),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use chrono::NaiveDate;
+
+    fn eval(source: &str, arguments: &[Value]) -> Result<Value, Error> {
+        let expression: Expression = source.parse().unwrap();
+        evaluate(expression.root(), arguments).and_then(Operand::into_value)
+    }
+
+    fn num<N: Into<Number>>(n: N) -> Value {
+        Value::Number(n.into())
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> Value {
+        Value::DateTime(ChronoValueAndFormat {
+            value: ChronoValue::NaiveDate(NaiveDate::from_ymd_opt(y, m, d).unwrap()),
+            format: "%Y-%m-%d".into(),
+        })
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            eval("@a.b * @a.c", &[num(3u64), num(4u64)]).unwrap(),
+            num(12u64)
+        );
+        assert_eq!(
+            eval("@a.b * @a.c", &[num(1.5), num(4u64)]).unwrap(),
+            num(6.)
+        );
+        assert_eq!(eval("@a.b - 10", &[num(3u64)]).unwrap(), num(-7i64));
+        assert_eq!(eval("-(7 / 2) % 2", &[]).unwrap(), num(-1i64));
+        assert_eq!(
+            eval("round(@a.b) + max(1, 2.5, -3)", &[num(1.4)]).unwrap(),
+            num(3.5)
+        );
+        assert!(eval("1 / 0", &[]).is_err());
+        assert!(eval("@a.b * 2", &[num(u64::MAX)]).is_err());
+    }
+
+    #[test]
+    fn strings() {
+        assert_eq!(
+            eval(
+                "upper(@a.b) + '-' + @a.c",
+                &[Value::from("ab".to_string()), num(42u64)]
+            )
+            .unwrap(),
+            Value::from("AB-42".to_string())
+        );
+        assert_eq!(
+            eval("concat(trim(' x '), 1, true)", &[]).unwrap(),
+            Value::from("x1true".to_string())
+        );
+        assert!(eval("'a' * 2", &[]).is_err());
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(
+            eval("@a.b + days(@a.c)", &[date(2021, 2, 27), num(3u64)]).unwrap(),
+            date(2021, 3, 2)
+        );
+        assert_eq!(
+            eval("@a.b - weeks(1) + hours(48)", &[date(2021, 3, 8)]).unwrap(),
+            date(2021, 3, 3)
+        );
+        assert_eq!(
+            eval("@a.b + 2 * days(1)", &[date(2021, 3, 8)]).unwrap(),
+            date(2021, 3, 10)
+        );
+        assert!(eval("days(1)", &[]).is_err());
+        assert!(eval("@a.b + 1", &[date(2021, 3, 8)]).is_err());
+    }
+
+    #[test]
+    fn dates_overflow() {
+        assert!(eval("@a.b + days(1000000000)", &[date(2021, 3, 8)]).is_err());
+        assert!(eval("@a.b - days(1000000000)", &[date(2021, 3, 8)]).is_err());
+        assert!(eval(
+            "@a.b + (days(100000000000) + days(100000000000))",
+            &[date(2021, 3, 8)]
+        )
+        .is_err());
+        assert!(eval(
+            "@a.b + (days(100000000000) - days(-100000000000))",
+            &[date(2021, 3, 8)]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn nulls() {
+        assert_eq!(
+            eval("@a.b * 2 + abs(@a.b)", &[Value::Null(())]).unwrap(),
+            Value::Null(())
+        );
+    }
+}
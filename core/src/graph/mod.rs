@@ -6,8 +6,9 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use sqlx::mysql::MySqlTypeInfo;
 use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo};
 use sqlx::{encode::IsNull, Encode, Type};
-use sqlx::{MySql, Postgres};
+use sqlx::{MySql, Postgres, Sqlite};
 
 use synth_gen::prelude::*;
 
@@ -20,14 +21,16 @@ pub mod null;
 pub use null::NullNode;
 
 pub mod string;
-pub use string::{Format, FormatArgs, RandFaker, RandomString, StringNode, Truncated, UuidGen};
+pub use string::{
+    Format, FormatArgs, PinnedLocale, RandFaker, RandomString, StringNode, Truncated, UuidGen,
+};
 
 pub mod date_time;
 pub use date_time::{DateTimeNode, RandomDateTime};
 
 pub mod number;
 pub use number::{
-    Incrementing, NumberNode, RandomF64, RandomI64, RandomU64, StandardFloatRangeStep,
+    Distributed, Incrementing, NumberNode, RandomF64, RandomI64, RandomU64, StandardFloatRangeStep,
     StandardIntRangeStep,
 };
 
@@ -46,9 +49,18 @@ pub use object::{KeyValueOrNothing, ObjectNode};
 pub mod unique;
 pub use unique::UniqueNode;
 
+pub mod relation;
+pub use relation::{Relation, RelationNode};
+
 pub mod one_of;
 pub(crate) mod series;
 
+pub mod expression;
+pub use expression::ExpressionNode;
+
+pub mod switch;
+pub use switch::SwitchNode;
+
 pub mod json;
 
 use crate::graph::series::SeriesNode;
@@ -365,8 +377,20 @@ impl Type<MySql> for Value {
     }
 }
 
+impl Type<Sqlite> for Value {
+    fn type_info() -> SqliteTypeInfo {
+        <String as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(_ty: &SqliteTypeInfo) -> bool {
+        unreachable!("This should never happen. Please reach out to https://github.com/getsynth/synth/issues if it does.")
+    }
+}
+
 impl Value {
-    fn to_postgres_string(&self) -> String {
+    /// The text representation of this value as understood by Postgres, e.g. `{1, 2}` for an
+    /// array.
+    pub fn to_postgres_string(&self) -> String {
         match self {
             Self::Array(arr) => {
                 let (typ, _) = self.get_postgres_type();
@@ -594,6 +618,50 @@ impl Encode<'_, MySql> for Value {
     }
 }
 
+impl<'q> Encode<'q, Sqlite> for Value {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        match self {
+            Value::Null(_) => IsNull::Yes,
+            Value::Bool(b) => <bool as Encode<'_, Sqlite>>::encode_by_ref(b, buf),
+            Value::Number(num) => match *num {
+                Number::I8(i) => <i8 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                Number::I16(i) => <i16 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                Number::I32(i) => <i32 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                Number::I64(i) => <i64 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                // SQLite integers are at most 64 bits, larger numbers are stored as text
+                Number::I128(i) => <String as Encode<'_, Sqlite>>::encode(i.to_string(), buf),
+                Number::U8(i) => <u8 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                Number::U16(i) => <u16 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                Number::U32(i) => <u32 as Encode<'_, Sqlite>>::encode_by_ref(&i, buf),
+                Number::U64(i) => <i64 as Encode<'_, Sqlite>>::encode_by_ref(&(i as i64), buf),
+                Number::U128(i) => <String as Encode<'_, Sqlite>>::encode(i.to_string(), buf),
+                Number::F32(f) => <f32 as Encode<'_, Sqlite>>::encode_by_ref(&f, buf),
+                Number::F64(f) => <f64 as Encode<'_, Sqlite>>::encode_by_ref(&f, buf),
+            },
+            Value::String(s) => <String as Encode<'_, Sqlite>>::encode_by_ref(s, buf),
+            Value::DateTime(ChronoValueAndFormat { value, .. }) => match value {
+                ChronoValue::NaiveDate(nd) => {
+                    <NaiveDate as Encode<'_, Sqlite>>::encode_by_ref(nd, buf)
+                }
+                ChronoValue::NaiveTime(nt) => {
+                    <NaiveTime as Encode<'_, Sqlite>>::encode_by_ref(nt, buf)
+                }
+                ChronoValue::NaiveDateTime(ndt) => {
+                    <NaiveDateTime as Encode<'_, Sqlite>>::encode_by_ref(ndt, buf)
+                }
+                ChronoValue::DateTime(dt) => {
+                    <DateTime<FixedOffset> as Encode<'_, Sqlite>>::encode_by_ref(dt, buf)
+                }
+            },
+            // SQLite has no JSON or array types, they are stored as JSON text
+            Value::Object(_) | Value::Array(_) => <String as Encode<'_, Sqlite>>::encode(
+                json::synth_val_to_json(self.clone()).to_string(),
+                buf,
+            ),
+        }
+    }
+}
+
 #[allow(unused)]
 impl Value {
     pub fn is_null(&self) -> bool {
@@ -777,9 +845,12 @@ derive_generator!(
         OneOf(OneOfNode),
         Series(SeriesNode),
         Unique(UniqueNode),
+        Relation(RelationNode),
         Link(Box<LinkNode>),
         Hidden(Box<Graph>),
         Iter(IterNode),
+        Expression(ExpressionNode),
+        Switch(SwitchNode),
     }
 );
 
@@ -3,7 +3,9 @@ use super::prelude::*;
 
 use rand::distributions::uniform::SampleRange;
 
-use num::{CheckedAdd, One, Zero};
+use num::{CheckedAdd, NumCast, One, Zero};
+
+use crate::Partition;
 
 use std::ops::{Bound, RangeBounds};
 
@@ -208,6 +210,33 @@ macro_rules! standard_int_range_step_impl {
                     Ok(Self { range, low: low as $larger, step })
                 }
             }
+
+            /// The number of values in the range, or `None` if it does not fit in a `u64`.
+            pub fn num_values(&self) -> Option<u64> {
+                let (first, last) = self.num_steps_bounds();
+                ((last - first) as u64).checked_add(1)
+            }
+
+            /// The `index`th smallest value in the range, where `index` is less than its number of values.
+            pub fn nth(&self, index: u64) -> $target {
+                let num_steps = self.num_steps_bounds().0 + index as $unsigned;
+                ((num_steps * self.step) as $larger + self.low) as $target
+            }
+
+            fn num_steps_bounds(&self) -> ($unsigned, $unsigned) {
+                // Both bounds are finite and the range is not empty
+                let first = match self.range.low {
+                    Bound::Included(first) => first,
+                    Bound::Excluded(low) => low + 1,
+                    Bound::Unbounded => 0,
+                };
+                let last = match self.range.high {
+                    Bound::Included(last) => last,
+                    Bound::Excluded(high) => high - 1,
+                    Bound::Unbounded => <$unsigned>::MAX,
+                };
+                (first, last)
+            }
         }
 
         impl Distribution<$target> for StandardIntRangeStep<$unsigned, $larger> {
@@ -275,6 +304,33 @@ macro_rules! standard_float_range_step_impl {
                     Ok(Self { range, low, step })
                 }
             }
+
+            /// The number of values in the range, or `None` if it has no 'step' or if it holds
+            /// too many values to tell them apart.
+            pub fn num_values(&self) -> Option<u64> {
+                let (low, step) = (self.low?, self.step?);
+                let (high, include_high) = match self.range.high {
+                    Bound::Included(high) => (high, true),
+                    Bound::Excluded(high) => (high, false),
+                    Bound::Unbounded => return None,
+                };
+                let mut last = ((high - low) / step).floor();
+                if last.is_nan() || last >= (1u64 << <$target>::MANTISSA_DIGITS) as $target {
+                    return None;
+                }
+                let value = low + last * step;
+                if value > high || (value == high && !include_high) {
+                    last -= 1.;
+                }
+                Some((last + 1.) as u64)
+            }
+
+            /// The `index`th smallest value in the range, where `index` is less than its number of values.
+            pub fn nth(&self, index: u64) -> $target {
+                let low = self.low.expect("a float range with a number of values has a step");
+                let step = self.step.expect("a float range with a number of values has a step");
+                low + index as $target * step
+            }
         }
 
         impl Distribution<$target> for StandardFloatRangeStep<$target> {
@@ -295,6 +351,164 @@ macro_rules! standard_float_range_step_impl {
 standard_float_range_step_impl! { f32 }
 standard_float_range_step_impl! { f64 }
 
+/// A statistical distribution of floats which are then converted to `N`, rounding them to the
+/// nearest integer for integer types, and clamped to optional inclusive bounds.
+pub struct Distributed<N> {
+    inner: ContinuousDistribution,
+    low: Option<N>,
+    high: Option<N>,
+}
+
+enum ContinuousDistribution {
+    Normal(rand_distr::Normal<f64>),
+    LogNormal(rand_distr::LogNormal<f64>),
+    Exponential(rand_distr::Exp<f64>),
+    Poisson(rand_distr::Poisson<f64>),
+    Zipf(rand_distr::Zipf<f64>),
+}
+
+impl Distribution<f64> for ContinuousDistribution {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            Self::Normal(dist) => dist.sample(rng),
+            Self::LogNormal(dist) => dist.sample(rng),
+            Self::Exponential(dist) => dist.sample(rng),
+            Self::Poisson(dist) => dist.sample(rng),
+            Self::Zipf(dist) => dist.sample(rng),
+        }
+    }
+}
+
+impl<N: PartialOrd + std::fmt::Display> Distributed<N> {
+    fn new(inner: ContinuousDistribution, low: Option<N>, high: Option<N>) -> anyhow::Result<Self> {
+        if let (Some(low), Some(high)) = (low.as_ref(), high.as_ref()) {
+            if low > high {
+                return Err(anyhow!(
+                    "distribution cannot be clamped with 'low'={} > 'high'={}",
+                    low,
+                    high
+                ));
+            }
+        }
+        Ok(Self { inner, low, high })
+    }
+
+    pub fn normal(normal: Normal<N>) -> anyhow::Result<Self> {
+        let Normal {
+            mean,
+            std_dev,
+            low,
+            high,
+        } = normal;
+        if !mean.is_finite() || !std_dev.is_finite() || std_dev < 0. {
+            return Err(anyhow!(
+                "normal distribution with mean={} std_dev={} is invalid: 'std_dev' must be a positive finite number",
+                mean,
+                std_dev
+            ));
+        }
+        let dist = rand_distr::Normal::new(mean, std_dev)?;
+        Self::new(ContinuousDistribution::Normal(dist), low, high)
+    }
+
+    pub fn log_normal(log_normal: LogNormal<N>) -> anyhow::Result<Self> {
+        let LogNormal {
+            mean,
+            std_dev,
+            low,
+            high,
+        } = log_normal;
+        if !mean.is_finite() || !std_dev.is_finite() || std_dev < 0. {
+            return Err(anyhow!(
+                "log-normal distribution with mean={} std_dev={} is invalid: 'std_dev' must be a positive finite number",
+                mean,
+                std_dev
+            ));
+        }
+        let dist = rand_distr::LogNormal::new(mean, std_dev)?;
+        Self::new(ContinuousDistribution::LogNormal(dist), low, high)
+    }
+
+    pub fn exponential(exponential: Exponential<N>) -> anyhow::Result<Self> {
+        let Exponential { rate, low, high } = exponential;
+        if !rate.is_finite() || rate <= 0. {
+            return Err(anyhow!(
+                "exponential distribution with rate={} is invalid: 'rate' must be a strictly positive finite number",
+                rate
+            ));
+        }
+        let dist = rand_distr::Exp::new(rate)?;
+        Self::new(ContinuousDistribution::Exponential(dist), low, high)
+    }
+
+    pub fn poisson(poisson: Poisson<N>) -> anyhow::Result<Self> {
+        let Poisson { lambda, low, high } = poisson;
+        if !lambda.is_finite() || lambda <= 0. {
+            return Err(anyhow!(
+                "poisson distribution with lambda={} is invalid: 'lambda' must be a strictly positive finite number",
+                lambda
+            ));
+        }
+        let dist = rand_distr::Poisson::new(lambda)?;
+        Self::new(ContinuousDistribution::Poisson(dist), low, high)
+    }
+
+    pub fn zipf(zipf: Zipf<N>) -> anyhow::Result<Self> {
+        let Zipf {
+            n,
+            exponent,
+            low,
+            high,
+        } = zipf;
+        if n == 0 || !exponent.is_finite() || exponent < 0. {
+            return Err(anyhow!(
+                "zipf distribution with n={} exponent={} is invalid: 'n' must be at least 1 and 'exponent' a positive finite number",
+                n,
+                exponent
+            ));
+        }
+        let dist = rand_distr::Zipf::new(n, exponent)?;
+        Self::new(ContinuousDistribution::Zipf(dist), low, high)
+    }
+}
+
+impl<N: PartialOrd + Copy> Distributed<N> {
+    fn clamp(&self, value: N) -> N {
+        match (self.low, self.high) {
+            (Some(low), _) if value < low => low,
+            (_, Some(high)) if value > high => high,
+            _ => value,
+        }
+    }
+}
+
+/// Samples are converted with `as`, which saturates at the bounds of `$target`.
+macro_rules! distributed_impl {
+    { int $($target:ty),* } => {
+        $(
+            impl Distribution<$target> for Distributed<$target> {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $target {
+                    let value: f64 = self.inner.sample(rng);
+                    self.clamp(value.round() as $target)
+                }
+            }
+        )*
+    };
+    { float $($target:ty),* } => {
+        $(
+            impl Distribution<$target> for Distributed<$target> {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $target {
+                    let value: f64 = self.inner.sample(rng);
+                    self.clamp(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+distributed_impl! { int u32, u64, i8, i16, i32, i64 }
+distributed_impl! { float f32, f64 }
+
 pub struct Incrementing<N = i64> {
     count: N,
     step: N,
@@ -337,6 +551,26 @@ impl<N> Incrementing<N> {
     }
 }
 
+impl<N> Incrementing<N>
+where
+    N: CheckedAdd + NumCast + Copy,
+{
+    /// Count from `start_at` over the values of the `partition`, i.e. every `partition.count`-th
+    /// value starting at the `partition.index`-th value.
+    pub fn new_partition(start_at: N, partition: Partition) -> anyhow::Result<Self> {
+        let out_of_range = || {
+            anyhow!(
+                "cannot split the values of an incrementing generator in {} partitions: try specifying the \'subtype\' parameter with a larger numerical primitive (e.g. u64)",
+                partition.count
+            )
+        };
+        let index = N::from(partition.index).ok_or_else(out_of_range)?;
+        let step = N::from(partition.count).ok_or_else(out_of_range)?;
+        let start_at = start_at.checked_add(&index).ok_or_else(out_of_range)?;
+        Ok(Self::new_at_by(start_at, step))
+    }
+}
+
 impl<N> Default for Incrementing<N>
 where
     N: Zero + One,
@@ -382,6 +616,7 @@ macro_rules! number_node {
             $rand:ident (
                 $range:ident<$dist:ty> as $new_range:ident,
                 $constant:ident as $new_constant:ident,
+                $distributed:ident as $new_distributed:ident,
                 $(
                     $categorical:ident as $new_categorical:ident
                 )?,
@@ -406,6 +641,7 @@ macro_rules! number_node {
                 pub enum $rand {
                     $range(OnceInfallible<Random<$ty, $dist>>),
                     $constant(OnceInfallible<Yield<$ty>>),
+                    $distributed(OnceInfallible<Random<$ty, Distributed<$ty>>>),
                     $($categorical(OnceInfallible<Random<$ty, Categorical<$ty>>>),)?
                     $($incrementing(TryOnce<Incrementing<$ty>>),)?
                 }
@@ -421,6 +657,10 @@ macro_rules! number_node {
                     Self::$constant(Yield::wrap(value).infallible().try_once())
                 }
 
+                pub fn $new_distributed(dist: Distributed<$ty>) -> Self {
+                    Self::$distributed(Random::new_with(dist).infallible().try_once())
+                }
+
                 $(
                     pub fn $new_categorical(cat: Categorical<$ty>) -> Self {
                         Self::$categorical(Random::new_with(cat).infallible().try_once())
@@ -447,44 +687,52 @@ number_node!(
     RandomU64 (
         U64Range<StandardIntRangeStep<u64, u64>> as range,
         U64Constant as constant,
+        U64Distributed as distributed,
         U64Categorical as categorical,
         Incrementing as incrementing,
     ) for u64,
     RandomI64 (
         I64Range<StandardIntRangeStep<u64, i128>> as range,
         I64Constant as constant,
+        I64Distributed as distributed,
         I64Categorical as categorical,
         Incrementing as incrementing,
     ) for i64,
     RandomF64 (
         F64Range<StandardFloatRangeStep<f64>> as range,
-        F64Constant as constant,,,
+        F64Constant as constant,
+        F64Distributed as distributed,,,
     ) for f64,
     RandomU32 (
         U32Range<StandardIntRangeStep<u32, u32>> as range,
         U32Constant as constant,
+        U32Distributed as distributed,
         U32Categorical as categorical,
         Incrementing as incrementing,
     ) for u32,
     RandomI32 (
         I32Range<StandardIntRangeStep<u32, i64>> as range,
         I32Constant as constant,
+        I32Distributed as distributed,
         I32Categorical as categorical,
         Incrementing as incrementing,
     ) for i32,
     RandomF32 (
         F32Range<StandardFloatRangeStep<f32>> as range,
-        F32Constant as constant,,,
+        F32Constant as constant,
+        F32Distributed as distributed,,,
     ) for f32,
     RandomI16 (
         I16Range<StandardIntRangeStep<u16, i32>> as range,
         I16Constant as constant,
+        I16Distributed as distributed,
         I16Categorical as categorical,
         Incrementing as incrementing,
     ) for i16,
     RandomI8 (
         I8Range<StandardIntRangeStep<u8, i16>> as range,
         I8Constant as constant,
+        I8Distributed as distributed,
         I8Categorical as categorical,
         Incrementing as incrementing,
     ) for i8,
@@ -494,6 +742,7 @@ number_node!(
 pub mod test {
     use super::*;
     use rand::rngs::OsRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_overflow_behaviour() {
@@ -512,4 +761,82 @@ pub mod test {
 
         assert!(incrementing.next(&mut rng).into_complete().is_err())
     }
+
+    #[test]
+    fn test_incrementing_partition() {
+        let mut rng = OsRng;
+
+        let partition = Partition::new(1, 3).unwrap();
+        let mut incrementing = Incrementing::<u32>::new_partition(1, partition).unwrap();
+        for i in [2, 5, 8] {
+            assert_eq!(i, incrementing.next(&mut rng).into_yielded().unwrap())
+        }
+
+        let partition = Partition::new(299, 300).unwrap();
+        assert!(Incrementing::<u8>::new_partition(1, partition).is_err());
+        assert!(Partition::new(3, 3).is_err());
+    }
+
+    #[test]
+    fn test_distributed() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+
+        let normal = Distributed::<f64>::normal(Normal {
+            mean: 50.,
+            std_dev: 10.,
+            low: None,
+            high: None,
+        })
+        .unwrap();
+        let samples: Vec<f64> = (0..10000).map(|_| normal.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        assert!((mean - 50.).abs() < 1., "mean={}", mean);
+
+        let clamped = Distributed::<i32>::normal(Normal {
+            mean: 0.,
+            std_dev: 100.,
+            low: Some(-10),
+            high: Some(10),
+        })
+        .unwrap();
+        assert!((0..1000)
+            .map(|_| clamped.sample(&mut rng))
+            .all(|value| (-10..=10).contains(&value)));
+
+        let zipf = Distributed::<u64>::zipf(Zipf {
+            n: 10,
+            exponent: 1.,
+            low: None,
+            high: None,
+        })
+        .unwrap();
+        assert!((0..1000)
+            .map(|_| zipf.sample(&mut rng))
+            .all(|value| (1..=10).contains(&value)));
+
+        let poisson = Distributed::<u64>::poisson(Poisson {
+            lambda: 3.,
+            low: Some(1),
+            high: None,
+        })
+        .unwrap();
+        let samples: Vec<u64> = (0..10000).map(|_| poisson.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|value| *value >= 1));
+        let mean = samples.iter().sum::<u64>() as f64 / samples.len() as f64;
+        assert!((mean - 3.).abs() < 0.2, "mean={}", mean);
+
+        assert!(Distributed::<u32>::exponential(Exponential {
+            rate: 0.,
+            low: None,
+            high: None
+        })
+        .is_err());
+        assert!(Distributed::<f32>::log_normal(LogNormal {
+            mean: 0.,
+            std_dev: 1.,
+            low: Some(2.),
+            high: Some(1.)
+        })
+        .is_err());
+    }
 }
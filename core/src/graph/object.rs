@@ -1,5 +1,6 @@
 use super::prelude::*;
 
+use super::PinnedLocale;
 use std::collections::BTreeMap;
 use synth_gen::value::Map;
 
@@ -54,11 +55,19 @@ impl KeyValueOrNothing {
     }
 }
 
-pub struct ObjectNode(Map<Chain<KeyValueOrNothing>>);
+pub struct ObjectNode(Map<Chain<KeyValueOrNothing>>, Option<PinnedLocale>);
 
 impl FromIterator<KeyValueOrNothing> for ObjectNode {
     fn from_iter<T: IntoIterator<Item = KeyValueOrNothing>>(iter: T) -> Self {
-        Self(Chain::from_iter(iter).into_map(None))
+        Self(Chain::from_iter(iter).into_map(None), None)
+    }
+}
+
+impl ObjectNode {
+    /// Release the locale pinned for each object once it is complete.
+    pub fn with_pinned_locale(mut self, pinned: Option<PinnedLocale>) -> Self {
+        self.1 = pinned;
+        self
     }
 }
 
@@ -78,7 +87,11 @@ impl Generator for ObjectNode {
             .map(|p| p.key.clone())
             .collect();
 
-        self.0.next(rng).map_complete(|kv| {
+        let next = self.0.next(rng);
+        if let (true, Some(pinned)) = (next.is_complete(), &self.1) {
+            pinned.release();
+        }
+        next.map_complete(|kv| {
             kv.into_iter()
                 .filter(|p| match p {
                     Some((k, _)) => !hidden_fields.contains(k),
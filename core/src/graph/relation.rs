@@ -0,0 +1,457 @@
+//! Relations between the elements of an array and the values they reference.
+//!
+//! When the array starts, [`RelationNode::Children`](RelationNode::Children) reads all the
+//! values of the parents, draws how many children each of them has and returns the total as the
+//! length of the array. Each element then takes the value of its parent from
+//! [`RelationNode::Parent`](RelationNode::Parent), in the order of the parents.
+//!
+//! Each element can also reference a partner (as in a join table), which is drawn from the
+//! values of another reference without replacement for each parent, so that a parent is never
+//! paired with the same partner twice.
+
+use super::prelude::*;
+
+use crate::compile::Link;
+use crate::graph::{unsigned_from_ok, LinkNode};
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+type Values = Link<Graph, Token, Result<Value, Error>>;
+
+struct Partner {
+    values: Option<Values>,
+    /// The partner of each child.
+    children: Vec<Value>,
+}
+
+#[derive(Default)]
+struct RelationImpl {
+    parents: Option<Values>,
+    cardinality: Option<Box<Graph>>,
+    partners: Vec<Partner>,
+    /// The parent of each child.
+    children: Vec<Value>,
+    generation: usize,
+}
+
+/// The state shared by the nodes of a relation, which are compiled separately.
+#[derive(Clone, Default)]
+pub struct Relation(Rc<RefCell<RelationImpl>>);
+
+/// Read a reference once rather than cycling through its values.
+fn read_once(values: Graph) -> Option<Values> {
+    // Anything else is a placeholder of a graph which is not generated
+    match values {
+        Graph::Link(box LinkNode(Link::View(view), _)) => Some(Link::View(view.once())),
+        _ => None,
+    }
+}
+
+fn read_distinct<R: Rng>(values: &mut Values, rng: &mut R) -> Result<Vec<Value>, Error> {
+    let mut seen = HashSet::new();
+    let mut distinct = Vec::new();
+    while let Some(value) = values.complete(rng) {
+        let value = value?;
+        if seen.insert(value.clone()) {
+            distinct.push(value);
+        }
+    }
+    Ok(distinct)
+}
+
+fn not_in_array() -> Error {
+    failed_crate!(
+        target: Release,
+        "a reference with a 'cardinality' must be a field of the objects of an array"
+    )
+}
+
+impl Relation {
+    /// Relate the values of `parents`, a reference, to a number of children generated by
+    /// `cardinality`.
+    pub fn set(&self, parents: Graph, cardinality: Graph) {
+        let mut relation = self.0.borrow_mut();
+        relation.parents = read_once(parents);
+        relation.cardinality = Some(Box::new(cardinality));
+    }
+
+    /// Pair the children with distinct values of `values`, a reference, and return the node
+    /// generating the partner of each child.
+    pub fn partner(&self, values: Graph) -> RelationNode {
+        let mut relation = self.0.borrow_mut();
+        relation.partners.push(Partner {
+            values: read_once(values),
+            children: Vec::new(),
+        });
+        RelationNode::Partner(Cursor::new(self.clone()), relation.partners.len() - 1)
+    }
+
+    fn start<R: Rng>(&self, rng: &mut R) -> Result<u64, Error> {
+        let mut relation = self.0.borrow_mut();
+        let RelationImpl {
+            parents,
+            cardinality,
+            partners,
+            children,
+            generation,
+        } = &mut *relation;
+        let (parents, cardinality) = parents
+            .as_mut()
+            .zip(cardinality.as_mut())
+            .ok_or_else(not_in_array)?;
+
+        *generation += 1;
+        children.clear();
+
+        // A parent cannot have more children than it has distinct partners
+        let mut partner_values = Vec::with_capacity(partners.len());
+        for partner in partners.iter_mut() {
+            let values = partner.values.as_mut().ok_or_else(not_in_array)?;
+            partner_values.push(read_distinct(values, rng)?);
+            partner.children.clear();
+        }
+        let max_count = partner_values
+            .iter()
+            .map(|values| values.len() as u64)
+            .min()
+            .unwrap_or(u64::MAX);
+
+        let parents = if partners.is_empty() {
+            let mut values = Vec::new();
+            while let Some(parent) = parents.complete(rng) {
+                values.push(parent?);
+            }
+            values
+        } else {
+            read_distinct(parents, rng)?
+        };
+
+        for parent in parents {
+            let count = unsigned_from_ok(cardinality.complete(rng))?.min(max_count);
+            for (partner, values) in partners.iter_mut().zip(partner_values.iter_mut()) {
+                // A partial Fisher-Yates shuffle
+                for taken in 0..count as usize {
+                    let index = rng.gen_range(taken..values.len());
+                    values.swap(taken, index);
+                    partner.children.push(values[taken].clone());
+                }
+            }
+            children.extend(std::iter::repeat(parent).take(count as usize));
+        }
+        Ok(children.len() as u64)
+    }
+}
+
+/// The position of a node in the children of a relation.
+pub struct Cursor {
+    relation: Relation,
+    generation: usize,
+    position: usize,
+}
+
+impl Cursor {
+    fn new(relation: Relation) -> Self {
+        Self {
+            relation,
+            generation: 0,
+            position: 0,
+        }
+    }
+
+    /// The next of the `children` of the relation, starting over when the relation does.
+    fn next<F>(&mut self, children: F) -> Result<Value, Error>
+    where
+        F: Fn(&RelationImpl) -> &Vec<Value>,
+    {
+        let relation = self.relation.0.borrow();
+        if self.generation != relation.generation {
+            self.generation = relation.generation;
+            self.position = 0;
+        }
+        let child = children(&relation)
+            .get(self.position)
+            .cloned()
+            .ok_or_else(not_in_array)?;
+        self.position += 1;
+        Ok(child)
+    }
+}
+
+pub enum RelationNode {
+    /// The number of children of all the parents, to be used as the length of the array.
+    Children(Relation),
+    /// The parent of the next child.
+    Parent(Cursor),
+    /// The partner of the next child, for the partner at the given index.
+    Partner(Cursor, usize),
+}
+
+impl RelationNode {
+    pub fn parent(relation: Relation) -> Self {
+        Self::Parent(Cursor::new(relation))
+    }
+}
+
+impl Generator for RelationNode {
+    type Yield = Token;
+
+    type Return = Result<Value, Error>;
+
+    fn next<R: Rng>(&mut self, rng: &mut R) -> GeneratorState<Self::Yield, Self::Return> {
+        let value = match self {
+            Self::Children(relation) => {
+                relation.start(rng).map(|total| Value::Number(total.into()))
+            }
+            Self::Parent(cursor) => cursor.next(|relation| &relation.children),
+            Self::Partner(cursor, index) => {
+                let index = *index;
+                cursor.next(|relation| &relation.partners[index].children)
+            }
+        };
+        GeneratorState::Complete(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Value;
+    use crate::tests::complete;
+    use crate::{Graph, Namespace};
+
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+    fn namespace(cardinality: serde_json::Value) -> Namespace {
+        serde_json::from_value(json!({
+            "users": {
+                "type": "array",
+                "length": 50,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    }
+                }
+            },
+            "orders": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    },
+                    "user_id": {
+                        "type": "same_as",
+                        "ref": "users.content.id",
+                        "cardinality": cardinality
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn orders_per_user(namespace: &Namespace) -> (Value, BTreeMap<String, u64>) {
+        let value = complete(Graph::from_namespace(namespace).unwrap()).unwrap();
+        let mut counts = BTreeMap::new();
+        let object = value.as_object().unwrap();
+        for user in object["users"].as_array().unwrap() {
+            counts.insert(user.as_object().unwrap()["id"].to_string(), 0);
+        }
+        for order in object["orders"].as_array().unwrap() {
+            let user_id = order.as_object().unwrap()["user_id"].to_string();
+            *counts.get_mut(&user_id).expect("dangling user_id") += 1;
+        }
+        (value, counts)
+    }
+
+    #[test]
+    fn relation_constant() {
+        let (_, counts) = orders_per_user(&namespace(json!(3)));
+        assert_eq!(counts.len(), 50);
+        assert!(counts.values().all(|count| *count == 3));
+    }
+
+    #[test]
+    fn relation_distribution() {
+        let namespace = namespace(json!({
+            "type": "number",
+            "subtype": "u64",
+            "poisson": {
+                "lambda": 2.0,
+                "low": 1,
+                "high": 5
+            }
+        }));
+        let (value, counts) = orders_per_user(&namespace);
+        assert!(counts.values().all(|count| (1..=5).contains(count)));
+        assert!(counts.values().collect::<BTreeSet<_>>().len() > 1);
+
+        // Children are grouped by parent, in the order of the parents
+        let object = value.as_object().unwrap();
+        let users: Vec<_> = object["users"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|user| user.as_object().unwrap()["id"].clone())
+            .collect();
+        let parents: Vec<_> = object["orders"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|order| {
+                let user_id = &order.as_object().unwrap()["user_id"];
+                users.iter().position(|id| id == user_id).unwrap()
+            })
+            .collect();
+        assert!(parents.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn relation_not_in_array() {
+        let namespace: Namespace = serde_json::from_value(json!({
+            "users": {
+                "type": "array",
+                "length": 5,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    }
+                }
+            },
+            "orders": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "user_id": {
+                        "type": "one_of",
+                        "variants": [{
+                            "type": "same_as",
+                            "ref": "users.content.id",
+                            "cardinality": 2
+                        }]
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        assert!(complete(Graph::from_namespace(&namespace).unwrap()).is_err());
+    }
+
+    #[test]
+    fn relation_partner() {
+        let namespace: Namespace = serde_json::from_value(json!({
+            "users": {
+                "type": "array",
+                "length": 30,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    }
+                }
+            },
+            "roles": {
+                "type": "array",
+                "length": 4,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    }
+                }
+            },
+            "user_roles": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "role_id": {
+                        "type": "same_as",
+                        "ref": "roles.content.id",
+                        "distinct": true
+                    },
+                    "user_id": {
+                        "type": "same_as",
+                        "ref": "users.content.id",
+                        "cardinality": {
+                            "type": "number",
+                            "subtype": "u64",
+                            "range": {
+                                "low": 1,
+                                "high": 7
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let value = complete(Graph::from_namespace(&namespace).unwrap()).unwrap();
+        let object = value.as_object().unwrap();
+        let ids = |collection: &str| -> HashSet<_> {
+            object[collection]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|element| element.as_object().unwrap()["id"].clone())
+                .collect()
+        };
+        let (users, roles) = (ids("users"), ids("roles"));
+
+        let mut pairs = BTreeSet::new();
+        let mut links = BTreeMap::new();
+        for user_role in object["user_roles"].as_array().unwrap() {
+            let user_role = user_role.as_object().unwrap();
+            let (user_id, role_id) = (&user_role["user_id"], &user_role["role_id"]);
+            assert!(users.contains(user_id) && roles.contains(role_id));
+            assert!(pairs.insert((user_id.to_string(), role_id.to_string())));
+            *links.entry(user_id.to_string()).or_insert(0) += 1;
+        }
+        // There are only 4 roles to link each user to
+        assert_eq!(links.len(), 30);
+        assert!(links.values().all(|count| (1..=4).contains(count)));
+        assert!(links.values().any(|count| *count == 4));
+    }
+
+    #[test]
+    fn relation_partner_without_parent() {
+        let namespace: Namespace = serde_json::from_value(json!({
+            "roles": {
+                "type": "array",
+                "length": 4,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "id": {}
+                    }
+                }
+            },
+            "user_roles": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "role_id": {
+                        "type": "same_as",
+                        "ref": "roles.content.id",
+                        "distinct": true
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let error = Graph::from_namespace(&namespace).err().unwrap();
+        assert!(format!("{:?}", error).contains("requires another field"));
+    }
+}
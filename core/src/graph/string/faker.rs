@@ -8,8 +8,12 @@ use fake::faker::internet::raw::*;
 use fake::faker::lorem::raw::*;
 use fake::faker::name::raw::*;
 use fake::faker::phone_number::raw::*;
-use fake::{locales, Fake};
+use fake::Fake;
 use rand::RngCore;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::locales;
 
 // this needs non-camel-case types because the fake crate has the same
 #[allow(non_camel_case_types)]
@@ -20,6 +24,12 @@ pub enum Locale {
     FR_FR,
     ZH_TW,
     ZH_CN,
+    DE_DE,
+    ES_ES,
+    IT_IT,
+    PT_BR,
+    JA_JP,
+    NL_NL,
 }
 
 impl Default for Locale {
@@ -28,18 +38,135 @@ impl Default for Locale {
     }
 }
 
+/// The number of items a faker generates: either exactly `n`, or between `low` and `high`
+/// (inclusive)
+#[derive(Copy, Clone, Deserialize, Debug, Serialize, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum FakerCount {
+    Exactly(usize),
+    Between { low: usize, high: usize },
+}
+
+impl FakerCount {
+    fn check(&self, name: &str) -> Result<(), anyhow::Error> {
+        let (low, high) = self.bounds();
+        if low == 0 {
+            Err(anyhow!("The number of {name} must be at least 1"))
+        } else if low > high {
+            Err(anyhow!(
+                "The number of {name} has a lower bound ({low}) greater than its upper bound ({high})"
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn bounds(&self) -> (usize, usize) {
+        match *self {
+            Self::Exactly(count) => (count, count),
+            Self::Between { low, high } => (low, high),
+        }
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> usize {
+        let (low, high) = self.bounds();
+        rng.gen_range(low..=high)
+    }
+}
+
+/// The arguments of the lorem generators
+#[derive(Clone, Default, Deserialize, Debug, Serialize, PartialEq, Eq, Hash)]
+pub struct LoremArgs {
+    /// The number of words of `words` and of each sentence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) words: Option<FakerCount>,
+    /// The number of sentences of `sentences` and of each paragraph
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) sentences: Option<FakerCount>,
+    /// The number of paragraphs of `paragraphs`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) paragraphs: Option<FakerCount>,
+}
+
+impl LoremArgs {
+    const DEFAULT_WORDS: FakerCount = FakerCount::Between { low: 4, high: 10 };
+    const DEFAULT_SENTENCES: FakerCount = FakerCount::Between { low: 4, high: 7 };
+    const DEFAULT_PARAGRAPHS: FakerCount = FakerCount::Between { low: 1, high: 3 };
+
+    fn check(&self, generator: &str) -> Result<(), anyhow::Error> {
+        let accepted: &[&str] = match generator {
+            "words" | "sentence" => &["words"],
+            "sentences" | "paragraph" => &["words", "sentences"],
+            "paragraphs" => &["words", "sentences", "paragraphs"],
+            _ => &[],
+        };
+        let counts = [
+            ("words", self.words),
+            ("sentences", self.sentences),
+            ("paragraphs", self.paragraphs),
+        ];
+        for (name, count) in counts {
+            if let Some(count) = count {
+                if !accepted.contains(&name) {
+                    return Err(anyhow!(
+                        "Generator '{generator}' does not take a '{name}' argument"
+                    ));
+                }
+                count.check(name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// The arguments for a faker
 #[derive(Clone, Default, Deserialize, Debug, Serialize, PartialEq, Eq, Hash)]
 pub struct FakerArgs {
     #[serde(default)]
     pub(crate) locales: Vec<Locale>,
+    #[serde(flatten)]
+    pub(crate) lorem: LoremArgs,
+}
+
+/// A locale shared by the fakers of an object, so that they agree on a country.
+///
+/// The first faker to generate a value for an object picks one of `locales`, which the others then
+/// use until the object is complete and releases it.
+#[derive(Clone, Debug)]
+pub struct PinnedLocale {
+    locales: Rc<[Locale]>,
+    current: Rc<Cell<Option<Locale>>>,
+}
+
+impl PinnedLocale {
+    pub fn new(locales: Vec<Locale>) -> Self {
+        Self {
+            locales: locales.into(),
+            current: Rc::default(),
+        }
+    }
+
+    fn get<R: Rng + ?Sized>(&self, rng: &mut R) -> Locale {
+        let locale = self.current.get().unwrap_or_else(|| {
+            self.locales
+                .get(rng.gen_range(0..self.locales.len().max(1)))
+                .copied()
+                .unwrap_or_default()
+        });
+        self.current.set(Some(locale));
+        locale
+    }
+
+    pub fn release(&self) {
+        self.current.set(None);
+    }
 }
 
 type FakerFunction = for<'r> fn(&'r mut dyn RngCore, &FakerArgs) -> String;
 
 macro_rules! fake_map_entry {
     (locale; $name:ident, $rng:ident, $args:ident, $map:ident, $faker:ident) => {
-        fake_map_entry!(with_locales; $name, $rng, $args, $map, $faker; EN, FR_FR, ZH_TW, ZH_CN)
+        fake_map_entry!(with_locales; $name, $rng, $args, $map, $faker; EN, FR_FR, ZH_TW, ZH_CN, DE_DE, ES_ES, IT_IT, PT_BR, JA_JP, NL_NL)
     };
     (with_locales; $name:ident, $rng:ident, $args:ident, $map:ident, $faker:ident; $($locale:ident),*) => {
         fn $name($rng: &mut dyn RngCore, $args: &FakerArgs) -> String {
@@ -63,12 +190,11 @@ lazy_static! {
         // Lorem
 
         fake_map_entry!(locale; word, r, args, m, Word);
-        // TODO here need to pass some params
-        // Words(count: Range<usize>);
-        // Sentence(count: Range<usize>);
-        // Sentences(count: Range<usize>);
-        // Paragraph(count: Range<usize>);
-        // Paragraphs(count: Range<usize>);
+        m.insert("words", words as _);
+        m.insert("sentence", sentence as _);
+        m.insert("sentences", sentences as _);
+        m.insert("paragraph", paragraph as _);
+        m.insert("paragraphs", paragraphs as _);
 
         // Name
         fake_map_entry!(locale; first_name, r, args, m, FirstName);
@@ -157,6 +283,7 @@ fn address(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
     // Here we get a single locale
     let args = &FakerArgs {
         locales: vec![*args.locales.first().unwrap_or(&Locale::EN)],
+        ..Default::default()
     };
 
     let number = (FAKE_MAP.get("building_number").unwrap())(rng, args);
@@ -166,9 +293,71 @@ fn address(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
     format!("{number} {street_name}, {state_abbr} {zip_code}")
 }
 
+/// Picks one of the locales so that a whole text is written in the same language.
+fn single_locale(rng: &mut dyn RngCore, args: &FakerArgs) -> FakerArgs {
+    let locale = args
+        .locales
+        .get(rng.gen_range(0..args.locales.len().max(1)))
+        .copied()
+        .unwrap_or_default();
+    FakerArgs {
+        locales: vec![locale],
+        lorem: args.lorem.clone(),
+    }
+}
+
+fn words(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
+    let args = &single_locale(rng, args);
+    let count = args.lorem.words.unwrap_or(LoremArgs::DEFAULT_WORDS);
+    let word = FAKE_MAP.get("word").unwrap();
+    (0..count.sample(rng))
+        .map(|_| word(rng, args))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn sentence(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
+    let words = words(rng, args);
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+fn sentences_with(rng: &mut dyn RngCore, args: &FakerArgs, separator: &str) -> String {
+    let args = &single_locale(rng, args);
+    let count = args.lorem.sentences.unwrap_or(LoremArgs::DEFAULT_SENTENCES);
+    (0..count.sample(rng))
+        .map(|_| sentence(rng, args))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn sentences(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
+    sentences_with(rng, args, "\n")
+}
+
+fn paragraph(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
+    sentences_with(rng, args, " ")
+}
+
+fn paragraphs(rng: &mut dyn RngCore, args: &FakerArgs) -> String {
+    let args = &single_locale(rng, args);
+    let count = args
+        .lorem
+        .paragraphs
+        .unwrap_or(LoremArgs::DEFAULT_PARAGRAPHS);
+    (0..count.sample(rng))
+        .map(|_| paragraph(rng, args))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 pub struct RandFaker {
     generator: FakerFunction,
     args: FakerArgs,
+    pinned: Option<PinnedLocale>,
 }
 
 impl RandFaker {
@@ -179,11 +368,23 @@ impl RandFaker {
                 generator.as_ref(),
                 suggest_closest(FAKE_MAP.keys(), generator.as_ref()).unwrap_or_default()
             )),
-            Some(generator) => Ok(Self {
-                generator: *generator,
-                args,
-            }),
+            Some(function) => {
+                args.lorem.check(generator.as_ref())?;
+                Ok(Self {
+                    generator: *function,
+                    args,
+                    pinned: None,
+                })
+            }
+        }
+    }
+
+    /// Use the locale pinned by the enclosing object, unless this faker has `locales` of its own.
+    pub(crate) fn with_pinned_locale(mut self, pinned: Option<PinnedLocale>) -> Self {
+        if self.args.locales.is_empty() {
+            self.pinned = pinned;
         }
+        self
     }
 }
 
@@ -193,6 +394,134 @@ impl Generator for RandFaker {
     type Return = Result<Never, Error>;
 
     fn next<R: Rng>(&mut self, rng: &mut R) -> GeneratorState<Self::Yield, Self::Return> {
-        GeneratorState::Yielded((self.generator)(rng, &self.args))
+        match &self.pinned {
+            Some(pinned) => {
+                let args = FakerArgs {
+                    locales: vec![pinned.get(rng)],
+                    lorem: self.args.lorem.clone(),
+                };
+                GeneratorState::Yielded((self.generator)(rng, &args))
+            }
+            None => GeneratorState::Yielded((self.generator)(rng, &self.args)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lorem(generator: &str, lorem: LoremArgs) -> String {
+        let args = FakerArgs {
+            lorem,
+            ..Default::default()
+        };
+        let mut faker = RandFaker::new(generator, args).unwrap();
+        match faker.next(&mut crate::tests::rng()) {
+            GeneratorState::Yielded(text) => text,
+            GeneratorState::Complete(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn lorem_counts() {
+        let text = lorem(
+            "words",
+            LoremArgs {
+                words: Some(FakerCount::Exactly(7)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(text.split(' ').count(), 7);
+
+        let text = lorem(
+            "sentences",
+            LoremArgs {
+                words: Some(FakerCount::Exactly(3)),
+                sentences: Some(FakerCount::Between { low: 2, high: 4 }),
+                ..Default::default()
+            },
+        );
+        let lines = text.lines().collect::<Vec<_>>();
+        assert!((2..=4).contains(&lines.len()));
+        for line in lines {
+            assert!(line.ends_with('.'));
+            assert!(line.starts_with(char::is_uppercase));
+            assert_eq!(line.split(' ').count(), 3);
+        }
+
+        let text = lorem(
+            "paragraphs",
+            LoremArgs {
+                words: Some(FakerCount::Exactly(2)),
+                sentences: Some(FakerCount::Exactly(3)),
+                paragraphs: Some(FakerCount::Exactly(2)),
+            },
+        );
+        let paragraphs = text.split("\n\n").collect::<Vec<_>>();
+        assert_eq!(paragraphs.len(), 2);
+        for paragraph in paragraphs {
+            assert_eq!(paragraph.matches('.').count(), 3);
+            assert_eq!(paragraph.split(' ').count(), 6);
+        }
+    }
+
+    #[test]
+    fn lorem_invalid_args() {
+        let args = |lorem| FakerArgs {
+            lorem,
+            ..Default::default()
+        };
+
+        let error = RandFaker::new(
+            "first_name",
+            args(LoremArgs {
+                words: Some(FakerCount::Exactly(2)),
+                ..Default::default()
+            }),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Generator 'first_name' does not take a 'words' argument"
+        );
+
+        let error = RandFaker::new(
+            "sentence",
+            args(LoremArgs {
+                sentences: Some(FakerCount::Exactly(2)),
+                ..Default::default()
+            }),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Generator 'sentence' does not take a 'sentences' argument"
+        );
+
+        let error = RandFaker::new(
+            "paragraphs",
+            args(LoremArgs {
+                paragraphs: Some(FakerCount::Between { low: 3, high: 1 }),
+                ..Default::default()
+            }),
+        )
+        .err()
+        .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "The number of paragraphs has a lower bound (3) greater than its upper bound (1)"
+        );
+
+        assert!(RandFaker::new(
+            "words",
+            args(LoremArgs {
+                words: Some(FakerCount::Exactly(0)),
+                ..Default::default()
+            }),
+        )
+        .is_err());
     }
 }
@@ -0,0 +1,709 @@
+//! Locales for the faker generators.
+//!
+//! The `fake` crate ships a handful of locales. The others are defined here the same way, by
+//! overriding the data of [`Data`](fake::locales::Data) that differs from English. Lorem words,
+//! user agents, etc. are left to their defaults.
+#![allow(non_camel_case_types)]
+
+use fake::locales::Data;
+
+pub use fake::locales::{EN, FR_FR, ZH_CN, ZH_TW};
+
+#[derive(Copy, Clone)]
+pub struct DE_DE;
+
+impl Data for DE_DE {
+    const NAME_FIRST_NAME: &'static [&'static str] = &[
+        "Anna",
+        "Ben",
+        "Clara",
+        "David",
+        "Emma",
+        "Felix",
+        "Greta",
+        "Hannah",
+        "Jan",
+        "Jonas",
+        "Julia",
+        "Katharina",
+        "Lena",
+        "Leon",
+        "Lukas",
+        "Maria",
+        "Maximilian",
+        "Mia",
+        "Niklas",
+        "Paul",
+        "Sabine",
+        "Sophie",
+        "Stefan",
+        "Thomas",
+        "Ursula",
+        "Wolfgang",
+    ];
+    const NAME_LAST_NAME: &'static [&'static str] = &[
+        "Bauer",
+        "Becker",
+        "Braun",
+        "Fischer",
+        "Hartmann",
+        "Hoffmann",
+        "Koch",
+        "Krüger",
+        "Lange",
+        "Meyer",
+        "Müller",
+        "Neumann",
+        "Richter",
+        "Schäfer",
+        "Schmidt",
+        "Schmitz",
+        "Schneider",
+        "Schulz",
+        "Schwarz",
+        "Wagner",
+        "Weber",
+        "Werner",
+        "Wolf",
+        "Zimmermann",
+    ];
+    const NAME_TITLE: &'static [&'static str] = &["Herr", "Frau", "Dr.", "Prof."];
+    const NAME_SUFFIX: &'static [&'static str] = &["jun.", "sen."];
+
+    const ADDRESS_CITY_PREFIX: &'static [&'static str] =
+        &["Alt", "Groß", "Klein", "Neu", "Nieder", "Ober"];
+    const ADDRESS_CITY_SUFFIX: &'static [&'static str] = &[
+        "bach", "berg", "burg", "dorf", "feld", "hagen", "hausen", "heim", "kirchen", "stadt",
+    ];
+    const ADDRESS_CITY_TPL: &'static str = "{CityName}{CitySuffix}";
+    const ADDRESS_CITY_WITH_PREFIX_TPL: &'static str = "{CityPrefix}{CitySuffix}";
+    const ADDRESS_STREET_SUFFIX: &'static [&'static str] =
+        &["straße", "weg", "allee", "gasse", "platz", "ring"];
+    const ADDRESS_STREET_TPL: &'static str = "{StreetName}{StreetSuffix}";
+    const ADDRESS_SECONDARY_ADDR_TYPE: &'static [&'static str] = &["Wohnung", "Etage"];
+    const ADDRESS_TIME_ZONE: &'static [&'static str] = &["Europe/Berlin"];
+    const ADDRESS_STATE: &'static [&'static str] = &[
+        "Baden-Württemberg",
+        "Bayern",
+        "Berlin",
+        "Brandenburg",
+        "Bremen",
+        "Hamburg",
+        "Hessen",
+        "Mecklenburg-Vorpommern",
+        "Niedersachsen",
+        "Nordrhein-Westfalen",
+        "Rheinland-Pfalz",
+        "Saarland",
+        "Sachsen",
+        "Sachsen-Anhalt",
+        "Schleswig-Holstein",
+        "Thüringen",
+    ];
+    const ADDRESS_STATE_ABBR: &'static [&'static str] = &[
+        "BW", "BY", "BE", "BB", "HB", "HH", "HE", "MV", "NI", "NW", "RP", "SL", "SN", "ST", "SH",
+        "TH",
+    ];
+    const ADDRESS_BUILDING_NUMBER_FORMATS: &'static [&'static str] = &["^##", "^#", "^"];
+    const ADDRESS_ZIP_FORMATS: &'static [&'static str] = &["#####"];
+    const ADDRESS_POSTCODE_FORMATS: &'static [&'static str] = &["#####"];
+
+    const COMPANY_SUFFIX: &'static [&'static str] =
+        &["GmbH", "AG", "KG", "GmbH & Co. KG", "OHG", "e.K."];
+    const COMPANY_NAME_TPLS: &'static [&'static str] =
+        &["{Name_1} {Suffix}", "{Name_1} & {Name_2} {Suffix}"];
+
+    const INTERNET_FREE_EMAIL_PROVIDER: &'static [&'static str] =
+        &["gmx.de", "web.de", "t-online.de", "freenet.de", "gmail.com"];
+    const INTERNET_DOMAIN_SUFFIX: &'static [&'static str] = &["de", "com", "net", "org", "eu"];
+
+    const PHONE_NUMBER_FORMATS: &'static [&'static str] = &[
+        "0^## #######",
+        "0^### ######",
+        "+49 ^## #######",
+        "+49 ^### ######",
+    ];
+    const PHONE_CELL_NUMBER_FORMATS: &'static [&'static str] = &[
+        "015# ########",
+        "016# #######",
+        "017# #######",
+        "+49 17# #######",
+    ];
+}
+
+#[derive(Copy, Clone)]
+pub struct ES_ES;
+
+impl Data for ES_ES {
+    const NAME_FIRST_NAME: &'static [&'static str] = &[
+        "Alejandro",
+        "Ana",
+        "Carlos",
+        "Carmen",
+        "Daniel",
+        "Elena",
+        "Francisco",
+        "Hugo",
+        "Isabel",
+        "Javier",
+        "Jorge",
+        "José",
+        "Laura",
+        "Lucía",
+        "Manuel",
+        "María",
+        "Marta",
+        "Miguel",
+        "Pablo",
+        "Paula",
+        "Pilar",
+        "Raúl",
+        "Rosa",
+        "Sergio",
+        "Sofía",
+        "Teresa",
+    ];
+    const NAME_LAST_NAME: &'static [&'static str] = &[
+        "Alonso",
+        "Díaz",
+        "Fernández",
+        "García",
+        "Gómez",
+        "González",
+        "Gutiérrez",
+        "Hernández",
+        "Jiménez",
+        "López",
+        "Martín",
+        "Martínez",
+        "Moreno",
+        "Muñoz",
+        "Navarro",
+        "Pérez",
+        "Romero",
+        "Ruiz",
+        "Sánchez",
+        "Torres",
+        "Vázquez",
+    ];
+    const NAME_TITLE: &'static [&'static str] = &["Sr.", "Sra.", "Srta.", "Dr.", "Dra."];
+
+    const ADDRESS_CITY_PREFIX: &'static [&'static str] = &[
+        "San Martín",
+        "Santa María",
+        "Villanueva",
+        "Torre",
+        "Puebla",
+        "Castillo",
+    ];
+    const ADDRESS_CITY_SUFFIX: &'static [&'static str] = &[
+        "de la Sierra",
+        "del Río",
+        "del Campo",
+        "de Arriba",
+        "de la Frontera",
+        "del Mar",
+    ];
+    const ADDRESS_CITY_WITH_PREFIX_TPL: &'static str = "{CityPrefix} {CitySuffix}";
+    const ADDRESS_STREET_SUFFIX: &'static [&'static str] =
+        &["Calle", "Avenida", "Plaza", "Paseo", "Camino", "Ronda"];
+    const ADDRESS_STREET_TPL: &'static str = "{StreetSuffix} {StreetName}";
+    const ADDRESS_SECONDARY_ADDR_TYPE: &'static [&'static str] = &["Piso", "Puerta"];
+    const ADDRESS_TIME_ZONE: &'static [&'static str] = &["Europe/Madrid", "Atlantic/Canary"];
+    const ADDRESS_STATE: &'static [&'static str] = &[
+        "Andalucía",
+        "Aragón",
+        "Asturias",
+        "Canarias",
+        "Cantabria",
+        "Castilla-La Mancha",
+        "Castilla y León",
+        "Cataluña",
+        "Comunidad de Madrid",
+        "Comunidad Valenciana",
+        "Extremadura",
+        "Galicia",
+        "Illes Balears",
+        "La Rioja",
+        "Navarra",
+        "País Vasco",
+        "Región de Murcia",
+    ];
+    const ADDRESS_STATE_ABBR: &'static [&'static str] = &[
+        "AN", "AR", "AS", "CN", "CB", "CM", "CL", "CT", "MD", "VC", "EX", "GA", "IB", "RI", "NC",
+        "PV", "MC",
+    ];
+    const ADDRESS_BUILDING_NUMBER_FORMATS: &'static [&'static str] = &["^##", "^#", "^"];
+    const ADDRESS_ZIP_FORMATS: &'static [&'static str] =
+        &["0^###", "1####", "2####", "3####", "4####"];
+    const ADDRESS_POSTCODE_FORMATS: &'static [&'static str] =
+        &["0^###", "1####", "2####", "3####", "4####"];
+
+    const COMPANY_SUFFIX: &'static [&'static str] = &["S.A.", "S.L.", "S.L.U.", "S.Coop."];
+    const COMPANY_NAME_TPLS: &'static [&'static str] =
+        &["{Name_1} {Suffix}", "{Name_1} y {Name_2} {Suffix}"];
+
+    const INTERNET_FREE_EMAIL_PROVIDER: &'static [&'static str] = &[
+        "gmail.com",
+        "hotmail.es",
+        "yahoo.es",
+        "outlook.es",
+        "telefonica.net",
+    ];
+    const INTERNET_DOMAIN_SUFFIX: &'static [&'static str] = &["es", "com", "net", "org"];
+
+    const PHONE_NUMBER_FORMATS: &'static [&'static str] = &["9## ### ###", "+34 9## ### ###"];
+    const PHONE_CELL_NUMBER_FORMATS: &'static [&'static str] =
+        &["6## ### ###", "7## ### ###", "+34 6## ### ###"];
+}
+
+#[derive(Copy, Clone)]
+pub struct IT_IT;
+
+impl Data for IT_IT {
+    const NAME_FIRST_NAME: &'static [&'static str] = &[
+        "Alessandro",
+        "Alessia",
+        "Andrea",
+        "Anna",
+        "Chiara",
+        "Davide",
+        "Elena",
+        "Federico",
+        "Francesca",
+        "Gabriele",
+        "Giorgia",
+        "Giovanni",
+        "Giulia",
+        "Giuseppe",
+        "Leonardo",
+        "Lorenzo",
+        "Luca",
+        "Marco",
+        "Maria",
+        "Martina",
+        "Matteo",
+        "Paola",
+        "Roberto",
+        "Sara",
+        "Sofia",
+        "Valentina",
+    ];
+    const NAME_LAST_NAME: &'static [&'static str] = &[
+        "Barbieri", "Bianchi", "Bruno", "Colombo", "Conti", "Costa", "De Luca", "Esposito",
+        "Ferrari", "Fontana", "Gallo", "Giordano", "Greco", "Lombardi", "Mancini", "Marino",
+        "Moretti", "Ricci", "Rizzo", "Romano", "Rossi", "Russo",
+    ];
+    const NAME_TITLE: &'static [&'static str] = &["Sig.", "Sig.ra", "Dott.", "Dott.ssa", "Prof."];
+
+    const ADDRESS_CITY_PREFIX: &'static [&'static str] = &[
+        "San Giovanni",
+        "Santa Maria",
+        "Castel",
+        "Borgo",
+        "Monte",
+        "Villa",
+    ];
+    const ADDRESS_CITY_SUFFIX: &'static [&'static str] = &[
+        "di Sopra", "di Sotto", "Marina", "Terme", "al Mare", "Vecchio",
+    ];
+    const ADDRESS_CITY_WITH_PREFIX_TPL: &'static str = "{CityPrefix} {CitySuffix}";
+    const ADDRESS_STREET_SUFFIX: &'static [&'static str] =
+        &["Via", "Viale", "Piazza", "Corso", "Vicolo", "Largo"];
+    const ADDRESS_STREET_TPL: &'static str = "{StreetSuffix} {StreetName}";
+    const ADDRESS_SECONDARY_ADDR_TYPE: &'static [&'static str] = &["Interno", "Scala"];
+    const ADDRESS_TIME_ZONE: &'static [&'static str] = &["Europe/Rome"];
+    const ADDRESS_STATE: &'static [&'static str] = &[
+        "Abruzzo",
+        "Basilicata",
+        "Calabria",
+        "Campania",
+        "Emilia-Romagna",
+        "Friuli-Venezia Giulia",
+        "Lazio",
+        "Liguria",
+        "Lombardia",
+        "Marche",
+        "Molise",
+        "Piemonte",
+        "Puglia",
+        "Sardegna",
+        "Sicilia",
+        "Toscana",
+        "Trentino-Alto Adige",
+        "Umbria",
+        "Valle d'Aosta",
+        "Veneto",
+    ];
+    const ADDRESS_STATE_ABBR: &'static [&'static str] = &[
+        "RM", "MI", "NA", "TO", "PA", "GE", "BO", "FI", "BA", "CT", "VE", "VR", "ME", "PD", "TS",
+        "BS", "PR", "TA", "PG", "CA",
+    ];
+    const ADDRESS_BUILDING_NUMBER_FORMATS: &'static [&'static str] = &["^##", "^#", "^"];
+    const ADDRESS_ZIP_FORMATS: &'static [&'static str] = &["#####"];
+    const ADDRESS_POSTCODE_FORMATS: &'static [&'static str] = &["#####"];
+
+    const COMPANY_SUFFIX: &'static [&'static str] = &["S.p.A.", "S.r.l.", "S.n.c.", "S.a.s."];
+    const COMPANY_NAME_TPLS: &'static [&'static str] =
+        &["{Name_1} {Suffix}", "{Name_1} e {Name_2} {Suffix}"];
+
+    const INTERNET_FREE_EMAIL_PROVIDER: &'static [&'static str] = &[
+        "libero.it",
+        "virgilio.it",
+        "alice.it",
+        "tiscali.it",
+        "gmail.com",
+    ];
+    const INTERNET_DOMAIN_SUFFIX: &'static [&'static str] = &["it", "com", "net", "org", "eu"];
+
+    const PHONE_NUMBER_FORMATS: &'static [&'static str] =
+        &["0^ ########", "0^# #######", "+39 0^ ########"];
+    const PHONE_CELL_NUMBER_FORMATS: &'static [&'static str] =
+        &["3## ### ####", "+39 3## ### ####"];
+}
+
+#[derive(Copy, Clone)]
+pub struct PT_BR;
+
+impl Data for PT_BR {
+    const NAME_FIRST_NAME: &'static [&'static str] = &[
+        "Ana", "Beatriz", "Bruno", "Camila", "Carlos", "Daniela", "Eduardo", "Fernanda", "Gabriel",
+        "Gustavo", "Isabela", "João", "Juliana", "Larissa", "Lucas", "Luiz", "Mariana", "Mateus",
+        "Paulo", "Pedro", "Rafael", "Renata", "Thiago", "Vitória",
+    ];
+    const NAME_LAST_NAME: &'static [&'static str] = &[
+        "Almeida",
+        "Alves",
+        "Araújo",
+        "Barbosa",
+        "Carvalho",
+        "Costa",
+        "Ferreira",
+        "Gomes",
+        "Lima",
+        "Martins",
+        "Oliveira",
+        "Pereira",
+        "Ribeiro",
+        "Rocha",
+        "Rodrigues",
+        "Santos",
+        "Silva",
+        "Souza",
+    ];
+    const NAME_TITLE: &'static [&'static str] = &["Sr.", "Sra.", "Dr.", "Dra.", "Prof."];
+    const NAME_SUFFIX: &'static [&'static str] = &["Filho", "Neto", "Júnior", "Sobrinho"];
+
+    const ADDRESS_CITY_PREFIX: &'static [&'static str] =
+        &["São José", "Santa Rita", "Nova", "Porto", "Vila", "Campo"];
+    const ADDRESS_CITY_SUFFIX: &'static [&'static str] = &[
+        "do Sul", "do Norte", "do Oeste", "de Minas", "da Serra", "Paulista",
+    ];
+    const ADDRESS_CITY_WITH_PREFIX_TPL: &'static str = "{CityPrefix} {CitySuffix}";
+    const ADDRESS_STREET_SUFFIX: &'static [&'static str] =
+        &["Rua", "Avenida", "Travessa", "Alameda", "Praça", "Rodovia"];
+    const ADDRESS_STREET_TPL: &'static str = "{StreetSuffix} {StreetName}";
+    const ADDRESS_SECONDARY_ADDR_TYPE: &'static [&'static str] = &["Apto.", "Sala", "Bloco"];
+    const ADDRESS_TIME_ZONE: &'static [&'static str] = &[
+        "America/Sao_Paulo",
+        "America/Manaus",
+        "America/Fortaleza",
+        "America/Recife",
+        "America/Belem",
+        "America/Cuiaba",
+        "America/Porto_Velho",
+        "America/Rio_Branco",
+        "America/Noronha",
+    ];
+    const ADDRESS_STATE: &'static [&'static str] = &[
+        "Acre",
+        "Alagoas",
+        "Amapá",
+        "Amazonas",
+        "Bahia",
+        "Ceará",
+        "Distrito Federal",
+        "Espírito Santo",
+        "Goiás",
+        "Maranhão",
+        "Mato Grosso",
+        "Mato Grosso do Sul",
+        "Minas Gerais",
+        "Pará",
+        "Paraíba",
+        "Paraná",
+        "Pernambuco",
+        "Piauí",
+        "Rio de Janeiro",
+        "Rio Grande do Norte",
+        "Rio Grande do Sul",
+        "Rondônia",
+        "Roraima",
+        "Santa Catarina",
+        "São Paulo",
+        "Sergipe",
+        "Tocantins",
+    ];
+    const ADDRESS_STATE_ABBR: &'static [&'static str] = &[
+        "AC", "AL", "AP", "AM", "BA", "CE", "DF", "ES", "GO", "MA", "MT", "MS", "MG", "PA", "PB",
+        "PR", "PE", "PI", "RJ", "RN", "RS", "RO", "RR", "SC", "SP", "SE", "TO",
+    ];
+    const ADDRESS_BUILDING_NUMBER_FORMATS: &'static [&'static str] = &["^###", "^##", "^#"];
+    const ADDRESS_ZIP_FORMATS: &'static [&'static str] = &["#####-###"];
+    const ADDRESS_POSTCODE_FORMATS: &'static [&'static str] = &["#####-###"];
+
+    const COMPANY_SUFFIX: &'static [&'static str] = &["Ltda.", "S.A.", "ME", "EIRELI"];
+    const COMPANY_NAME_TPLS: &'static [&'static str] =
+        &["{Name_1} {Suffix}", "{Name_1} e {Name_2} {Suffix}"];
+
+    const INTERNET_FREE_EMAIL_PROVIDER: &'static [&'static str] = &[
+        "gmail.com",
+        "hotmail.com",
+        "uol.com.br",
+        "bol.com.br",
+        "terra.com.br",
+        "yahoo.com.br",
+    ];
+    const INTERNET_DOMAIN_SUFFIX: &'static [&'static str] =
+        &["com.br", "br", "net", "org.br", "com"];
+
+    const PHONE_NUMBER_FORMATS: &'static [&'static str] = &["(^#) ####-####", "+55 ^# ####-####"];
+    const PHONE_CELL_NUMBER_FORMATS: &'static [&'static str] =
+        &["(^#) 9####-####", "+55 ^# 9####-####"];
+}
+
+#[derive(Copy, Clone)]
+pub struct JA_JP;
+
+impl Data for JA_JP {
+    const NAME_FIRST_NAME: &'static [&'static str] = &[
+        "蓮",
+        "陽翔",
+        "湊",
+        "大翔",
+        "悠真",
+        "翔太",
+        "健太",
+        "拓海",
+        "大輝",
+        "直樹",
+        "誠",
+        "浩",
+        "陽葵",
+        "結菜",
+        "葵",
+        "凛",
+        "芽依",
+        "美咲",
+        "さくら",
+        "愛",
+        "由美",
+        "恵子",
+        "真由美",
+        "花子",
+    ];
+    const NAME_LAST_NAME: &'static [&'static str] = &[
+        "佐藤",
+        "鈴木",
+        "高橋",
+        "田中",
+        "伊藤",
+        "渡辺",
+        "山本",
+        "中村",
+        "小林",
+        "加藤",
+        "吉田",
+        "山田",
+        "佐々木",
+        "山口",
+        "松本",
+        "井上",
+        "木村",
+        "林",
+        "斎藤",
+        "清水",
+    ];
+    const NAME_TITLE: &'static [&'static str] = &["様", "さん", "先生"];
+    const NAME_TPL: &'static str = "{LastName} {FirstName}";
+    const NAME_WITH_TITLE_TPL: &'static str = "{LastName} {FirstName}{Title}";
+
+    const ADDRESS_CITY_PREFIX: &'static [&'static str] = &[
+        "北山", "南川", "東浜", "西原", "新田", "大野", "高島", "小松",
+    ];
+    const ADDRESS_CITY_SUFFIX: &'static [&'static str] = &["市", "町", "村"];
+    const ADDRESS_CITY_TPL: &'static str = "{CityName}{CitySuffix}";
+    const ADDRESS_CITY_WITH_PREFIX_TPL: &'static str = "{CityPrefix}{CitySuffix}";
+    const ADDRESS_STREET_SUFFIX: &'static [&'static str] = &["通り", "町", "坂"];
+    const ADDRESS_STREET_TPL: &'static str = "{StreetName}{StreetSuffix}";
+    const ADDRESS_SECONDARY_ADDR_TYPE: &'static [&'static str] = &["号室"];
+    const ADDRESS_SECONDARY_ADDR_TPL: &'static str = "{Number}{SecondaryAddrType}";
+    const ADDRESS_TIME_ZONE: &'static [&'static str] = &["Asia/Tokyo"];
+    const ADDRESS_STATE: &'static [&'static str] = &[
+        "北海道",
+        "青森県",
+        "岩手県",
+        "宮城県",
+        "秋田県",
+        "山形県",
+        "福島県",
+        "茨城県",
+        "栃木県",
+        "群馬県",
+        "埼玉県",
+        "千葉県",
+        "東京都",
+        "神奈川県",
+        "新潟県",
+        "富山県",
+        "石川県",
+        "福井県",
+        "山梨県",
+        "長野県",
+        "岐阜県",
+        "静岡県",
+        "愛知県",
+        "三重県",
+        "滋賀県",
+        "京都府",
+        "大阪府",
+        "兵庫県",
+        "奈良県",
+        "和歌山県",
+        "鳥取県",
+        "島根県",
+        "岡山県",
+        "広島県",
+        "山口県",
+        "徳島県",
+        "香川県",
+        "愛媛県",
+        "高知県",
+        "福岡県",
+        "佐賀県",
+        "長崎県",
+        "熊本県",
+        "大分県",
+        "宮崎県",
+        "鹿児島県",
+        "沖縄県",
+    ];
+    // ISO 3166-2 codes of the prefectures
+    const ADDRESS_STATE_ABBR: &'static [&'static str] = &[
+        "JP-01", "JP-02", "JP-03", "JP-04", "JP-05", "JP-06", "JP-07", "JP-08", "JP-09", "JP-10",
+        "JP-11", "JP-12", "JP-13", "JP-14", "JP-15", "JP-16", "JP-17", "JP-18", "JP-19", "JP-20",
+        "JP-21", "JP-22", "JP-23", "JP-24", "JP-25", "JP-26", "JP-27", "JP-28", "JP-29", "JP-30",
+        "JP-31", "JP-32", "JP-33", "JP-34", "JP-35", "JP-36", "JP-37", "JP-38", "JP-39", "JP-40",
+        "JP-41", "JP-42", "JP-43", "JP-44", "JP-45", "JP-46", "JP-47",
+    ];
+    const ADDRESS_BUILDING_NUMBER_FORMATS: &'static [&'static str] = &["^-^-^", "^-^#-^", "^#-^"];
+    const ADDRESS_ZIP_FORMATS: &'static [&'static str] = &["###-####"];
+    const ADDRESS_POSTCODE_FORMATS: &'static [&'static str] = &["###-####"];
+
+    const COMPANY_SUFFIX: &'static [&'static str] = &["株式会社", "有限会社", "合同会社"];
+    const COMPANY_NAME_TPLS: &'static [&'static str] = &["{Suffix}{Name_1}", "{Name_1}{Suffix}"];
+
+    const INTERNET_FREE_EMAIL_PROVIDER: &'static [&'static str] = &[
+        "yahoo.co.jp",
+        "gmail.com",
+        "docomo.ne.jp",
+        "ezweb.ne.jp",
+        "softbank.ne.jp",
+        "outlook.jp",
+    ];
+    const INTERNET_DOMAIN_SUFFIX: &'static [&'static str] =
+        &["jp", "co.jp", "ne.jp", "or.jp", "com"];
+
+    const PHONE_NUMBER_FORMATS: &'static [&'static str] =
+        &["0^-####-####", "0^#-###-####", "0^##-##-####"];
+    const PHONE_CELL_NUMBER_FORMATS: &'static [&'static str] =
+        &["090-####-####", "080-####-####", "070-####-####"];
+}
+
+#[derive(Copy, Clone)]
+pub struct NL_NL;
+
+impl Data for NL_NL {
+    const NAME_FIRST_NAME: &'static [&'static str] = &[
+        "Anouk", "Bram", "Daan", "Emma", "Eva", "Fenna", "Finn", "Isa", "Jan", "Jesse", "Julia",
+        "Lars", "Lieke", "Lotte", "Luuk", "Milan", "Noah", "Pieter", "Sanne", "Sem", "Sophie",
+        "Thijs", "Tess", "Willem",
+    ];
+    const NAME_LAST_NAME: &'static [&'static str] = &[
+        "Bakker",
+        "Bos",
+        "de Boer",
+        "de Groot",
+        "de Jong",
+        "de Vries",
+        "Dekker",
+        "Jansen",
+        "Janssen",
+        "Meijer",
+        "Mulder",
+        "Peters",
+        "Smit",
+        "van Dijk",
+        "van den Berg",
+        "van der Meer",
+        "Visser",
+        "Vos",
+    ];
+    const NAME_TITLE: &'static [&'static str] = &["Dhr.", "Mevr.", "Dr.", "Prof.", "Ir."];
+
+    const ADDRESS_CITY_PREFIX: &'static [&'static str] = &[
+        "Zand", "Hoog", "Oost", "West", "Noord", "Zuid", "Berg", "Lange",
+    ];
+    const ADDRESS_CITY_SUFFIX: &'static [&'static str] = &[
+        "dam", "dorp", "veen", "wijk", "hoven", "broek", "kerk", "meer", "zijl",
+    ];
+    const ADDRESS_CITY_TPL: &'static str = "{CityName}{CitySuffix}";
+    const ADDRESS_CITY_WITH_PREFIX_TPL: &'static str = "{CityPrefix}{CitySuffix}";
+    const ADDRESS_STREET_SUFFIX: &'static [&'static str] =
+        &["straat", "laan", "weg", "plein", "gracht", "kade", "dijk"];
+    const ADDRESS_STREET_TPL: &'static str = "{StreetName}{StreetSuffix}";
+    const ADDRESS_SECONDARY_ADDR_TYPE: &'static [&'static str] = &["Appartement", "Bus"];
+    const ADDRESS_TIME_ZONE: &'static [&'static str] = &["Europe/Amsterdam"];
+    const ADDRESS_STATE: &'static [&'static str] = &[
+        "Drenthe",
+        "Flevoland",
+        "Friesland",
+        "Gelderland",
+        "Groningen",
+        "Limburg",
+        "Noord-Brabant",
+        "Noord-Holland",
+        "Overijssel",
+        "Utrecht",
+        "Zeeland",
+        "Zuid-Holland",
+    ];
+    const ADDRESS_STATE_ABBR: &'static [&'static str] = &[
+        "DR", "FL", "FR", "GE", "GR", "LI", "NB", "NH", "OV", "UT", "ZE", "ZH",
+    ];
+    const ADDRESS_BUILDING_NUMBER_FORMATS: &'static [&'static str] = &["^##", "^#", "^"];
+    const ADDRESS_ZIP_FORMATS: &'static [&'static str] = &[
+        "^### AB", "^### BK", "^### CD", "^### GH", "^### JL", "^### KR", "^### PX", "^### TZ",
+    ];
+    const ADDRESS_POSTCODE_FORMATS: &'static [&'static str] = &[
+        "^### AB", "^### BK", "^### CD", "^### GH", "^### JL", "^### KR", "^### PX", "^### TZ",
+    ];
+
+    const COMPANY_SUFFIX: &'static [&'static str] = &["B.V.", "N.V.", "V.O.F."];
+    const COMPANY_NAME_TPLS: &'static [&'static str] =
+        &["{Name_1} {Suffix}", "{Name_1} & {Name_2} {Suffix}"];
+
+    const INTERNET_FREE_EMAIL_PROVIDER: &'static [&'static str] = &[
+        "ziggo.nl",
+        "kpnmail.nl",
+        "hotmail.nl",
+        "live.nl",
+        "planet.nl",
+        "gmail.com",
+    ];
+    const INTERNET_DOMAIN_SUFFIX: &'static [&'static str] = &["nl", "com", "net", "eu", "org"];
+
+    const PHONE_NUMBER_FORMATS: &'static [&'static str] =
+        &["0^# #######", "0^## ######", "+31 ^# #######"];
+    const PHONE_CELL_NUMBER_FORMATS: &'static [&'static str] = &["06-########", "+31 6 ########"];
+}
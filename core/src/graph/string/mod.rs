@@ -5,6 +5,7 @@ use rand_regex::Regex as RandRegex;
 pub mod constant;
 pub mod faker;
 pub mod format;
+pub mod locales;
 pub mod serialized;
 pub mod sliced;
 pub mod truncated;
@@ -12,7 +13,7 @@ pub mod uuid;
 
 pub use self::uuid::UuidGen;
 pub use constant::Constant;
-pub use faker::{FakerArgs, Locale, RandFaker};
+pub use faker::{FakerArgs, FakerCount, Locale, LoremArgs, PinnedLocale, RandFaker};
 pub use format::{Format, FormatArgs};
 pub use serialized::Serialized;
 pub use sliced::Sliced;
@@ -0,0 +1,71 @@
+use super::prelude::*;
+
+use std::collections::BTreeMap;
+
+use crate::schema::FieldRef;
+
+pub struct SwitchNode {
+    on: FieldRef,
+    discriminant: Box<Graph>,
+    cases: BTreeMap<String, Graph>,
+    default: Option<Box<Graph>>,
+}
+
+impl SwitchNode {
+    pub fn new(
+        on: FieldRef,
+        discriminant: Graph,
+        cases: BTreeMap<String, Graph>,
+        default: Option<Graph>,
+    ) -> Self {
+        Self {
+            on,
+            discriminant: Box::new(discriminant),
+            cases,
+            default: default.map(Box::new),
+        }
+    }
+
+    fn branch(&mut self, value: Value) -> Result<&mut Graph, Error> {
+        let key = match value {
+            Value::Null(_) => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            value @ (Value::String(_) | Value::Number(_) | Value::DateTime(_)) => {
+                value.try_into()?
+            }
+            otherwise => {
+                return Err(failed_crate!(
+                    target: Release,
+                    "cannot use a '{}' to select a case of the `switch` on `{}`",
+                    otherwise.type_(),
+                    self.on
+                ))
+            }
+        };
+        match self.cases.get_mut(&key) {
+            Some(branch) => Ok(branch),
+            None => self.default.as_deref_mut().ok_or_else(|| {
+                failed_crate!(
+                    target: Release,
+                    "no case of the `switch` on `{}` matches '{}'",
+                    self.on,
+                    key
+                )
+            }),
+        }
+    }
+}
+
+impl Generator for SwitchNode {
+    type Yield = Token;
+    type Return = Result<Value, Error>;
+
+    fn next<R: Rng>(&mut self, rng: &mut R) -> GeneratorState<Self::Yield, Self::Return> {
+        let value = self
+            .discriminant
+            .complete(rng)
+            .and_then(|value| self.branch(value))
+            .and_then(|branch| branch.complete(rng));
+        GeneratorState::Complete(value)
+    }
+}
@@ -1,8 +1,15 @@
-use crate::graph::prelude::{Error, Rng, Token, TryFilterMap, TryGeneratorExt, Value};
-use crate::Graph;
+use crate::compile::Journal;
+use crate::graph::prelude::{
+    Error, Generator, GeneratorState, Rng, Token, TryFilterMap, TryGeneratorExt, Value,
+};
+use crate::{Graph, Partition};
 
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
-use std::hash::BuildHasher;
+use std::hash::{BuildHasher, BuildHasherDefault};
 
 const MAX_RETRIES: usize = 64;
 
@@ -14,12 +21,20 @@ derive_generator! {
     return Result<Value, Error>,
     pub enum UniqueNode {
         Hash(ValueFilter),
+        Permutation(PermutationNode),
     }
 }
 
 impl UniqueNode {
     pub fn hash(inner: Graph, retries: Option<usize>) -> Self {
-        let mut seen: HashMap<u64, usize> = HashMap::new();
+        Self::hash_partition(inner, retries, Partition::default())
+    }
+
+    /// Only accept values whose hash belongs to `partition`, so that the values generated by
+    /// nodes of different partitions are unique together.
+    pub fn hash_partition(inner: Graph, retries: Option<usize>, partition: Partition) -> Self {
+        // The hasher must be the same for all partitions
+        let mut seen: HashMap<u64, usize, BuildHasherDefault<DefaultHasher>> = HashMap::default();
         let filter = move |value: Value| {
             let hash = seen.hasher().hash_one(&value);
 
@@ -31,12 +46,89 @@ impl UniqueNode {
                 .or_insert(0);
 
             match *count {
-                0 => Ok(Some(value)),
+                0 if partition.contains(hash) => Ok(Some(value)),
                 x if x < retries.unwrap_or(MAX_RETRIES) => Ok(None),
-                _ => Err(failed_crate!(
-                    target: Release,
-                    "Could not generate enough unique values from generator: \
-                    try reducing the number of values generated"
+                _ => Err(exhausted(
+                    "Could not generate enough unique values from generator",
+                    partition,
+                )),
+            }
+        };
+        Self::Hash(Box::new(inner).try_filter_map(Box::new(filter)))
+    }
+
+    /// Only accept objects whose values at each set of `keys` have not been accepted together
+    /// before. Like in SQL, sets with a null or missing value never collide.
+    ///
+    /// Rejected objects are regenerated whole, and what the recorders of `journal` recorded while
+    /// generating them is discarded.
+    pub fn hash_fields_partition(
+        inner: Graph,
+        keys: Vec<Vec<String>>,
+        retries: Option<usize>,
+        partition: Partition,
+        mut journal: Journal<Token, Result<Value, Error>>,
+    ) -> Self {
+        let mut seen: Vec<HashMap<u64, usize, BuildHasherDefault<DefaultHasher>>> =
+            keys.iter().map(|_| HashMap::default()).collect();
+        let filter = move |value: Value| {
+            let hashes: Vec<Option<u64>> = keys
+                .iter()
+                .zip(seen.iter())
+                .map(|(fields, seen)| {
+                    let tuple = fields
+                        .iter()
+                        .map(|field| match value.as_object()?.get(field)? {
+                            Value::Null(_) => None,
+                            value => Some(value),
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+                    Some(seen.hasher().hash_one(tuple))
+                })
+                .collect();
+
+            let is_new = |(hash, seen): (&Option<u64>, &HashMap<u64, usize, _>)| match hash {
+                Some(hash) => !seen.contains_key(hash) && partition.contains(*hash),
+                None => true,
+            };
+            if hashes.iter().zip(seen.iter()).all(is_new) {
+                for (hash, seen) in hashes.into_iter().zip(seen.iter_mut()) {
+                    if let Some(hash) = hash {
+                        seen.insert(hash, 0);
+                    }
+                }
+                journal.commit();
+                return Ok(Some(value));
+            }
+            journal.rollback();
+
+            // Only count the tuples that collided: the others may still be accepted in another
+            // object, unless they belong to another partition
+            let mut exhausted_fields = None;
+            for ((hash, seen), fields) in hashes.into_iter().zip(seen.iter_mut()).zip(&keys) {
+                let Some(hash) = hash else { continue };
+                if let Some(i) = seen.get_mut(&hash) {
+                    *i += 1;
+                    if *i >= retries.unwrap_or(MAX_RETRIES) {
+                        exhausted_fields = Some(fields);
+                    }
+                } else if !partition.contains(hash) {
+                    seen.insert(hash, 0);
+                }
+            }
+
+            match exhausted_fields {
+                None => Ok(None),
+                Some(fields) => Err(exhausted(
+                    &format!(
+                        "Could not generate enough objects whose fields {} are unique together",
+                        fields
+                            .iter()
+                            .map(|field| format!("'{field}'"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    partition,
                 )),
             }
         };
@@ -44,6 +136,139 @@ impl UniqueNode {
     }
 }
 
+/// The number of rounds of the Feistel network of [`PermutationNode`](PermutationNode).
+const FEISTEL_ROUNDS: usize = 4;
+
+/// The values of a finite space, by index.
+pub type Enumeration = Box<dyn Fn(u64) -> Value>;
+
+/// Generates the values of a finite space of `size` values in a pseudo-random order, without
+/// repeating any of them.
+///
+/// The order is a permutation of the indices of the values given by a Feistel network, keyed by
+/// the rng the first time a value is generated. It needs no memory of the values already
+/// generated and never retries, unlike [`UniqueNode::hash`].
+pub struct PermutationNode {
+    size: u64,
+    values: Enumeration,
+    partition: Partition,
+    next: u64,
+    feistel: Option<Feistel>,
+}
+
+impl PermutationNode {
+    /// Partitions take every `partition.count`th value of the same permutation, which is keyed
+    /// by the seed of the partition rather than by the rng.
+    pub fn new(size: u64, values: Enumeration, partition: Partition) -> Self {
+        Self {
+            size,
+            values,
+            partition,
+            next: partition.index,
+            feistel: None,
+        }
+    }
+}
+
+impl Generator for PermutationNode {
+    type Yield = Token;
+
+    type Return = Result<Value, Error>;
+
+    fn next<R: Rng>(&mut self, rng: &mut R) -> GeneratorState<Self::Yield, Self::Return> {
+        if self.next >= self.size {
+            return GeneratorState::Complete(Err(exhausted(
+                &format!(
+                    "Could not generate more than {} unique values from generator",
+                    self.size
+                ),
+                self.partition,
+            )));
+        }
+
+        let size = self.size;
+        let partition = self.partition;
+        let feistel = self.feistel.get_or_insert_with(|| {
+            if partition.is_whole() {
+                Feistel::new(size, rng)
+            } else {
+                Feistel::new(size, &mut StdRng::seed_from_u64(partition.seed))
+            }
+        });
+        let index = feistel.permute(self.next);
+        // There are no more values when this overflows
+        self.next = self.next.checked_add(partition.count).unwrap_or(u64::MAX);
+
+        GeneratorState::Complete(Ok((self.values)(index)))
+    }
+}
+
+/// A pseudo-random permutation of `0..size`.
+///
+/// The network permutes the integers of the smallest domain of an even number of bits holding
+/// `size` values, and permuted indices which fall outside of `0..size` are permuted again until
+/// they fall inside (cycle walking). Since the domain holds less than `4 * size` values, this
+/// takes a few rounds at most on average.
+struct Feistel {
+    size: u64,
+    half_bits: u32,
+    keys: [u64; FEISTEL_ROUNDS],
+}
+
+impl Feistel {
+    fn new<R: Rng>(size: u64, rng: &mut R) -> Self {
+        let bits = u64::BITS - size.saturating_sub(1).leading_zeros();
+        Self {
+            size,
+            half_bits: bits.div_ceil(2).max(1),
+            keys: rng.gen(),
+        }
+    }
+
+    fn permute(&self, index: u64) -> u64 {
+        let mut index = self.encrypt(index);
+        while index >= self.size {
+            index = self.encrypt(index);
+        }
+        index
+    }
+
+    fn encrypt(&self, index: u64) -> u64 {
+        let mask = (1u64 << self.half_bits) - 1;
+        let mut left = (index >> self.half_bits) & mask;
+        let mut right = index & mask;
+        for key in self.keys {
+            let round = mix(right ^ key) & mask;
+            (left, right) = (right, left ^ round);
+        }
+        (left << self.half_bits) | right
+    }
+}
+
+/// The finalizer of SplitMix64, a cheap function whose output bits all depend on all the bits of
+/// its input.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn exhausted(message: &str, partition: Partition) -> Error {
+    if partition.is_whole() {
+        failed_crate!(
+            target: Release,
+            "{}: try reducing the number of values generated",
+            message
+        )
+    } else {
+        failed_crate!(
+            target: Release,
+            "{}: try reducing the number of values generated or the number of threads",
+            message
+        )
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -84,4 +309,91 @@ pub mod tests {
 
         assert!(output.iter().any(Result::is_err));
     }
+
+    #[test]
+    fn unique_node_partition() {
+        let mut rng = rand::thread_rng();
+        let mut all = std::collections::HashSet::new();
+        for index in 0..2 {
+            let numbers = Graph::Number(NumberNode::from(
+                RandomU64::range(RangeStep::new(0, NUM_GENERATED as u64, 1)).unwrap(),
+            ));
+            let partition = Partition::new(index, 2).unwrap();
+            let output = UniqueNode::hash_partition(numbers, None, partition)
+                .repeat(NUM_GENERATED / 4)
+                .complete(&mut rng);
+
+            for value in output {
+                assert!(all.insert(format!("{:?}", value.unwrap())));
+            }
+        }
+        assert_eq!(all.len(), NUM_GENERATED / 2);
+    }
+
+    fn permutation(size: u64, partition: Partition) -> UniqueNode {
+        UniqueNode::Permutation(PermutationNode::new(
+            size,
+            Box::new(|index| Value::Number(index.into())),
+            partition,
+        ))
+    }
+
+    #[test]
+    fn permutation_node() {
+        for size in [1, 2, 3, 17, 1000, NUM_GENERATED as u64] {
+            let mut rng = StdRng::seed_from_u64(size);
+            let output: Vec<_> = permutation(size, Partition::default())
+                .repeat(size as usize)
+                .complete(&mut rng)
+                .into_iter()
+                .map(Result::unwrap)
+                .collect();
+
+            let seen: std::collections::HashSet<_> = output.iter().cloned().collect();
+            let expected: Vec<_> = (0..size).map(|n| Value::Number(n.into())).collect();
+            assert_eq!(seen.len(), expected.len());
+            assert!(expected.iter().all(|value| seen.contains(value)));
+            if size > 3 {
+                assert_ne!(output, expected);
+            }
+        }
+
+        let output = permutation(10, Partition::default())
+            .repeat(11)
+            .complete(&mut rand::thread_rng());
+        assert!(output[..10].iter().all(Result::is_ok));
+        assert!(output[10].is_err());
+    }
+
+    #[test]
+    fn permutation_node_seeded() {
+        let sample = |seed| {
+            permutation(NUM_GENERATED as u64, Partition::default())
+                .repeat(NUM_GENERATED)
+                .complete(&mut StdRng::seed_from_u64(seed))
+                .into_iter()
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(sample(1), sample(1));
+        assert_ne!(sample(1), sample(2));
+    }
+
+    #[test]
+    fn permutation_node_partition() {
+        let mut all = std::collections::HashSet::new();
+        for index in 0..3 {
+            // Each partition is sampled from its own rng, but shares the seed of the partition
+            let mut rng = StdRng::seed_from_u64(index);
+            let partition = Partition::new(index, 3).unwrap().with_seed(42);
+            let output = permutation(NUM_GENERATED as u64, partition)
+                .repeat(NUM_GENERATED / 3)
+                .complete(&mut rng);
+
+            for value in output {
+                assert!(all.insert(value.unwrap()));
+            }
+        }
+        assert_eq!(all.len(), NUM_GENERATED / 3 * 3);
+    }
 }
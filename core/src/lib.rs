@@ -103,7 +103,7 @@ pub mod graph;
 pub use graph::{Graph, Value};
 
 pub mod compile;
-pub use compile::{Compile, Compiler};
+pub use compile::{Compile, Compiler, Partition};
 
 #[cfg(test)]
 pub mod tests {
@@ -163,11 +163,52 @@ impl ArrayContent {
     }
 }
 
+impl ArrayContent {
+    /// Whether the objects of the array reference their parents with a `cardinality`.
+    pub fn has_relation(&self) -> Result<bool> {
+        let Content::Object(object) = self.content.as_ref() else {
+            return Ok(false);
+        };
+        let references = || {
+            object.iter().filter_map(|(name, content)| match content {
+                Content::SameAs(same_as) => Some((name.as_str(), same_as)),
+                _ => None,
+            })
+        };
+        let parents: Vec<_> = references()
+            .filter(|(_, same_as)| same_as.cardinality.is_some())
+            .map(|(name, _)| name)
+            .collect();
+        if parents.len() > 1 {
+            return Err(anyhow!(
+                "only one field of an array's objects can have a 'cardinality', found {}",
+                parents.join(", ")
+            ));
+        }
+        if parents.is_empty() {
+            if let Some((name, _)) = references().find(|(_, same_as)| same_as.distinct) {
+                return Err(anyhow!(
+                    "the 'distinct' reference '{}' requires another field of the same objects to have a 'cardinality'",
+                    name
+                ));
+            }
+        }
+        Ok(!parents.is_empty())
+    }
+}
+
 impl Compile for ArrayContent {
     fn compile<'a, C: Compiler<'a>>(&'a self, mut compiler: C) -> Result<Graph> {
-        let length = compiler.build("length", self.length.as_ref())?.into_size();
+        let mut length = compiler.build("length", self.length.as_ref())?;
+        if self.has_relation()? {
+            // The length of the array is the number of children of all the parents
+            length = Graph::Relation(RelationNode::Children(compiler.relation()));
+        }
         let content = compiler.build("content", &self.content)?;
-        Ok(Graph::Array(ArrayNode::new_with(length, content)))
+        Ok(Graph::Array(ArrayNode::new_with(
+            length.into_size(),
+            content,
+        )))
     }
 }
 
@@ -31,6 +31,30 @@ pub enum ChronoValue {
     DateTime(DateTime<FixedOffset>),
 }
 
+impl ChronoValue {
+    /// Adds a duration, or returns `None` if the result is out of range. Times wrap around
+    /// midnight.
+    pub fn checked_add_signed(self, rhs: Duration) -> Option<Self> {
+        match self {
+            Self::NaiveDate(n_d) => n_d.checked_add_signed(rhs).map(Self::NaiveDate),
+            Self::NaiveTime(n_t) => Some(Self::NaiveTime(n_t.overflowing_add_signed(rhs).0)),
+            Self::NaiveDateTime(n_dt) => n_dt.checked_add_signed(rhs).map(Self::NaiveDateTime),
+            Self::DateTime(dt) => dt.checked_add_signed(rhs).map(Self::DateTime),
+        }
+    }
+
+    /// Subtracts a duration, or returns `None` if the result is out of range. Times wrap around
+    /// midnight.
+    pub fn checked_sub_signed(self, rhs: Duration) -> Option<Self> {
+        match self {
+            Self::NaiveDate(n_d) => n_d.checked_sub_signed(rhs).map(Self::NaiveDate),
+            Self::NaiveTime(n_t) => Some(Self::NaiveTime(n_t.overflowing_sub_signed(rhs).0)),
+            Self::NaiveDateTime(n_dt) => n_dt.checked_sub_signed(rhs).map(Self::NaiveDateTime),
+            Self::DateTime(dt) => dt.checked_sub_signed(rhs).map(Self::DateTime),
+        }
+    }
+}
+
 impl std::ops::Add<Duration> for ChronoValue {
     type Output = Self;
 
@@ -0,0 +1,553 @@
+//! Computed fields: a small expression language over other fields.
+//!
+//! Expressions are made of:
+//!
+//! - literals: numbers (`42`, `0.5`), strings (`"abc"` or `'abc'`), `true`, `false` and `null`;
+//! - references to other fields, using the same syntax as `same_as` (`@users.content.id`);
+//! - the binary operators `+`, `-`, `*`, `/` and `%` and the unary operator `-`, with the usual
+//!   precedence (parentheses can be used to override it);
+//! - function calls (`round(@orders.content.price * 1.2)`).
+//!
+//! Parsing happens when the schema is deserialized, so that syntax errors are reported along
+//! with the other errors in the schema. Evaluation is implemented in
+//! [`graph::expression`](crate::graph::expression).
+
+use super::prelude::*;
+
+use super::FieldRef;
+
+use crate::graph::Value;
+use synth_gen::value::Number;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnaryOp {
+    Neg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl std::fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+        };
+        write!(f, "{op}")
+    }
+}
+
+macro_rules! functions {
+    {
+        $($variant:ident($name:literal, $arity:expr),)*
+    } => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Function {
+            $($variant,)*
+        }
+
+        impl Function {
+            const NAMES: &'static [&'static str] = &[$($name,)*];
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)*
+                }
+            }
+
+            /// The accepted number of arguments (`None` for variadic functions).
+            fn arity(&self) -> Option<usize> {
+                match self {
+                    $(Self::$variant => $arity,)*
+                }
+            }
+        }
+
+        impl FromStr for Function {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                match s {
+                    $($name => Ok(Self::$variant),)*
+                    _ => Err(anyhow!(
+                        "unknown function `{}`{}",
+                        s,
+                        suggest_closest(Self::NAMES.iter(), s).unwrap_or_default()
+                    )),
+                }
+            }
+        }
+    };
+}
+
+functions! {
+    Abs("abs", Some(1)),
+    Round("round", Some(1)),
+    Floor("floor", Some(1)),
+    Ceil("ceil", Some(1)),
+    Min("min", None),
+    Max("max", None),
+    Lower("lower", Some(1)),
+    Upper("upper", Some(1)),
+    Trim("trim", Some(1)),
+    Concat("concat", None),
+    Seconds("seconds", Some(1)),
+    Minutes("minutes", Some(1)),
+    Hours("hours", Some(1)),
+    Days("days", Some(1)),
+    Weeks("weeks", Some(1)),
+}
+
+/// A node of the syntax tree of an expression.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum Expr {
+    Literal(Value),
+    /// A reference to another field, as an index into [`Expression::references`].
+    Reference(usize),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Function, Vec<Expr>),
+}
+
+/// A parsed expression, which (de)serializes as its source.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Expr,
+    references: Vec<FieldRef>,
+}
+
+impl Expression {
+    pub fn root(&self) -> &Expr {
+        &self.root
+    }
+
+    /// The distinct fields referenced by the expression.
+    pub fn references(&self) -> &[FieldRef] {
+        &self.references
+    }
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Hash for Expression {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.source.hash(state)
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = anyhow::Error;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let tokens =
+            tokenize(source).with_context(|| format!("could not parse expression `{source}`"))?;
+        let mut parser = ExpressionParser {
+            tokens: tokens.into_iter().peekable(),
+            references: Vec::new(),
+        };
+        let root = parser
+            .parse()
+            .with_context(|| format!("could not parse expression `{source}`"))?;
+        Ok(Self {
+            source: source.to_string(),
+            root,
+            references: parser.references,
+        })
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(deserializer)?;
+        source
+            .parse()
+            .map_err(|err| D::Error::custom(format!("{err:#}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ExprToken {
+    Number(Number),
+    String(String),
+    Reference(FieldRef),
+    Ident(String),
+    Op(char),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl Display for ExprToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::String(string) => write!(f, "{string:?}"),
+            Self::Reference(reference) => write!(f, "@{reference}"),
+            Self::Ident(ident) => write!(f, "{ident}"),
+            Self::Op(op) => write!(f, "{op}"),
+            Self::LeftParen => write!(f, "("),
+            Self::RightParen => write!(f, ")"),
+            Self::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<ExprToken>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '+' | '-' | '*' | '/' | '%' => ExprToken::Op(c),
+            '(' => ExprToken::LeftParen,
+            ')' => ExprToken::RightParen,
+            ',' => ExprToken::Comma,
+            '"' | '\'' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) => string.push(escaped),
+                            None => bail!("unterminated string literal"),
+                        },
+                        Some((_, end)) if end == c => break,
+                        Some((_, other)) => string.push(other),
+                        None => bail!("unterminated string literal"),
+                    }
+                }
+                ExprToken::String(string)
+            }
+            '@' => {
+                let mut end = start + 1;
+                let mut quoted = false;
+                while let Some(&(idx, next)) = chars.peek() {
+                    if next == '"' {
+                        quoted = !quoted;
+                    } else if !(quoted || next.is_alphanumeric() || next == '_' || next == '.') {
+                        break;
+                    }
+                    chars.next();
+                    end = idx + next.len_utf8();
+                }
+                let reference = &source[start + 1..end];
+                ExprToken::Reference(
+                    reference
+                        .parse()
+                        .with_context(|| format!("invalid reference `@{reference}`"))?,
+                )
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = start + 1;
+                while let Some(&(idx, next)) = chars.peek() {
+                    if next.is_ascii_digit() || next == '.' {
+                        chars.next();
+                        end = idx + 1;
+                    } else {
+                        break;
+                    }
+                }
+                let literal = &source[start..end];
+                let number = if literal.contains('.') {
+                    literal.parse::<f64>().map(Number::from).ok()
+                } else {
+                    literal.parse::<u64>().map(Number::from).ok()
+                };
+                ExprToken::Number(
+                    number.ok_or_else(|| anyhow!("invalid number literal `{}`", literal))?,
+                )
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(idx, next)) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' {
+                        chars.next();
+                        end = idx + next.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+                ExprToken::Ident(source[start..end].to_string())
+            }
+            other => bail!("unexpected character `{}`", other),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser for the grammar:
+///
+/// ```text
+/// expr    := term (('+' | '-') term)*
+/// term    := unary (('*' | '/' | '%') unary)*
+/// unary   := '-' unary | primary
+/// primary := number | string | reference | ident '(' (expr (',' expr)*)? ')' | ident
+///          | '(' expr ')'
+/// ```
+struct ExpressionParser {
+    tokens: Peekable<std::vec::IntoIter<ExprToken>>,
+    references: Vec<FieldRef>,
+}
+
+impl ExpressionParser {
+    fn parse(&mut self) -> Result<Expr> {
+        let expr = self.parse_expr()?;
+        match self.tokens.next() {
+            None => Ok(expr),
+            Some(token) => bail!("unexpected `{}`", token),
+        }
+    }
+
+    fn parse_binary<F>(&mut self, ops: &[(char, BinaryOp)], mut operand: F) -> Result<Expr>
+    where
+        F: FnMut(&mut Self) -> Result<Expr>,
+    {
+        let mut lhs = operand(self)?;
+        while let Some(ExprToken::Op(c)) = self.tokens.peek() {
+            match ops.iter().find(|(op, _)| op == c) {
+                Some((_, op)) => {
+                    let op = *op;
+                    self.tokens.next();
+                    let rhs = operand(self)?;
+                    lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+                }
+                None => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        self.parse_binary(
+            &[('+', BinaryOp::Add), ('-', BinaryOp::Sub)],
+            Self::parse_term,
+        )
+    }
+
+    fn parse_term(&mut self) -> Result<Expr> {
+        self.parse_binary(
+            &[
+                ('*', BinaryOp::Mul),
+                ('/', BinaryOp::Div),
+                ('%', BinaryOp::Rem),
+            ],
+            Self::parse_unary,
+        )
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if let Some(ExprToken::Op('-')) = self.tokens.peek() {
+            self.tokens.next();
+            Ok(Expr::Unary(UnaryOp::Neg, Box::new(self.parse_unary()?)))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        match self.tokens.next() {
+            Some(ExprToken::Number(number)) => Ok(Expr::Literal(Value::Number(number))),
+            Some(ExprToken::String(string)) => Ok(Expr::Literal(Value::String(string))),
+            Some(ExprToken::Reference(reference)) => {
+                let idx = match self.references.iter().position(|r| *r == reference) {
+                    Some(idx) => idx,
+                    None => {
+                        self.references.push(reference);
+                        self.references.len() - 1
+                    }
+                };
+                Ok(Expr::Reference(idx))
+            }
+            Some(ExprToken::LeftParen) => {
+                let expr = self.parse_expr()?;
+                self.expect(ExprToken::RightParen)?;
+                Ok(expr)
+            }
+            Some(ExprToken::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null(()))),
+                _ => {
+                    let function: Function = ident.parse()?;
+                    self.expect(ExprToken::LeftParen)?;
+                    let args = self.parse_args()?;
+                    match function.arity() {
+                        Some(arity) if arity != args.len() => bail!(
+                            "function `{}` expects {} argument(s), found {}",
+                            function.name(),
+                            arity,
+                            args.len()
+                        ),
+                        None if args.is_empty() => bail!(
+                            "function `{}` expects at least one argument",
+                            function.name()
+                        ),
+                        _ => Ok(Expr::Call(function, args)),
+                    }
+                }
+            },
+            Some(token) => bail!("unexpected `{}`", token),
+            None => bail!("unexpected end of expression"),
+        }
+    }
+
+    fn parse_args(&mut self) -> Result<Vec<Expr>> {
+        let mut args = Vec::new();
+        if let Some(ExprToken::RightParen) = self.tokens.peek() {
+            self.tokens.next();
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_expr()?);
+            match self.tokens.next() {
+                Some(ExprToken::Comma) => continue,
+                Some(ExprToken::RightParen) => return Ok(args),
+                Some(token) => bail!("expected `,` or `)`, found `{}`", token),
+                None => bail!("expected `,` or `)`, found the end of expression"),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: ExprToken) -> Result<()> {
+        match self.tokens.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => bail!("expected `{}`, found `{}`", expected, token),
+            None => bail!("expected `{}`, found the end of expression", expected),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct ExpressionContent {
+    pub expression: Expression,
+}
+
+impl Compile for ExpressionContent {
+    fn compile<'a, C: Compiler<'a>>(&'a self, mut compiler: C) -> Result<Graph> {
+        let arguments = self
+            .expression
+            .references()
+            .iter()
+            .map(|reference| compiler.get(reference.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Graph::Expression(ExpressionNode::new(
+            self.expression.clone(),
+            arguments,
+        )))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Expr {
+        source.parse::<Expression>().unwrap().root
+    }
+
+    fn parse_err(source: &str) -> String {
+        format!("{:#}", source.parse::<Expression>().unwrap_err())
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            parse("1 + 2 * -3"),
+            Expr::Binary(
+                BinaryOp::Add,
+                Box::new(Expr::Literal(Value::Number(1u64.into()))),
+                Box::new(Expr::Binary(
+                    BinaryOp::Mul,
+                    Box::new(Expr::Literal(Value::Number(2u64.into()))),
+                    Box::new(Expr::Unary(
+                        UnaryOp::Neg,
+                        Box::new(Expr::Literal(Value::Number(3u64.into())))
+                    ))
+                ))
+            )
+        );
+        assert_eq!(parse("(1 - 2) - 3"), parse("1 - 2 - 3"),);
+    }
+
+    #[test]
+    fn references() {
+        let expression: Expression = "@orders.content.price * @orders.content.quantity + \
+             round(@orders.content.price)"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            expression.references(),
+            &[
+                "orders.content.price".parse::<FieldRef>().unwrap(),
+                "orders.content.quantity".parse::<FieldRef>().unwrap()
+            ]
+        );
+        assert_eq!(
+            "concat(@users.content.\"first.name\", ' ', 'x')"
+                .parse::<Expression>()
+                .unwrap()
+                .references(),
+            &["users.content.\"first.name\"".parse::<FieldRef>().unwrap()]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(parse_err("1 +").contains("unexpected end of expression"));
+        assert!(parse_err("(1 + 2").contains("expected `)`"));
+        assert!(parse_err("rond(1)").contains("unknown function `rond`, did you mean 'round'?"));
+        assert!(parse_err("abs(1, 2)").contains("expects 1 argument(s), found 2"));
+        assert!(parse_err("'abc").contains("unterminated string literal"));
+        assert!(parse_err("1 # 2").contains("unexpected character `#`"));
+        assert!(parse_err("@orders.").contains("invalid reference"));
+    }
+
+    #[test]
+    fn serde() {
+        let content: ExpressionContent = serde_json::from_value(json!({
+            "expression": "days(@a.b) + @a.c"
+        }))
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&content).unwrap(),
+            json!({
+                "expression": "days(@a.b) + @a.c"
+            })
+        );
+    }
+}
@@ -24,7 +24,10 @@ mod r#bool;
 pub use self::r#bool::BoolContent;
 
 mod number;
-pub use number::{number_content, NumberContent, NumberContentKind, NumberKindExt, RangeStep};
+pub use number::{
+    number_content, Exponential, LogNormal, Normal, NumberContent, NumberContentKind,
+    NumberKindExt, Poisson, RangeStep, Zipf,
+};
 
 mod string;
 pub use string::{
@@ -64,6 +67,12 @@ pub use unique::{UniqueAlgorithm, UniqueContent};
 pub mod hidden;
 pub use hidden::HiddenContent;
 
+pub mod expression;
+pub use expression::{BinaryOp, Expr, Expression, ExpressionContent, Function, UnaryOp};
+
+pub mod switch;
+pub use switch::SwitchContent;
+
 use prelude::*;
 
 use super::{FieldRef, Namespace};
@@ -96,11 +105,21 @@ pub trait Find<C> {
         R: AsRef<str>;
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Hash)]
 #[serde(deny_unknown_fields)]
 pub struct SameAsContent {
     #[serde(rename = "ref")]
     pub ref_: FieldRef,
+    /// The number of elements of the array which reference each value of `ref`, when the
+    /// reference is a field of the objects of an array.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cardinality: Option<Box<Content>>,
+    /// Whether the elements of the array which reference the same value of the field with a
+    /// `cardinality` reference distinct values of `ref`, as in a join table.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub distinct: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -277,7 +296,11 @@ macro_rules! content {
                     {
                         if let Some(s) = v.strip_prefix("@") {
                             let ref_ = FieldRef::deserialize(s.into_deserializer())?;
-                            Ok(Content::SameAs(SameAsContent { ref_ }))
+                            Ok(Content::SameAs(SameAsContent {
+                                ref_,
+                                cardinality: None,
+                                distinct: false,
+                            }))
                         } else {
                             Ok(Content::String(StringContent::Constant(ConstantContent::from(v.to_string()))))
                         }
@@ -316,6 +339,8 @@ content! {
         Unique(UniqueContent) => "missing a `content` field",
         Datasource(DatasourceContent) => "missing a `path` field",
         Hidden(HiddenContent) => "missing a `content` field",
+        Expression(ExpressionContent) => "missing an `expression` field",
+        Switch(SwitchContent) => "missing an `on` and `cases` field",
         Empty(EmptyContent) => None,
     }
 }
@@ -388,6 +413,14 @@ impl Content {
                 Ok(true)
             }
             Self::Unique(unique) => unique.content.is_scalar(ns),
+            Self::Switch(switch) => {
+                for content in switch.iter() {
+                    if !content.is_scalar(ns)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
             _ => Ok(true),
         }
     }
@@ -424,6 +457,8 @@ impl Content {
             Self::Unique(unique_content) => unique_content.content.accepts(value),
             Self::Hidden(_) => Ok(()),
             Self::SameAs(_) => Ok(()),
+            Self::Expression(_) => Ok(()),
+            Self::Switch(switch_content) => switch_content.accepts(value),
             Self::OneOf(one_of_content) => {
                 let res: Vec<_> = one_of_content
                     .iter()
@@ -510,6 +545,8 @@ impl Content {
             Content::Unique(_) => "unique".to_string(),
             Content::Hidden(_) => "hidden".to_string(),
             Content::Datasource(_) => "datasource".to_string(),
+            Content::Expression(_) => "expression".to_string(),
+            Content::Switch(_) => "switch".to_string(),
             Content::Empty(_) => "empty".to_string(),
         }
     }
@@ -641,6 +678,8 @@ impl Compile for Content {
             Self::Hidden(hidden_content) => hidden_content.compile(compiler),
             Self::Null(_) => Ok(Graph::null()),
             Self::Datasource(datasource) => datasource.compile(compiler),
+            Self::Expression(expression_content) => expression_content.compile(compiler),
+            Self::Switch(switch_content) => switch_content.compile(compiler),
             Self::Empty(_) => Err(anyhow!("unexpected empty object")),
         }
     }
@@ -648,7 +687,23 @@ impl Compile for Content {
 
 impl Compile for SameAsContent {
     fn compile<'a, C: Compiler<'a>>(&'a self, mut compiler: C) -> Result<Graph> {
-        compiler.get(self.ref_.clone())
+        match (self.cardinality.as_deref(), self.distinct) {
+            (None, false) => compiler.get(self.ref_.clone()),
+            (Some(cardinality), false) => {
+                let cardinality = compiler.build("cardinality", cardinality)?;
+                let parents = compiler.get(self.ref_.clone())?;
+                let relation = compiler.relation();
+                relation.set(parents, cardinality);
+                Ok(Graph::Relation(RelationNode::parent(relation)))
+            }
+            (None, true) => {
+                let values = compiler.get(self.ref_.clone())?;
+                Ok(Graph::Relation(compiler.relation().partner(values)))
+            }
+            (Some(_), true) => Err(anyhow!(
+                "a reference cannot both have a 'cardinality' and be 'distinct'"
+            )),
+        }
     }
 }
 
@@ -1085,6 +1140,36 @@ pub mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "`expression` generator is missing an `expression` field")]
+    fn expression_missing_subtype() {
+        let _schema: Content = schema!({
+            "type": "array",
+            "length": 1,
+            "content": {
+                "type": "object",
+                "e": {
+                    "type": "expression"
+                }
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "`switch` generator is missing an `on` and `cases` field")]
+    fn switch_missing_subtype() {
+        let _schema: Content = schema!({
+            "type": "array",
+            "length": 1,
+            "content": {
+                "type": "object",
+                "s": {
+                    "type": "switch"
+                }
+            }
+        });
+    }
+
     #[test]
     fn null_missing_subtype() {
         let _schema: Content = schema!({
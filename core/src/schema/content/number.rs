@@ -150,7 +150,7 @@ macro_rules! number_content {
         }
 
         pub mod number_content {
-            use super::{RangeStep, Categorical, NumberContent};
+            use super::{RangeStep, Categorical, NumberContent, Normal, LogNormal, Exponential, Poisson, Zipf};
             use serde::{Serialize, Deserialize};
 
             $(
@@ -323,6 +323,136 @@ impl<N> std::ops::RangeBounds<N> for RangeStep<N> {
     }
 }
 
+/// Hashing of number parameters which may be floats, and are therefore not [`Hash`](Hash).
+pub trait HashBits {
+    fn hash_bits<H: Hasher>(&self, state: &mut H);
+}
+
+macro_rules! hash_bits_impl {
+    (float $($t:ty),*) => {
+        $(
+            impl HashBits for $t {
+                fn hash_bits<H: Hasher>(&self, state: &mut H) {
+                    self.to_bits().hash(state)
+                }
+            }
+        )*
+    };
+    ($($t:ty),*) => {
+        $(
+            impl HashBits for $t {
+                fn hash_bits<H: Hasher>(&self, state: &mut H) {
+                    self.hash(state)
+                }
+            }
+        )*
+    };
+}
+
+hash_bits_impl!(i8, i16, i32, u32, i64, u64);
+hash_bits_impl!(float f32, f64);
+
+impl<N: HashBits> HashBits for Option<N> {
+    fn hash_bits<H: Hasher>(&self, state: &mut H) {
+        if let Some(value) = self {
+            value.hash_bits(state);
+        }
+    }
+}
+
+/// Parameters of statistical distributions. Whatever the subtype of the number, the parameters
+/// of the distribution are floats: samples are rounded to the nearest integer for integer
+/// subtypes. Samples can optionally be clamped to `low` and `high` (both inclusive).
+macro_rules! distribution_content {
+    {
+        $(
+            $(#[$doc:meta])*
+            $name:ident {
+                $(
+                    $(#[$param_doc:meta])*
+                    $param:ident: $param_ty:ty,
+                )*
+            },
+        )*
+    } => {
+        $(
+            $(#[$doc])*
+            #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+            #[serde(deny_unknown_fields)]
+            pub struct $name<N> {
+                $(
+                    $(#[$param_doc])*
+                    pub $param: $param_ty,
+                )*
+                #[serde(default)]
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub low: Option<N>,
+                #[serde(default)]
+                #[serde(skip_serializing_if = "Option::is_none")]
+                pub high: Option<N>,
+            }
+
+            impl<N: HashBits> Hash for $name<N> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    $(self.$param.hash_bits(state);)*
+                    self.low.hash_bits(state);
+                    self.high.hash_bits(state);
+                }
+            }
+
+            impl<N> $name<N> {
+                fn cast<F, M>(self, f: F) -> $name<M>
+                where
+                    F: Fn(N) -> M,
+                {
+                    self.try_cast::<_, _, std::convert::Infallible>(|value| Ok(f(value)))
+                        .unwrap()
+                }
+
+                fn try_cast<F, M, E>(self, f: F) -> Result<$name<M>, E>
+                where
+                    F: Fn(N) -> Result<M, E>,
+                {
+                    Ok($name::<M> {
+                        $($param: self.$param,)*
+                        low: self.low.map(&f).transpose()?,
+                        high: self.high.map(&f).transpose()?,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+distribution_content! {
+    /// A normal (gaussian) distribution.
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+    /// A log-normal distribution, i.e. the distribution of `exp(X)` where `X` follows a normal
+    /// distribution with the given `mean` and `std_dev`.
+    LogNormal {
+        mean: f64,
+        std_dev: f64,
+    },
+    /// An exponential distribution with the given `rate` (often written `lambda`).
+    Exponential {
+        rate: f64,
+    },
+    /// A Poisson distribution with the given mean `lambda`, i.e. the distribution of the number
+    /// of events happening in an interval where `lambda` events happen on average.
+    Poisson {
+        lambda: f64,
+    },
+    /// A Zipf distribution over the integers `1..=n`, where the frequency of the `k`-th integer
+    /// is proportional to `1 / k^exponent`.
+    Zipf {
+        n: u64,
+        exponent: f64,
+    },
+}
+
 macro_rules! derive_hash {
     (f32) => {
         impl Hash for RangeStep<f32> {
@@ -386,6 +516,11 @@ number_content!(
         Categorical(Categorical<u32>),
         Constant(u32),
         Id(crate::schema::Id<u32>),
+        Normal(Normal<u32>),
+        LogNormal(LogNormal<u32>),
+        Exponential(Exponential<u32>),
+        Poisson(Poisson<u32>),
+        Zipf(Zipf<u32>),
     },
     #[derive(PartialEq, Hash)]
     u64[is_u64, default_u64_range] as U64 {
@@ -393,6 +528,11 @@ number_content!(
         Categorical(Categorical<u64>),
         Constant(u64),
         Id(crate::schema::Id<u64>),
+        Normal(Normal<u64>),
+        LogNormal(LogNormal<u64>),
+        Exponential(Exponential<u64>),
+        Poisson(Poisson<u64>),
+        Zipf(Zipf<u64>),
     },
     #[derive(PartialEq, Hash)]
     i8[is_i8, default_i8_range] as I8 {
@@ -400,6 +540,11 @@ number_content!(
         Categorical(Categorical<i8>),
         Constant(i8),
         Id(crate::schema::Id<i8>),
+        Normal(Normal<i8>),
+        LogNormal(LogNormal<i8>),
+        Exponential(Exponential<i8>),
+        Poisson(Poisson<i8>),
+        Zipf(Zipf<i8>),
     },
     #[derive(PartialEq, Hash)]
     i16[is_i16, default_i16_range] as I16 {
@@ -407,6 +552,11 @@ number_content!(
         Categorical(Categorical<i16>),
         Constant(i16),
         Id(crate::schema::Id<i16>),
+        Normal(Normal<i16>),
+        LogNormal(LogNormal<i16>),
+        Exponential(Exponential<i16>),
+        Poisson(Poisson<i16>),
+        Zipf(Zipf<i16>),
     },
     #[derive(PartialEq, Hash)]
     i32[is_i32, default_i32_range] as I32 {
@@ -414,6 +564,11 @@ number_content!(
         Categorical(Categorical<i32>),
         Constant(i32),
         Id(crate::schema::Id<i32>),
+        Normal(Normal<i32>),
+        LogNormal(LogNormal<i32>),
+        Exponential(Exponential<i32>),
+        Poisson(Poisson<i32>),
+        Zipf(Zipf<i32>),
     },
     #[derive(PartialEq, Hash)]
     i64[is_i64, default_i64_range] as I64 {
@@ -421,19 +576,35 @@ number_content!(
         Categorical(Categorical<i64>),
         Constant(i64),
         Id(crate::schema::Id<i64>),
+        Normal(Normal<i64>),
+        LogNormal(LogNormal<i64>),
+        Exponential(Exponential<i64>),
+        Poisson(Poisson<i64>),
+        Zipf(Zipf<i64>),
     },
     f64[is_f64, default_f64_range] as F64 {
         Range(RangeStep<f64>),
         Constant(f64),
+        Normal(Normal<f64>),
+        LogNormal(LogNormal<f64>),
+        Exponential(Exponential<f64>),
+        Poisson(Poisson<f64>),
+        Zipf(Zipf<f64>),
     },
     f32[is_f32, default_f32_range] as F32 {
         Range(RangeStep<f32>),
         Constant(f32),
+        Normal(Normal<f32>),
+        LogNormal(LogNormal<f32>),
+        Exponential(Exponential<f32>),
+        Poisson(Poisson<f32>),
+        Zipf(Zipf<f32>),
     },
 );
 
 impl Compile for NumberContent {
-    fn compile<'a, C: Compiler<'a>>(&'a self, _compiler: C) -> Result<Graph> {
+    fn compile<'a, C: Compiler<'a>>(&'a self, compiler: C) -> Result<Graph> {
+        let partition = compiler.partition();
         let number_node = match self {
             Self::U64(u64_content) => {
                 let random_u64 = match u64_content {
@@ -443,9 +614,24 @@ impl Compile for NumberContent {
                     }
                     number_content::U64::Constant(val) => RandomU64::constant(*val),
                     number_content::U64::Id(id) => {
-                        let gen = Incrementing::new_at(id.start_at.unwrap_or(1));
+                        let gen = Incrementing::new_partition(id.start_at.unwrap_or(1), partition)?;
                         RandomU64::incrementing(gen)
                     }
+                    number_content::U64::Normal(normal) => {
+                        RandomU64::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::U64::LogNormal(log_normal) => {
+                        RandomU64::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::U64::Exponential(exponential) => {
+                        RandomU64::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::U64::Poisson(poisson) => {
+                        RandomU64::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::U64::Zipf(zipf) => {
+                        RandomU64::distributed(Distributed::zipf(*zipf)?)
+                    }
                 };
                 random_u64.into()
             }
@@ -456,8 +642,23 @@ impl Compile for NumberContent {
                         RandomI64::categorical(categorical_content.clone())
                     }
                     number_content::I64::Constant(val) => RandomI64::constant(*val),
-                    number_content::I64::Id(id) => {
-                        RandomI64::incrementing(Incrementing::new_at(id.start_at.unwrap_or(1)))
+                    number_content::I64::Id(id) => RandomI64::incrementing(
+                        Incrementing::new_partition(id.start_at.unwrap_or(1), partition)?,
+                    ),
+                    number_content::I64::Normal(normal) => {
+                        RandomI64::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::I64::LogNormal(log_normal) => {
+                        RandomI64::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::I64::Exponential(exponential) => {
+                        RandomI64::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::I64::Poisson(poisson) => {
+                        RandomI64::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::I64::Zipf(zipf) => {
+                        RandomI64::distributed(Distributed::zipf(*zipf)?)
                     }
                 };
                 random_i64.into()
@@ -466,6 +667,21 @@ impl Compile for NumberContent {
                 let random_f64 = match f64_content {
                     number_content::F64::Range(range) => RandomF64::range(*range)?,
                     number_content::F64::Constant(val) => RandomF64::constant(*val),
+                    number_content::F64::Normal(normal) => {
+                        RandomF64::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::F64::LogNormal(log_normal) => {
+                        RandomF64::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::F64::Exponential(exponential) => {
+                        RandomF64::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::F64::Poisson(poisson) => {
+                        RandomF64::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::F64::Zipf(zipf) => {
+                        RandomF64::distributed(Distributed::zipf(*zipf)?)
+                    }
                 };
                 random_f64.into()
             }
@@ -476,8 +692,23 @@ impl Compile for NumberContent {
                         RandomU32::categorical(categorical_content.clone())
                     }
                     number_content::U32::Constant(val) => RandomU32::constant(*val),
-                    number_content::U32::Id(id) => {
-                        RandomU32::incrementing(Incrementing::new_at(id.start_at.unwrap_or(1)))
+                    number_content::U32::Id(id) => RandomU32::incrementing(
+                        Incrementing::new_partition(id.start_at.unwrap_or(1), partition)?,
+                    ),
+                    number_content::U32::Normal(normal) => {
+                        RandomU32::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::U32::LogNormal(log_normal) => {
+                        RandomU32::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::U32::Exponential(exponential) => {
+                        RandomU32::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::U32::Poisson(poisson) => {
+                        RandomU32::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::U32::Zipf(zipf) => {
+                        RandomU32::distributed(Distributed::zipf(*zipf)?)
                     }
                 };
                 random_u32.into()
@@ -489,8 +720,23 @@ impl Compile for NumberContent {
                         RandomI32::categorical(categorical_content.clone())
                     }
                     number_content::I32::Constant(val) => RandomI32::constant(*val),
-                    number_content::I32::Id(id) => {
-                        RandomI32::incrementing(Incrementing::new_at(id.start_at.unwrap_or(1)))
+                    number_content::I32::Id(id) => RandomI32::incrementing(
+                        Incrementing::new_partition(id.start_at.unwrap_or(1), partition)?,
+                    ),
+                    number_content::I32::Normal(normal) => {
+                        RandomI32::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::I32::LogNormal(log_normal) => {
+                        RandomI32::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::I32::Exponential(exponential) => {
+                        RandomI32::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::I32::Poisson(poisson) => {
+                        RandomI32::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::I32::Zipf(zipf) => {
+                        RandomI32::distributed(Distributed::zipf(*zipf)?)
                     }
                 };
                 random_i32.into()
@@ -499,6 +745,21 @@ impl Compile for NumberContent {
                 let random_f32 = match f32_content {
                     number_content::F32::Range(range) => RandomF32::range(*range)?,
                     number_content::F32::Constant(val) => RandomF32::constant(*val),
+                    number_content::F32::Normal(normal) => {
+                        RandomF32::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::F32::LogNormal(log_normal) => {
+                        RandomF32::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::F32::Exponential(exponential) => {
+                        RandomF32::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::F32::Poisson(poisson) => {
+                        RandomF32::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::F32::Zipf(zipf) => {
+                        RandomF32::distributed(Distributed::zipf(*zipf)?)
+                    }
                 };
                 random_f32.into()
             }
@@ -509,8 +770,23 @@ impl Compile for NumberContent {
                         RandomI8::categorical(categorical_content.clone())
                     }
                     number_content::I8::Constant(val) => RandomI8::constant(*val),
-                    number_content::I8::Id(id) => {
-                        RandomI8::incrementing(Incrementing::new_at(id.start_at.unwrap_or(1)))
+                    number_content::I8::Id(id) => RandomI8::incrementing(
+                        Incrementing::new_partition(id.start_at.unwrap_or(1), partition)?,
+                    ),
+                    number_content::I8::Normal(normal) => {
+                        RandomI8::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::I8::LogNormal(log_normal) => {
+                        RandomI8::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::I8::Exponential(exponential) => {
+                        RandomI8::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::I8::Poisson(poisson) => {
+                        RandomI8::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::I8::Zipf(zipf) => {
+                        RandomI8::distributed(Distributed::zipf(*zipf)?)
                     }
                 };
                 random_i8.into()
@@ -522,8 +798,23 @@ impl Compile for NumberContent {
                         RandomI16::categorical(categorical_content.clone())
                     }
                     number_content::I16::Constant(val) => RandomI16::constant(*val),
-                    number_content::I16::Id(id) => {
-                        RandomI16::incrementing(Incrementing::new_at(id.start_at.unwrap_or(1)))
+                    number_content::I16::Id(id) => RandomI16::incrementing(
+                        Incrementing::new_partition(id.start_at.unwrap_or(1), partition)?,
+                    ),
+                    number_content::I16::Normal(normal) => {
+                        RandomI16::distributed(Distributed::normal(*normal)?)
+                    }
+                    number_content::I16::LogNormal(log_normal) => {
+                        RandomI16::distributed(Distributed::log_normal(*log_normal)?)
+                    }
+                    number_content::I16::Exponential(exponential) => {
+                        RandomI16::distributed(Distributed::exponential(*exponential)?)
+                    }
+                    number_content::I16::Poisson(poisson) => {
+                        RandomI16::distributed(Distributed::poisson(*poisson)?)
+                    }
+                    number_content::I16::Zipf(zipf) => {
+                        RandomI16::distributed(Distributed::zipf(*zipf)?)
                     }
                 };
                 random_i16.into()
@@ -640,6 +931,58 @@ impl Categorical<i64> {
     }
 }
 
+macro_rules! distribution_upcast_impl {
+    ($($name:ident),*) => {
+        $(
+            impl $name<u64> {
+                pub fn upcast(self, to: NumberContentKind) -> Result<NumberContent> {
+                    match to {
+                        NumberContentKind::U64 => Ok(number_content::U64::$name(self).into()),
+                        NumberContentKind::I64 => {
+                            let cast = self.try_cast(i64::try_from)?;
+                            Ok(number_content::I64::$name(cast).into())
+                        }
+                        NumberContentKind::F64 => {
+                            let cast = self.cast(|value| value as f64);
+                            Ok(number_content::F64::$name(cast).into())
+                        }
+                    }
+                }
+            }
+
+            impl $name<i64> {
+                pub fn upcast(self, to: NumberContentKind) -> Result<NumberContent> {
+                    match to {
+                        NumberContentKind::U64 => Err(failed!(
+                            target: Release,
+                            "cannot downcast numerical subtypes"
+                        )),
+                        NumberContentKind::I64 => Ok(number_content::I64::$name(self).into()),
+                        NumberContentKind::F64 => {
+                            let cast = self.cast(|value| value as f64);
+                            Ok(number_content::F64::$name(cast).into())
+                        }
+                    }
+                }
+            }
+
+            impl $name<f64> {
+                pub fn upcast(self, to: NumberContentKind) -> Result<NumberContent> {
+                    match to {
+                        NumberContentKind::U64 | NumberContentKind::I64 => Err(failed!(
+                            target: Release,
+                            "cannot downcast numerical subtypes"
+                        )),
+                        NumberContentKind::F64 => Ok(number_content::F64::$name(self).into()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+distribution_upcast_impl!(Normal, LogNormal, Exponential, Poisson, Zipf);
+
 impl number_content::U64 {
     pub fn upcast(self, to: NumberContentKind) -> Result<NumberContent> {
         match self {
@@ -660,6 +1003,11 @@ impl number_content::U64 {
                 target: Release,
                 "cannot upcast an id number subtype: only unsigned integers are supported"
             )),
+            Self::Normal(normal) => normal.upcast(to),
+            Self::LogNormal(log_normal) => log_normal.upcast(to),
+            Self::Exponential(exponential) => exponential.upcast(to),
+            Self::Poisson(poisson) => poisson.upcast(to),
+            Self::Zipf(zipf) => zipf.upcast(to),
         }
     }
 }
@@ -681,6 +1029,11 @@ impl number_content::I64 {
                 }
             },
             Self::Id(id) => id.upcast(to),
+            Self::Normal(normal) => normal.upcast(to),
+            Self::LogNormal(log_normal) => log_normal.upcast(to),
+            Self::Exponential(exponential) => exponential.upcast(to),
+            Self::Poisson(poisson) => poisson.upcast(to),
+            Self::Zipf(zipf) => zipf.upcast(to),
         }
     }
 }
@@ -700,6 +1053,11 @@ impl number_content::F64 {
                 )),
                 NumberContentKind::F64 => Ok(self.into()),
             },
+            Self::Normal(normal) => normal.upcast(to),
+            Self::LogNormal(log_normal) => log_normal.upcast(to),
+            Self::Exponential(exponential) => exponential.upcast(to),
+            Self::Poisson(poisson) => poisson.upcast(to),
+            Self::Zipf(zipf) => zipf.upcast(to),
         }
     }
 }
@@ -709,6 +1067,11 @@ impl Hash for number_content::F32 {
         match self {
             Self::Range(range) => range.hash(state),
             Self::Constant(constant) => constant.to_bits().hash(state),
+            Self::Normal(normal) => normal.hash(state),
+            Self::LogNormal(log_normal) => log_normal.hash(state),
+            Self::Exponential(exponential) => exponential.hash(state),
+            Self::Poisson(poisson) => poisson.hash(state),
+            Self::Zipf(zipf) => zipf.hash(state),
         }
     }
 }
@@ -724,6 +1087,26 @@ impl PartialEq for number_content::F32 {
                 Self::Constant(o_constant) => constant == o_constant,
                 _ => false,
             },
+            Self::Normal(normal) => match other {
+                Self::Normal(o_normal) => normal == o_normal,
+                _ => false,
+            },
+            Self::LogNormal(log_normal) => match other {
+                Self::LogNormal(o_log_normal) => log_normal == o_log_normal,
+                _ => false,
+            },
+            Self::Exponential(exponential) => match other {
+                Self::Exponential(o_exponential) => exponential == o_exponential,
+                _ => false,
+            },
+            Self::Poisson(poisson) => match other {
+                Self::Poisson(o_poisson) => poisson == o_poisson,
+                _ => false,
+            },
+            Self::Zipf(zipf) => match other {
+                Self::Zipf(o_zipf) => zipf == o_zipf,
+                _ => false,
+            },
         }
     }
 }
@@ -733,6 +1116,11 @@ impl Hash for number_content::F64 {
         match self {
             Self::Range(range) => range.hash(state),
             Self::Constant(constant) => constant.to_bits().hash(state),
+            Self::Normal(normal) => normal.hash(state),
+            Self::LogNormal(log_normal) => log_normal.hash(state),
+            Self::Exponential(exponential) => exponential.hash(state),
+            Self::Poisson(poisson) => poisson.hash(state),
+            Self::Zipf(zipf) => zipf.hash(state),
         }
     }
 }
@@ -748,6 +1136,26 @@ impl PartialEq for number_content::F64 {
                 Self::Constant(o_constant) => constant == o_constant,
                 _ => false,
             },
+            Self::Normal(normal) => match other {
+                Self::Normal(o_normal) => normal == o_normal,
+                _ => false,
+            },
+            Self::LogNormal(log_normal) => match other {
+                Self::LogNormal(o_log_normal) => log_normal == o_log_normal,
+                _ => false,
+            },
+            Self::Exponential(exponential) => match other {
+                Self::Exponential(o_exponential) => exponential == o_exponential,
+                _ => false,
+            },
+            Self::Poisson(poisson) => match other {
+                Self::Poisson(o_poisson) => poisson == o_poisson,
+                _ => false,
+            },
+            Self::Zipf(zipf) => match other {
+                Self::Zipf(o_zipf) => zipf == o_zipf,
+                _ => false,
+            },
         }
     }
 }
@@ -803,4 +1211,53 @@ pub mod tests {
         test_i32 -> "i32" I32: i32,
         test_i64 -> "i64" I64: i64,
     );
+
+    #[test]
+    fn test_distributions() {
+        let number_content: NumberContent = serde_json::from_value(json!({
+            "subtype": "f64",
+            "normal": {
+                "mean": 50,
+                "std_dev": 10
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            number_content,
+            NumberContent::F64(number_content::F64::Normal(Normal {
+                mean: 50.,
+                std_dev: 10.,
+                low: None,
+                high: None
+            }))
+        );
+
+        let number_content: NumberContent = serde_json::from_value(json!({
+            "subtype": "u64",
+            "zipf": {
+                "n": 100,
+                "exponent": 1.1,
+                "high": 10
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            number_content,
+            NumberContent::U64(number_content::U64::Zipf(Zipf {
+                n: 100,
+                exponent: 1.1,
+                low: None,
+                high: Some(10)
+            }))
+        );
+
+        assert!(serde_json::from_value::<NumberContent>(json!({
+            "subtype": "i32",
+            "exponential": {
+                "rate": 0.5,
+                "mean": 2
+            }
+        }))
+        .is_err());
+    }
 }
@@ -1,15 +1,24 @@
 use super::prelude::*;
+use crate::graph::string::Locale;
 use serde::ser::Serializer;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 
-const RESERVED_FIELDS: [&str; 2] = ["type", "skip_when_null"];
+const RESERVED_FIELDS: [&str; 4] = ["type", "skip_when_null", "unique_together", "locales"];
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Hash)]
 pub struct ObjectContent {
     #[serde(default)]
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub skip_when_null: bool,
+    /// Sets of fields whose values must be unique together across all the generated objects.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unique_together: Vec<Vec<String>>,
+    /// Locales, one of which is pinned for each generated object and used by all of its fakers.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<Locale>,
     #[serde(flatten)]
     #[serde(serialize_with = "normalize_keys")]
     #[serde(deserialize_with = "denormalize_keys")]
@@ -152,7 +161,22 @@ impl Find<Content> for ObjectContent {
 
 impl Compile for ObjectContent {
     fn compile<'a, C: Compiler<'a>>(&'a self, mut compiler: C) -> Result<Graph> {
-        let object_node = self
+        for fields in &self.unique_together {
+            if fields.is_empty() {
+                return Err(failed!(target: Release, "`unique_together` sets cannot be empty"));
+            }
+            for field in fields {
+                if matches!(self.get(field)?, Content::Hidden(_)) {
+                    return Err(failed!(
+                        target: Release,
+                        "hidden field '{}' cannot be part of `unique_together`",
+                        field
+                    ));
+                }
+            }
+        }
+
+        let mut object_node = self
             .iter()
             .map(|(name, field)| {
                 if self.skip_when_null && field.is_nullable() {
@@ -167,6 +191,11 @@ impl Compile for ObjectContent {
                 }
             })
             .collect::<Result<ObjectNode>>()?;
+        // Only the object which pins the locale releases it, once all its fields are generated
+        if !self.locales.is_empty() {
+            object_node = object_node.with_pinned_locale(compiler.locale());
+        }
+        // `unique_together` is applied by the compiler, around the `Ordered` node of the fields
         Ok(Graph::Object(object_node))
     }
 }
@@ -1,9 +1,11 @@
+use std::ffi::OsStr;
 use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 
 use super::prelude::*;
 use super::Categorical;
-use crate::graph::string::FakerArgs;
 use crate::graph::string::{Constant, Serialized, Sliced};
+use crate::graph::string::{FakerArgs, LoremArgs};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -18,6 +20,7 @@ pub enum StringContent {
     Sliced(SlicedContent),
     Format(FormatContent),
     Constant(ConstantContent),
+    Dictionary(DictionaryContent),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
@@ -35,6 +38,7 @@ impl StringContent {
             Self::Sliced(_) => "sliced".to_string(),
             Self::Constant(_) => "constant".to_string(),
             Self::Format(_) => "format".to_string(),
+            Self::Dictionary(_) => "dictionary".to_string(),
         }
     }
 }
@@ -192,6 +196,9 @@ impl<'de> Deserialize<'de> for FakerContent {
         enum Field {
             Generator,
             Locales,
+            Words,
+            Sentences,
+            Paragraphs,
             #[serde(other)]
             Unknown,
         }
@@ -210,6 +217,7 @@ impl<'de> Deserialize<'de> for FakerContent {
             {
                 let mut generator = None;
                 let mut locales = None;
+                let mut lorem = LoremArgs::default();
                 while let Some(key) = map.next_key()? {
                     match key {
                         Field::Generator => {
@@ -224,6 +232,9 @@ impl<'de> Deserialize<'de> for FakerContent {
                             }
                             locales = Some(map.next_value()?);
                         }
+                        Field::Words => lorem.words = Some(map.next_value()?),
+                        Field::Sentences => lorem.sentences = Some(map.next_value()?),
+                        Field::Paragraphs => lorem.paragraphs = Some(map.next_value()?),
                         Field::Unknown => {}
                     }
                 }
@@ -231,7 +242,7 @@ impl<'de> Deserialize<'de> for FakerContent {
                 let locales = locales.unwrap_or_default();
                 Ok(FakerContent {
                     generator,
-                    args: FakerArgs { locales },
+                    args: FakerArgs { locales, lorem },
                     locales: Vec::new(),
                 })
             }
@@ -307,6 +318,171 @@ pub struct JsonContent {
     content: Box<Content>,
 }
 
+/// Values read from a local `.txt`, `.csv` or `.json` file when the schema is compiled, and
+/// sampled uniformly or by weight.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct DictionaryContent {
+    /// The path of the file, relative to the namespace directory
+    pub path: String,
+    /// The column (CSV) or key (JSON objects) of the values
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// The column (CSV) or key (JSON objects) of the weights of the values
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<String>,
+}
+
+impl DictionaryContent {
+    fn load(&self, directory: Option<&Path>) -> Result<Categorical<String>> {
+        let path = match directory {
+            Some(directory) => directory.join(&self.path),
+            None => PathBuf::from(&self.path),
+        };
+        let text = std::fs::read_to_string(&path).map_err(|e| {
+            failed_crate!(
+                target: Release,
+                "failed to open dictionary: {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+
+        let entries = match path.extension().and_then(OsStr::to_str) {
+            Some("txt") => self.read_txt(&text),
+            Some("csv") => self.read_csv(&text),
+            Some("json") => self.read_json(&text),
+            _ => {
+                return Err(failed_crate!(
+                    target: Release,
+                    "unsupported dictionary: {}: expected a .txt, .csv or .json file",
+                    path.display()
+                )
+                .into())
+            }
+        }
+        .with_context(|| anyhow!("failed to read dictionary: {}", path.display()))?;
+
+        let mut categorical = Categorical::default();
+        for (value, weight) in entries {
+            *categorical.seen.entry(value).or_default() += weight;
+            categorical.total += weight;
+        }
+        if categorical.total == 0 {
+            return Err(failed_crate!(
+                target: Release,
+                "dictionary {} has no values with a positive weight",
+                path.display()
+            )
+            .into());
+        }
+        Ok(categorical)
+    }
+
+    /// One value per line.
+    fn read_txt(&self, text: &str) -> Result<Vec<(String, u64)>> {
+        if self.column.is_some() || self.weight.is_some() {
+            return Err(anyhow!(
+                "`column` and `weight` are only supported by CSV and JSON dictionaries"
+            ));
+        }
+        Ok(text
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| (line.to_string(), 1))
+            .collect())
+    }
+
+    /// A header row, then one value per row in `column` (or the first column).
+    fn read_csv(&self, text: &str) -> Result<Vec<(String, u64)>> {
+        let mut reader = csv::Reader::from_reader(text.as_bytes());
+        let headers = reader.headers()?.clone();
+        let position = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "no column named '{}'{}",
+                        name,
+                        suggest_closest(headers.iter(), name).unwrap_or_default()
+                    )
+                })
+        };
+        let column = self.column.as_deref().map(position).transpose()?;
+        let weight = self.weight.as_deref().map(position).transpose()?;
+
+        let mut entries = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            let value = record.get(column.unwrap_or(0)).unwrap_or_default();
+            let weight = match weight {
+                Some(weight) => parse_weight(record.get(weight).unwrap_or_default())?,
+                None => 1,
+            };
+            entries.push((value.to_string(), weight));
+        }
+        Ok(entries)
+    }
+
+    /// An array of values, an array of objects with the values in `column`, or an object
+    /// mapping values to their weights (like `categorical`).
+    fn read_json(&self, text: &str) -> Result<Vec<(String, u64)>> {
+        let as_value = |value: &Value| match value {
+            Value::String(value) => Ok(value.clone()),
+            Value::Number(value) => Ok(value.to_string()),
+            _ => Err(anyhow!("expected a string, found: {}", value)),
+        };
+        let as_weight = |weight: &Value| match weight {
+            Value::Number(weight) => parse_weight(&weight.to_string()),
+            _ => Err(anyhow!("invalid weight '{}': expected a number", weight)),
+        };
+
+        match serde_json::from_str(text)? {
+            Value::Array(values) => values
+                .iter()
+                .map(|value| match (value, &self.column) {
+                    (Value::Object(object), Some(column)) => {
+                        let get = |key: &str| {
+                            object.get(key).ok_or_else(|| {
+                                anyhow!("missing key '{}' in object: {}", key, value)
+                            })
+                        };
+                        let weight = match &self.weight {
+                            Some(weight) => as_weight(get(weight)?)?,
+                            None => 1,
+                        };
+                        Ok((as_value(get(column)?)?, weight))
+                    }
+                    (Value::Object(_), None) => Err(anyhow!(
+                        "`column` is required to read the values of an array of objects"
+                    )),
+                    (value, _) if self.weight.is_none() => Ok((as_value(value)?, 1)),
+                    (value, _) => Err(anyhow!("expected an object, found: {}", value)),
+                })
+                .collect(),
+            Value::Object(weights) if self.column.is_none() && self.weight.is_none() => weights
+                .iter()
+                .map(|(value, weight)| Ok((value.clone(), as_weight(weight)?)))
+                .collect(),
+            _ => Err(anyhow!(
+                "expected an array of values or objects, or an object of weights"
+            )),
+        }
+    }
+}
+
+fn parse_weight(weight: &str) -> Result<u64> {
+    weight.trim().parse().map_err(|_| {
+        anyhow!(
+            "invalid weight '{}': weights must be non-negative integers",
+            weight
+        )
+    })
+}
+
 impl Compile for StringContent {
     fn compile<'a, C: Compiler<'a>>(&'a self, mut compiler: C) -> Result<Graph> {
         let string_node = match self {
@@ -326,8 +502,15 @@ impl Compile for StringContent {
                 args,
                 locales: _, // to combine locales from the 'locales' field and the args::locales,
                             // we should impl Hash on locale and then put them in a Set
-            }) => RandomString::from(RandFaker::new(generator.clone(), args.clone())?).into(),
+            }) => RandomString::from(
+                RandFaker::new(generator.clone(), args.clone())?
+                    .with_pinned_locale(compiler.locale()),
+            )
+            .into(),
             StringContent::Categorical(cat) => RandomString::from(cat.clone()).into(),
+            StringContent::Dictionary(dictionary) => {
+                RandomString::from(dictionary.load(compiler.directory())?).into()
+            }
             StringContent::Serialized(sc) => match sc {
                 SerializedContent::Json(serialized_json_content) => {
                     let inner = serialized_json_content.content.compile(compiler)?;
@@ -361,7 +544,15 @@ impl Compile for StringContent {
 
 #[cfg(test)]
 mod tests {
+    use super::DictionaryContent;
+    use crate::graph::string::FakerCount;
+    use crate::graph::Graph;
     use crate::schema::content::Content;
+    use crate::schema::{Namespace, StringContent};
+    use crate::tests::complete;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use tempfile::tempdir;
 
     #[test]
     #[should_panic(
@@ -373,4 +564,171 @@ mod tests {
             "faker": "lastname"
         });
     }
+
+    #[test]
+    fn faker_lorem_args() {
+        let schema: Content = schema!({
+            "type": "string",
+            "faker": {
+                "generator": "paragraphs",
+                "words": {"low": 3, "high": 8},
+                "sentences": 2
+            }
+        });
+        let faker = match &schema {
+            Content::String(StringContent::Faker(faker)) => faker,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            faker.args.lorem.words,
+            Some(FakerCount::Between { low: 3, high: 8 })
+        );
+        assert_eq!(faker.args.lorem.sentences, Some(FakerCount::Exactly(2)));
+        assert_eq!(faker.args.lorem.paragraphs, None);
+
+        assert_eq!(
+            serde_json::to_value(&schema).unwrap()["faker"],
+            serde_json::json!({
+                "generator": "paragraphs",
+                "locales": [],
+                "words": {"low": 3, "high": 8},
+                "sentences": 2
+            })
+        );
+    }
+
+    fn dictionary(
+        directory: &Path,
+        file: &str,
+        text: &str,
+        column: Option<&str>,
+        weight: Option<&str>,
+    ) -> anyhow::Result<BTreeMap<String, u64>> {
+        std::fs::write(directory.join(file), text).unwrap();
+        let dictionary = DictionaryContent {
+            path: file.to_string(),
+            column: column.map(str::to_string),
+            weight: weight.map(str::to_string),
+        };
+        Ok(dictionary.load(Some(directory))?.seen)
+    }
+
+    #[test]
+    fn dictionary_load() {
+        let dir = tempdir().unwrap();
+        let expected = |entries: &[(&str, u64)]| {
+            entries
+                .iter()
+                .map(|(value, weight)| (value.to_string(), *weight))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let txt = dictionary(
+            dir.path(),
+            "cities.txt",
+            "Paris\nLyon\n\nParis\n",
+            None,
+            None,
+        );
+        assert_eq!(txt.unwrap(), expected(&[("Lyon", 1), ("Paris", 2)]));
+
+        let csv = "country,city,population\nFR,Paris,2100000\nFR,Lyon,500000\n";
+        let first = dictionary(dir.path(), "cities.csv", csv, None, None);
+        assert_eq!(first.unwrap(), expected(&[("FR", 2)]));
+        let weighted = dictionary(
+            dir.path(),
+            "cities.csv",
+            csv,
+            Some("city"),
+            Some("population"),
+        );
+        assert_eq!(
+            weighted.unwrap(),
+            expected(&[("Lyon", 500000), ("Paris", 2100000)])
+        );
+
+        let array = dictionary(
+            dir.path(),
+            "cities.json",
+            r#"["Paris", "Lyon"]"#,
+            None,
+            None,
+        );
+        assert_eq!(array.unwrap(), expected(&[("Lyon", 1), ("Paris", 1)]));
+        let objects = r#"[{"city": "Paris", "weight": 3}, {"city": "Lyon", "weight": 1}]"#;
+        let objects = dictionary(
+            dir.path(),
+            "cities.json",
+            objects,
+            Some("city"),
+            Some("weight"),
+        );
+        assert_eq!(objects.unwrap(), expected(&[("Lyon", 1), ("Paris", 3)]));
+        let weights = dictionary(
+            dir.path(),
+            "cities.json",
+            r#"{"Paris": 3, "Lyon": 1}"#,
+            None,
+            None,
+        );
+        assert_eq!(weights.unwrap(), expected(&[("Lyon", 1), ("Paris", 3)]));
+    }
+
+    #[test]
+    fn dictionary_invalid() {
+        let dir = tempdir().unwrap();
+        let error = |file, text, column, weight| {
+            format!(
+                "{:?}",
+                dictionary(dir.path(), file, text, column, weight).unwrap_err()
+            )
+        };
+
+        assert!(error("cities.txt", "Paris", None, Some("weight")).contains("only supported"));
+        assert!(error("cities.csv", "city\nParis\n", Some("cty"), None)
+            .contains("no column named 'cty'"));
+        assert!(error("cities.csv", "city,n\nParis,-1\n", None, Some("n"))
+            .contains("invalid weight '-1'"));
+        assert!(
+            error("cities.csv", "city,n\nParis,0\n", None, Some("n")).contains("positive weight")
+        );
+        assert!(error("cities.json", r#"[{"city": "Paris"}]"#, None, None)
+            .contains("`column` is required"));
+        assert!(
+            error("cities.json", r#"{"Paris": "a lot"}"#, None, None).contains("invalid weight")
+        );
+        assert!(error("cities.txt", "", None, None).contains("positive weight"));
+        assert!(error("cities.xml", "<city>Paris</city>", None, None)
+            .contains("unsupported dictionary"));
+    }
+
+    #[test]
+    fn dictionary_relative_to_namespace() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("data")).unwrap();
+        std::fs::write(dir.path().join("data/cities.txt"), "Paris\nLyon\n").unwrap();
+
+        let content = schema!({
+            "type": "array",
+            "length": 16,
+            "content": {
+                "type": "string",
+                "dictionary": {"path": "data/cities.txt"}
+            }
+        });
+        let mut namespace = Namespace::new();
+        namespace
+            .put_collection("cities".to_string(), content)
+            .unwrap();
+
+        assert!(Graph::from_namespace(&namespace).is_err());
+
+        let namespace = namespace.with_directory(dir.path());
+        let value = complete(Graph::from_namespace(&namespace).unwrap()).unwrap();
+        let cities = value.as_object().unwrap().get("cities").unwrap();
+        for city in cities.as_array().unwrap() {
+            let city = city.as_string().unwrap();
+            assert!(city == "Paris" || city == "Lyon");
+        }
+    }
 }
@@ -0,0 +1,175 @@
+use super::prelude::*;
+
+use std::collections::BTreeMap;
+
+use super::FieldRef;
+
+/// Picks the content of a field according to the value of another field.
+///
+/// The value of the field at `on` is converted to a string (so that numbers and booleans can
+/// be used as keys of `cases`) and looked up in `cases`. When no case matches, the `default`
+/// branch is used if there is one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct SwitchContent {
+    pub on: FieldRef,
+    pub cases: BTreeMap<String, Content>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Box<Content>>,
+}
+
+impl SwitchContent {
+    /// Iterate over all the branches, including the `default` one.
+    pub fn iter(&self) -> impl Iterator<Item = &Content> {
+        self.cases.values().chain(self.default.as_deref())
+    }
+
+    pub fn accepts(&self, value: &Value) -> Result<()> {
+        if self.iter().any(|content| content.accepts(value).is_ok()) {
+            Ok(())
+        } else {
+            Err(failed!(
+                target: Release,
+                "no case of this will accept: {}",
+                value
+            ))
+        }
+    }
+}
+
+impl Compile for SwitchContent {
+    fn compile<'a, C: Compiler<'a>>(&'a self, mut compiler: C) -> Result<Graph> {
+        if self.default.is_some() && self.cases.contains_key("default") {
+            return Err(failed!(
+                target: Release,
+                "a `switch` cannot have both a `default` branch and a case called 'default'"
+            ));
+        }
+        let discriminant = compiler.get(self.on.clone())?;
+        let cases = self
+            .cases
+            .iter()
+            .map(|(key, content)| Ok((key.clone(), compiler.build(key, content)?)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        let default = self
+            .default
+            .as_ref()
+            .map(|content| compiler.build("default", content))
+            .transpose()?;
+        Ok(Graph::Switch(SwitchNode::new(
+            self.on.clone(),
+            discriminant,
+            cases,
+            default,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::Value;
+    use crate::tests::complete;
+
+    #[test]
+    fn switch() {
+        let generator = generator!({
+            "type": "array",
+            "length": 50,
+            "content": {
+                "type": "object",
+                "status": {
+                    "type": "string",
+                    "categorical": {
+                        "pending": 1,
+                        "shipped": 1,
+                        "cancelled": 1
+                    }
+                },
+                "shipped_at": {
+                    "type": "switch",
+                    "on": "content.status",
+                    "cases": {
+                        "shipped": {
+                            "type": "date_time",
+                            "format": "%Y-%m-%d",
+                            "begin": "2021-01-01",
+                            "end": "2021-12-31"
+                        },
+                        "pending": {
+                            "type": "string",
+                            "pattern": "pending"
+                        }
+                    },
+                    "default": {
+                        "type": "null"
+                    }
+                }
+            }
+        });
+
+        let value = complete(generator).unwrap();
+        for item in value.as_array().unwrap() {
+            let item = item.as_object().unwrap();
+            let shipped_at = item.get("shipped_at").unwrap();
+            match item.get("status").unwrap().as_string().unwrap().as_str() {
+                "shipped" => assert!(matches!(shipped_at, Value::DateTime(_))),
+                "pending" => assert_eq!(shipped_at.as_string().unwrap(), "pending"),
+                _ => assert!(shipped_at.is_null()),
+            }
+        }
+    }
+
+    #[test]
+    fn switch_on_number() {
+        let generator = generator!({
+            "type": "object",
+            "kind": {
+                "type": "number",
+                "constant": 2
+            },
+            "value": {
+                "type": "switch",
+                "on": "kind",
+                "cases": {
+                    "1": {
+                        "type": "bool",
+                        "constant": false
+                    },
+                    "2": {
+                        "type": "bool",
+                        "constant": true
+                    }
+                }
+            }
+        });
+
+        let value = complete(generator).unwrap();
+        let value = value.as_object().unwrap().get("value").unwrap();
+        assert!(value.as_bool().unwrap());
+    }
+
+    #[test]
+    fn switch_without_match() {
+        let generator = generator!({
+            "type": "object",
+            "kind": {
+                "type": "string",
+                "pattern": "other"
+            },
+            "value": {
+                "type": "switch",
+                "on": "kind",
+                "cases": {
+                    "some": {
+                        "type": "null"
+                    }
+                }
+            }
+        });
+
+        let error = complete(generator).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("no case of the `switch` on `kind` matches 'other'"));
+    }
+}
@@ -1,13 +1,19 @@
 #![allow(clippy::derivable_impls)]
 use crate::compile::Compile;
-use crate::graph::UniqueNode;
-use crate::{Compiler, Content, Graph};
+use crate::graph::unique::{Enumeration, PermutationNode};
+use crate::graph::{StandardFloatRangeStep, StandardIntRangeStep, UniqueNode};
+use crate::schema::{number_content, NumberContent, StringContent};
+use crate::{Compiler, Content, Graph, Value};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use synth_gen::value::Number;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum UniqueAlgorithm {
+    /// Generate values until one has not been seen before.
     Hash { retries: Option<usize> },
+    /// Walk the values of a number range or of a categorical in a pseudo-random order.
+    Permutation,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -28,10 +34,250 @@ pub struct UniqueContent {
 
 impl Compile for UniqueContent {
     fn compile<'a, C: Compiler<'a>>(&'a self, compiler: C) -> Result<Graph> {
-        let graph = self.content.compile(compiler)?;
+        let partition = compiler.partition();
         let node = match self.algorithm {
-            UniqueAlgorithm::Hash { retries } => UniqueNode::hash(graph, retries),
+            UniqueAlgorithm::Hash { retries } => {
+                let graph = self.content.compile(compiler)?;
+                UniqueNode::hash_partition(graph, retries, partition)
+            }
+            UniqueAlgorithm::Permutation => {
+                let (size, values) = enumerate(&self.content)?;
+                if size == 0 {
+                    return Err(anyhow!(
+                        "cannot generate unique values from content without any value"
+                    ));
+                }
+                UniqueNode::Permutation(PermutationNode::new(size, values, partition))
+            }
         };
         Ok(Graph::Unique(node))
     }
 }
+
+/// The number of distinct values `content` generates, and these values by index.
+fn enumerate(content: &Content) -> Result<(u64, Enumeration)> {
+    macro_rules! int_range {
+        ($range:expr, $unsigned:ty, $larger:ty) => {{
+            let range = StandardIntRangeStep::<$unsigned, $larger>::try_from_range(*$range)?;
+            let size = range.num_values().ok_or_else(|| {
+                anyhow!(
+                    "cannot generate unique values from {}: it has more than 2^64 values",
+                    range
+                )
+            })?;
+            let values: Enumeration =
+                Box::new(move |index| Value::Number(Number::from(range.nth(index))));
+            (size, values)
+        }};
+    }
+
+    macro_rules! float_range {
+        ($range:expr, $target:ty) => {{
+            let range = StandardFloatRangeStep::<$target>::try_from_range(*$range)?;
+            let size = range.num_values().ok_or_else(|| {
+                anyhow!(
+                    "cannot generate unique values from a {} range without a 'step', or with a 'step' too small for its bounds",
+                    stringify!($target)
+                )
+            })?;
+            let values: Enumeration =
+                Box::new(move |index| Value::Number(Number::from(range.nth(index))));
+            (size, values)
+        }};
+    }
+
+    fn categorical<T: Clone + 'static>(values: Vec<T>, into: fn(T) -> Value) -> (u64, Enumeration) {
+        let size = values.len() as u64;
+        (
+            size,
+            Box::new(move |index| into(values[index as usize].clone())),
+        )
+    }
+
+    fn number<T: Into<Number>>(value: T) -> Value {
+        Value::Number(value.into())
+    }
+
+    let enumeration = match content {
+        Content::Number(number_content) => match number_content {
+            NumberContent::U32(number_content::U32::Range(range)) => int_range!(range, u32, u32),
+            NumberContent::U64(number_content::U64::Range(range)) => int_range!(range, u64, u64),
+            NumberContent::I8(number_content::I8::Range(range)) => int_range!(range, u8, i16),
+            NumberContent::I16(number_content::I16::Range(range)) => int_range!(range, u16, i32),
+            NumberContent::I32(number_content::I32::Range(range)) => int_range!(range, u32, i64),
+            NumberContent::I64(number_content::I64::Range(range)) => int_range!(range, u64, i128),
+            NumberContent::F32(number_content::F32::Range(range)) => float_range!(range, f32),
+            NumberContent::F64(number_content::F64::Range(range)) => float_range!(range, f64),
+            NumberContent::U32(number_content::U32::Categorical(cat)) => {
+                categorical(cat.seen.keys().cloned().collect(), number)
+            }
+            NumberContent::U64(number_content::U64::Categorical(cat)) => {
+                categorical(cat.seen.keys().cloned().collect(), number)
+            }
+            NumberContent::I8(number_content::I8::Categorical(cat)) => {
+                categorical(cat.seen.keys().cloned().collect(), number)
+            }
+            NumberContent::I16(number_content::I16::Categorical(cat)) => {
+                categorical(cat.seen.keys().cloned().collect(), number)
+            }
+            NumberContent::I32(number_content::I32::Categorical(cat)) => {
+                categorical(cat.seen.keys().cloned().collect(), number)
+            }
+            NumberContent::I64(number_content::I64::Categorical(cat)) => {
+                categorical(cat.seen.keys().cloned().collect(), number)
+            }
+            _ => return Err(unsupported(content)),
+        },
+        Content::String(StringContent::Categorical(cat)) => {
+            categorical(cat.seen.keys().cloned().collect(), Value::String)
+        }
+        _ => return Err(unsupported(content)),
+    };
+    Ok(enumeration)
+}
+
+fn unsupported(content: &Content) -> anyhow::Error {
+    anyhow!(
+        "the Permutation algorithm of `unique` only supports number ranges and categoricals, not {}",
+        content.kind()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::complete;
+    use crate::Value;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn permutation() {
+        let generator = generator!({
+            "type": "array",
+            "length": 6,
+            "content": {
+                "type": "unique",
+                "algorithm": "Permutation",
+                "content": {
+                    "type": "number",
+                    "subtype": "i32",
+                    "range": {
+                        "low": -5,
+                        "high": 13,
+                        "step": 3
+                    }
+                }
+            }
+        });
+        let value = complete(generator).unwrap();
+        let numbers: HashSet<_> = value.as_array().unwrap().iter().cloned().collect();
+        let expected: HashSet<_> = [-5i32, -2, 1, 4, 7, 10]
+            .into_iter()
+            .map(|n| Value::Number(n.into()))
+            .collect();
+        assert_eq!(numbers, expected);
+
+        let generator = generator!({
+            "type": "array",
+            "length": 3,
+            "content": {
+                "type": "unique",
+                "algorithm": "Permutation",
+                "content": {
+                    "type": "string",
+                    "categorical": {
+                        "a": 1,
+                        "b": 10,
+                        "c": 100
+                    }
+                }
+            }
+        });
+        let value = complete(generator).unwrap();
+        let mut strings: Vec<_> = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_string().unwrap().clone())
+            .collect();
+        strings.sort();
+        assert_eq!(strings, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn permutation_float_range() {
+        let generator = generator!({
+            "type": "array",
+            "length": 5,
+            "content": {
+                "type": "unique",
+                "algorithm": "Permutation",
+                "content": {
+                    "type": "number",
+                    "subtype": "f64",
+                    "range": {
+                        "low": 0.0,
+                        "high": 1.0,
+                        "step": 0.25,
+                        "include_high": true
+                    }
+                }
+            }
+        });
+        let value = complete(generator).unwrap();
+        let numbers: HashSet<_> = value.as_array().unwrap().iter().cloned().collect();
+        let expected: HashSet<_> = [0., 0.25, 0.5, 0.75, 1.]
+            .into_iter()
+            .map(|n: f64| Value::Number(n.into()))
+            .collect();
+        assert_eq!(numbers, expected);
+
+        let generator = generator!({
+            "type": "unique",
+            "algorithm": "Permutation",
+            "content": {
+                "type": "number",
+                "subtype": "u64",
+                "range": {
+                    "low": 0,
+                    "high": 2,
+                    "step": 1
+                }
+            }
+        });
+        let mut generator = generator;
+        let output: Vec<_> = (0..3)
+            .map(|_| crate::tests::complete_once(&mut generator))
+            .collect();
+        assert!(output[..2].iter().all(Result::is_ok));
+        assert!(output[2].is_err());
+    }
+
+    #[test]
+    fn permutation_unsupported() {
+        let error = try_generator!({
+            "type": "unique",
+            "algorithm": "Permutation",
+            "content": {
+                "type": "string",
+                "pattern": "[a-z]{4}"
+            }
+        })
+        .err()
+        .unwrap();
+        assert!(format!("{:?}", error).contains("only supports number ranges and categoricals"));
+
+        let error = try_generator!({
+            "type": "unique",
+            "algorithm": "Permutation",
+            "content": {
+                "type": "number",
+                "subtype": "f64",
+                "range": {}
+            }
+        })
+        .err()
+        .unwrap();
+        assert!(format!("{:?}", error).contains("without a 'step'"));
+    }
+}
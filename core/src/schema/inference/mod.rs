@@ -12,8 +12,8 @@ pub use value::ValueMergeStrategy;
 
 use super::{
     number_content, ArrayContent, BoolContent, Categorical, CategoricalType, ChronoValueFormatter,
-    Content, DateTimeContent, Id, NumberContent, NumberKindExt, ObjectContent, OneOfContent,
-    RangeStep, StringContent, ValueKindExt,
+    Content, DateTimeContent, Exponential, Id, LogNormal, Normal, NumberContent, NumberKindExt,
+    ObjectContent, OneOfContent, Poisson, RangeStep, StringContent, ValueKindExt, Zipf,
 };
 use crate::graph::prelude::content::number_content::{I16, I32, I64, I8};
 use crate::schema::UniqueContent;
@@ -36,7 +36,7 @@ impl MergeStrategy<Content, Value> for OptionalMergeStrategy {
     fn try_merge(self, master: &mut Content, candidate: &Value) -> Result<()> {
         match (master, candidate) {
             // Logical nodes go first
-            (Content::SameAs(_), _) => {
+            (Content::SameAs(_) | Content::Expression(_) | Content::Switch(_), _) => {
                 // Nothing can happen here because this is not a visitor pattern
                 Ok(())
             }
@@ -131,6 +131,7 @@ impl MergeStrategy<StringContent, String> for OptionalMergeStrategy {
             StringContent::Sliced(_) => Ok(()),
             StringContent::Constant(_) => Ok(()),
             StringContent::Format(_) => Ok(()),
+            StringContent::Dictionary(_) => Ok(()),
         }
     }
 }
@@ -247,6 +248,31 @@ where
     }
 }
 
+/// Distributions are left untouched, apart from their clamping bounds (if any) which are widened
+/// to accept the candidate.
+macro_rules! distribution_merge_impl {
+    ($($name:ident),*) => {
+        $(
+            impl<N> MergeStrategy<$name<N>, N> for OptionalMergeStrategy
+            where
+                N: PartialOrd + Copy,
+            {
+                fn try_merge(self, master: &mut $name<N>, value: &N) -> Result<()> {
+                    if let Some(low) = master.low.as_mut() {
+                        *low = if *value < *low { *value } else { *low };
+                    }
+                    if let Some(high) = master.high.as_mut() {
+                        *high = if *value > *high { *value } else { *high };
+                    }
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+distribution_merge_impl!(Normal, LogNormal, Exponential, Poisson, Zipf);
+
 impl<N: PartialEq + Display> MergeStrategy<N, N> for OptionalMergeStrategy {
     fn try_merge(self, master: &mut N, candidate: &N) -> Result<()> {
         if *master == *candidate {
@@ -269,6 +295,11 @@ impl MergeStrategy<number_content::U64, u64> for OptionalMergeStrategy {
             number_content::U64::Categorical(cat) => self.try_merge(cat, candidate),
             number_content::U64::Constant(cst) => self.try_merge(cst, candidate),
             number_content::U64::Id(id) => self.try_merge(id, candidate),
+            number_content::U64::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::U64::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::U64::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::U64::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::U64::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -280,6 +311,11 @@ impl MergeStrategy<number_content::I64, i64> for OptionalMergeStrategy {
             number_content::I64::Categorical(cat) => self.try_merge(cat, candidate),
             number_content::I64::Constant(cst) => self.try_merge(cst, candidate),
             I64::Id(id) => self.try_merge(id, candidate),
+            number_content::I64::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::I64::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::I64::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::I64::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::I64::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -289,6 +325,11 @@ impl MergeStrategy<number_content::F64, f64> for OptionalMergeStrategy {
         match master {
             number_content::F64::Range(range) => self.try_merge(range, candidate),
             number_content::F64::Constant(cst) => self.try_merge(cst, candidate),
+            number_content::F64::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::F64::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::F64::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::F64::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::F64::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -300,6 +341,11 @@ impl MergeStrategy<number_content::U32, u32> for OptionalMergeStrategy {
             number_content::U32::Categorical(cat) => self.try_merge(cat, candidate),
             number_content::U32::Constant(cst) => self.try_merge(cst, candidate),
             number_content::U32::Id(id) => self.try_merge(id, candidate),
+            number_content::U32::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::U32::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::U32::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::U32::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::U32::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -311,6 +357,11 @@ impl MergeStrategy<number_content::I32, i32> for OptionalMergeStrategy {
             number_content::I32::Categorical(cat) => self.try_merge(cat, candidate),
             number_content::I32::Constant(cst) => self.try_merge(cst, candidate),
             I32::Id(id) => self.try_merge(id, candidate),
+            number_content::I32::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::I32::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::I32::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::I32::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::I32::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -320,6 +371,11 @@ impl MergeStrategy<number_content::F32, f32> for OptionalMergeStrategy {
         match master {
             number_content::F32::Range(range) => self.try_merge(range, candidate),
             number_content::F32::Constant(cst) => self.try_merge(cst, candidate),
+            number_content::F32::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::F32::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::F32::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::F32::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::F32::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -331,6 +387,11 @@ impl MergeStrategy<number_content::I16, i16> for OptionalMergeStrategy {
             number_content::I16::Categorical(cat) => self.try_merge(cat, candidate),
             number_content::I16::Constant(cst) => self.try_merge(cst, candidate),
             I16::Id(id) => self.try_merge(id, candidate),
+            number_content::I16::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::I16::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::I16::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::I16::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::I16::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
@@ -342,6 +403,11 @@ impl MergeStrategy<number_content::I8, i8> for OptionalMergeStrategy {
             number_content::I8::Categorical(cat) => self.try_merge(cat, candidate),
             number_content::I8::Constant(cst) => self.try_merge(cst, candidate),
             I8::Id(id) => self.try_merge(id, candidate),
+            number_content::I8::Normal(normal) => self.try_merge(normal, candidate),
+            number_content::I8::LogNormal(log_normal) => self.try_merge(log_normal, candidate),
+            number_content::I8::Exponential(exponential) => self.try_merge(exponential, candidate),
+            number_content::I8::Poisson(poisson) => self.try_merge(poisson, candidate),
+            number_content::I8::Zipf(zipf) => self.try_merge(zipf, candidate),
         }
     }
 }
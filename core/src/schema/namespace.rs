@@ -7,6 +7,8 @@ use crate::graph::{Graph, KeyValueOrNothing};
 
 use std::collections::BTreeMap;
 use std::default::Default;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -15,10 +17,26 @@ use serde_json::{value::Value, Map};
 #[allow(dead_code)]
 type JsonObject = Map<String, Value>;
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Hash)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Namespace {
     #[serde(flatten)]
     collections: BTreeMap<String, Content>,
+    /// The directory the namespace was read from, against which the relative paths of its
+    /// files (such as dictionaries) are resolved.
+    #[serde(skip)]
+    directory: Option<PathBuf>,
+}
+
+impl PartialEq for Namespace {
+    fn eq(&self, other: &Self) -> bool {
+        self.collections == other.collections
+    }
+}
+
+impl Hash for Namespace {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.collections.hash(state);
+    }
 }
 
 impl IntoIterator for Namespace {
@@ -36,6 +54,7 @@ impl FromIterator<(String, Content)> for Namespace {
     fn from_iter<T: IntoIterator<Item = (String, Content)>>(iter: T) -> Self {
         Self {
             collections: iter.into_iter().collect(),
+            directory: None,
         }
     }
 }
@@ -45,6 +64,15 @@ impl Namespace {
         Self::default()
     }
 
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub fn directory(&self) -> Option<&Path> {
+        self.directory.as_deref()
+    }
+
     #[cfg(test)]
     pub fn accepts(&self, name: &str, value: &Value) -> Result<()> {
         self.get_collection(name)?.accepts(value)
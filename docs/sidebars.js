@@ -3,8 +3,8 @@ module.exports = {
         "Getting Started": ['getting_started/synth', 'getting_started/installation', 'getting_started/hello-world', 'getting_started/core-concepts', 'getting_started/schema', 'getting_started/command-line', 'getting_started/how-it-works'],
         "Examples": ['examples/bank'],
         "Tutorials": ['tutorials/creating-logs-with-synth'],
-        "Integrations": ['integrations/index', 'integrations/postgres', 'integrations/mysql'],
-        "Generators": ['content/index', 'content/modifiers', 'content/null', 'content/bool', 'content/number', 'content/string', 'content/date-time', 'content/object', 'content/array', 'content/one-of', 'content/same-as', 'content/unique', 'content/series', 'content/datasource'],
+        "Integrations": ['integrations/index', 'integrations/postgres', 'integrations/mysql', 'integrations/sqlite'],
+        "Generators": ['content/index', 'content/modifiers', 'content/null', 'content/bool', 'content/number', 'content/string', 'content/date-time', 'content/object', 'content/array', 'content/one-of', 'content/same-as', 'content/expression', 'content/switch', 'content/unique', 'content/series', 'content/datasource'],
         "Other": ['other/telemetry']
     },
 };
@@ -24,10 +24,12 @@ fn bench_generate_n_to_stdout(size: usize) {
             namespace,
             collection: None,
             scenario: None,
-            size,
+            size: vec![size.into()],
             to: "json:".to_string(),
             seed: Some(0),
             random: false,
+            threads: 1,
+            create_tables: false,
             schema: None,
         });
         let output = io::stdout();
@@ -376,6 +376,8 @@ mod tests {
                             VariantContent::new(Content::Null(NullContent)),
                             VariantContent::new(Content::SameAs(SameAsContent {
                                 ref_: FieldRef::new("my_collection.z").unwrap(),
+                                cardinality: None,
+                                distinct: false,
                             })),
                         ],
                     }),
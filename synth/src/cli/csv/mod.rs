@@ -3,7 +3,7 @@
 mod headers;
 
 use crate::cli::export::ExportStrategy;
-use crate::sampler::SamplerOutput;
+use crate::sampler::{Sample, SamplerOutput};
 
 use synth_core::schema::content::{number_content, ArrayContent, NumberContent};
 use synth_core::schema::{MergeStrategy, OptionalMergeStrategy};
@@ -12,6 +12,7 @@ use synth_gen::value::Number;
 
 use anyhow::Result;
 
+use std::io::Write;
 use std::path::PathBuf;
 
 use super::import::ImportStrategy;
@@ -22,23 +23,21 @@ pub struct CsvFileExportStrategy {
 }
 
 impl ExportStrategy for CsvFileExportStrategy {
-    fn export(&self, namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
         if self.to_dir.exists() {
             return Err(anyhow::anyhow!("Output directory already exists"));
         } else {
             std::fs::create_dir_all(&self.to_dir)?;
         }
 
-        match csv_output_from_sampler_ouput(sample, &namespace)? {
-            CsvOutput::Namespace(ns) => {
-                for (name, csv) in ns {
-                    std::fs::write(self.to_dir.join(name + ".csv"), csv)?;
-                }
-            }
-            CsvOutput::Collection(csv) => {
-                std::fs::write(self.to_dir.join("collection.csv"), csv)?;
-            }
+        let mut writers = CsvWriters::new(&namespace);
+        for chunk in sample {
+            writers.write_chunk(chunk?, |name| {
+                let file_name = name.unwrap_or("collection").to_string() + ".csv";
+                Ok(std::fs::File::create(self.to_dir.join(file_name))?)
+            })?;
         }
+        writers.flush()?;
 
         Ok(())
     }
@@ -48,14 +47,27 @@ impl ExportStrategy for CsvFileExportStrategy {
 pub struct CsvStdoutExportStrategy;
 
 impl ExportStrategy for CsvStdoutExportStrategy {
-    fn export(&self, namespace: Namespace, sample: SamplerOutput) -> Result<()> {
-        match csv_output_from_sampler_ouput(sample, &namespace)? {
-            CsvOutput::Namespace(ns) => {
+    fn export(&self, namespace: Namespace, mut sample: Sample) -> Result<()> {
+        let first = sample
+            .next()
+            .expect("a sample always has at least one chunk")?;
+
+        if let SamplerOutput::Collection(..) = first {
+            // A single collection can be printed as it is generated.
+            let mut writers = CsvWriters::new(&namespace);
+            writers.write_chunk(first, |_| Ok(std::io::stdout()))?;
+            for chunk in sample {
+                writers.write_chunk(chunk?, |_| Ok(std::io::stdout()))?;
+            }
+            writers.flush()?;
+            println!();
+        } else {
+            let sample = Sample::new(std::iter::once(Ok(first)).chain(sample));
+            if let CsvOutput::Namespace(ns) = csv_output_from_sample(sample, &namespace)? {
                 for (name, csv) in ns {
                     println!("\n{}\n{}\n\n{}\n", name, "-".repeat(name.len()), csv)
                 }
             }
-            CsvOutput::Collection(csv) => println!("{csv}"),
         }
 
         Ok(())
@@ -271,55 +283,112 @@ pub enum CsvOutput {
     Collection(String),
 }
 
-fn csv_output_from_sampler_ouput(
-    output: SamplerOutput,
-    namespace: &Namespace,
-) -> Result<CsvOutput> {
-    Ok(match output {
-        SamplerOutput::Namespace(key_values) => CsvOutput::Namespace(
-            key_values
-                .into_iter()
-                .map(|(collection_name, value)| {
-                    Ok((
-                        collection_name.clone(),
-                        to_csv_string(collection_name, value, namespace)?,
-                    ))
-                })
-                .collect::<Result<Vec<(String, String)>>>()?,
-        ),
-        SamplerOutput::Collection(collection_name, value) => {
-            CsvOutput::Collection(to_csv_string(collection_name, value, namespace)?)
-        }
-    })
+/// CSV writers for the collections of a sample, created when the first chunk of each collection
+/// is written.
+struct CsvWriters<'a, W: Write> {
+    namespace: &'a Namespace,
+    writers: Vec<(String, csv::Writer<W>)>,
 }
 
-fn to_csv_string(collection_name: String, value: Value, namespace: &Namespace) -> Result<String> {
-    let mut writer = csv::Writer::from_writer(vec![]);
-
-    let collection = namespace.get_collection(&collection_name)?;
-
-    match (collection, value) {
-        (Content::Array(array_content), Value::Array(elements)) => {
-            let inner_content: &Content = &array_content.content;
-
-            writer.write_record(
-                &headers::CsvHeaders::from_content(inner_content, namespace)?.to_csv_record(),
-            )?;
+impl<'a, W: Write> CsvWriters<'a, W> {
+    fn new(namespace: &'a Namespace) -> Self {
+        Self {
+            namespace,
+            writers: Vec::new(),
+        }
+    }
 
-            for val in elements {
-                let record = synth_val_to_csv_record(val, inner_content, namespace);
-                writer.write_record(record)?;
+    /// Write a chunk of values. New writers are created with `create`, which is given the name of
+    /// the collection or `None` if the sample is of a single collection.
+    fn write_chunk<F>(&mut self, chunk: SamplerOutput, mut create: F) -> Result<()>
+    where
+        F: FnMut(Option<&str>) -> Result<W>,
+    {
+        let is_collection = matches!(chunk, SamplerOutput::Collection(..));
+        for (collection_name, value) in chunk.into_collections() {
+            let collection = self.namespace.get_collection(&collection_name)?;
+
+            let idx = match self
+                .writers
+                .iter()
+                .position(|(name, _)| *name == collection_name)
+            {
+                Some(idx) => idx,
+                None => {
+                    let mut writer = csv::Writer::from_writer(create(
+                        Some(collection_name.as_str()).filter(|_| !is_collection),
+                    )?);
+                    let header_content = match collection {
+                        Content::Array(array_content) => array_content.content.as_ref(),
+                        collection => collection,
+                    };
+                    writer.write_record(
+                        &headers::CsvHeaders::from_content(header_content, self.namespace)?
+                            .to_csv_record(),
+                    )?;
+                    self.writers.push((collection_name.clone(), writer));
+                    self.writers.len() - 1
+                }
+            };
+            let writer = &mut self.writers[idx].1;
+
+            match (collection, value) {
+                (Content::Array(array_content), Value::Array(elements)) => {
+                    for val in elements {
+                        let record =
+                            synth_val_to_csv_record(val, &array_content.content, self.namespace);
+                        writer.write_record(record)?;
+                    }
+                }
+                (_, value) => {
+                    writer.write_record(synth_val_to_csv_record(
+                        value,
+                        collection,
+                        self.namespace,
+                    ))?;
+                }
             }
+            writer.flush()?;
         }
-        (_, value) => {
-            writer.write_record(
-                &headers::CsvHeaders::from_content(collection, namespace)?.to_csv_record(),
-            )?;
-            writer.write_record(synth_val_to_csv_record(value, collection, namespace))?;
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        for (_, writer) in self.writers.iter_mut() {
+            writer.flush()?;
         }
+        Ok(())
     }
 
-    Ok(String::from_utf8(writer.into_inner()?)?)
+    fn into_inner(self) -> Result<Vec<(String, W)>> {
+        self.writers
+            .into_iter()
+            .map(|(name, writer)| Ok((name, writer.into_inner().map_err(|err| err.into_error())?)))
+            .collect()
+    }
+}
+
+fn csv_output_from_sample(sample: Sample, namespace: &Namespace) -> Result<CsvOutput> {
+    let mut writers = CsvWriters::new(namespace);
+    let mut is_collection = false;
+    for chunk in sample {
+        let chunk = chunk?;
+        is_collection = matches!(chunk, SamplerOutput::Collection(..));
+        writers.write_chunk(chunk, |_| Ok(Vec::new()))?;
+    }
+
+    let mut outputs = writers
+        .into_inner()?
+        .into_iter()
+        .map(|(name, csv)| Ok((name, String::from_utf8(csv)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(if is_collection {
+        CsvOutput::Collection(outputs.pop().map(|(_, csv)| csv).unwrap_or_default())
+    } else {
+        CsvOutput::Namespace(outputs)
+    })
 }
 
 fn synth_val_to_csv_record(val: Value, content: &Content, namespace: &Namespace) -> Vec<String> {
@@ -538,7 +607,7 @@ mod tests {
             .unwrap();
 
         assert_eq!(
-            csv_output_from_sampler_ouput(output, &ns).unwrap(),
+            csv_output_from_sample(output, &ns).unwrap(),
             CsvOutput::Collection(
                 concat!(
                     "a.b,a.c,a.d[0].e,a.d[0].f,a.d[1].e,a.d[1].f\n",
@@ -0,0 +1,418 @@
+use crate::cli::sql::SqlDialect;
+
+use anyhow::Result;
+
+use synth_core::graph::Graph;
+use synth_core::schema::number_content::{I16, I32, I64, I8, U32, U64};
+use synth_core::schema::{ChronoValueType, FieldRef, NumberContent};
+use synth_core::{Content, Namespace};
+
+/// A column of a table, as derived from the content of a field.
+#[derive(Debug, Default)]
+struct Column {
+    type_: String,
+    nullable: bool,
+    unique: bool,
+    id: bool,
+    /// The table and column referenced by the column.
+    references: Option<(String, String)>,
+}
+
+/// Build the `CREATE TABLE` statements for the collections of `namespace`.
+///
+/// Each collection which is an array of objects (or a single object) becomes a table with a
+/// column per field. Hidden collections and fields are skipped. Tables are created in the order
+/// in which collections are generated, so that the tables referenced by foreign keys are created
+/// first.
+pub(crate) fn create_tables(dialect: SqlDialect, namespace: &Namespace) -> Result<Vec<String>> {
+    let graph = Graph::from_namespace(namespace)?;
+    let mut ordered: Vec<&str> = graph
+        .iter_ordered()
+        .map(|iter| iter.collect())
+        .unwrap_or_default();
+    for name in namespace.keys() {
+        if !ordered.contains(&name) {
+            ordered.push(name);
+        }
+    }
+
+    let mut statements = Vec::new();
+    for collection_name in ordered {
+        let object = match namespace.get_collection(collection_name)? {
+            Content::Hidden(_) => continue,
+            Content::Array(array) => match array.content.as_ref() {
+                Content::Object(object) => object,
+                other => {
+                    return Err(anyhow!(
+                        "Cannot create a table for collection '{}': was expecting its elements to be objects. Instead found {}",
+                        collection_name,
+                        other.kind()
+                    ))
+                }
+            },
+            Content::Object(object) => object,
+            other => {
+                return Err(anyhow!(
+                    "Cannot create a table for collection '{}': was expecting an array of objects or an object. Instead found {}",
+                    collection_name,
+                    other.kind()
+                ))
+            }
+        };
+
+        let mut columns = Vec::new();
+        for (field_name, content) in &object.fields {
+            if content.is_hidden() {
+                continue;
+            }
+            let column = dialect
+                .column(namespace, content)
+                .map_err(|err| err.context(format!("At field {collection_name}.{field_name}")))?;
+            columns.push((field_name, column));
+        }
+
+        // A single id becomes the primary key, several are only unique
+        let primary_key = match columns.iter().filter(|(_, column)| column.id).count() {
+            1 => columns
+                .iter()
+                .find(|(_, column)| column.id)
+                .map(|(name, _)| *name),
+            _ => None,
+        };
+
+        let table_name = dialect.quote_identifier(collection_name);
+        let mut definitions = Vec::new();
+        for (name, column) in &columns {
+            let mut definition = format!("{} {}", dialect.quote_identifier(name), column.type_);
+            if primary_key == Some(*name) {
+                definition.push_str(" PRIMARY KEY");
+            } else {
+                if !column.nullable {
+                    definition.push_str(" NOT NULL");
+                }
+                if column.unique || column.id {
+                    definition.push_str(" UNIQUE");
+                }
+            }
+            definitions.push(definition);
+        }
+        for fields in &object.unique_together {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| dialect.quote_identifier(field))
+                .collect();
+            definitions.push(format!("UNIQUE ({})", fields.join(", ")));
+        }
+        for (name, column) in &columns {
+            if let Some((to_table, to_column)) = &column.references {
+                definitions.push(format!(
+                    "FOREIGN KEY ({}) REFERENCES {} ({})",
+                    dialect.quote_identifier(name),
+                    dialect.quote_identifier(to_table),
+                    dialect.quote_identifier(to_column)
+                ));
+            }
+        }
+
+        statements.push(format!(
+            "CREATE TABLE IF NOT EXISTS {} (\n    {}\n);",
+            table_name,
+            definitions.join(",\n    ")
+        ));
+    }
+
+    Ok(statements)
+}
+
+impl SqlDialect {
+    fn column(self, namespace: &Namespace, content: &Content) -> Result<Column> {
+        let mut column = Column::default();
+        column.type_ = self.describe(namespace, content, &mut column)?;
+        Ok(column)
+    }
+
+    /// The type of the column holding values of `content`, recording the constraints it implies
+    /// in `column`.
+    fn describe(
+        self,
+        namespace: &Namespace,
+        content: &Content,
+        column: &mut Column,
+    ) -> Result<String> {
+        let type_ = match content {
+            Content::Unique(unique) => {
+                column.unique = true;
+                return self.describe(namespace, &unique.content, column);
+            }
+            Content::Null(_) => {
+                column.nullable = true;
+                self.text_type()
+            }
+            Content::OneOf(one_of) => {
+                return self.common_type(
+                    namespace,
+                    one_of
+                        .variants
+                        .iter()
+                        .map(|variant| variant.content.as_ref()),
+                    column,
+                )
+            }
+            Content::Switch(switch) => return self.common_type(namespace, switch.iter(), column),
+            Content::SameAs(same_as) => {
+                let target = namespace.get_s_node(&same_as.ref_)?;
+                let referenced = self.column(namespace, target)?;
+                if referenced.id || referenced.unique {
+                    column.references = referenced_column(namespace, &same_as.ref_);
+                }
+                referenced.type_
+            }
+            Content::Number(number) => {
+                column.id = matches!(
+                    number,
+                    NumberContent::I8(I8::Id(_))
+                        | NumberContent::I16(I16::Id(_))
+                        | NumberContent::I32(I32::Id(_))
+                        | NumberContent::I64(I64::Id(_))
+                        | NumberContent::U32(U32::Id(_))
+                        | NumberContent::U64(U64::Id(_))
+                );
+                self.number_type(number).to_string()
+            }
+            Content::String(_) => self.text_type(),
+            Content::Bool(_) => "BOOLEAN".to_string(),
+            Content::DateTime(date_time) => self.date_time_type(&date_time.type_).to_string(),
+            Content::Series(_) => self
+                .date_time_type(&ChronoValueType::NaiveDateTime)
+                .to_string(),
+            Content::Object(_) => self.json_type().to_string(),
+            Content::Array(array) => match self {
+                Self::Postgres => {
+                    let element = self.column(namespace, &array.content)?;
+                    // Arrays of objects are inserted as a single `jsonb` value
+                    if element.type_ == self.json_type() || element.type_.ends_with("[]") {
+                        element.type_
+                    } else {
+                        format!("{}[]", element.type_)
+                    }
+                }
+                Self::MySql | Self::Sqlite => self.json_type().to_string(),
+            },
+            other => {
+                return Err(anyhow!(
+                    "Cannot derive a column type from a {} generator",
+                    other.kind()
+                ))
+            }
+        };
+        Ok(type_)
+    }
+
+    /// The type of a column holding values of any of `contents`, which must all have the same
+    /// type unless they are null.
+    fn common_type<'a, I: Iterator<Item = &'a Content>>(
+        self,
+        namespace: &Namespace,
+        contents: I,
+        column: &mut Column,
+    ) -> Result<String> {
+        let mut common: Option<String> = None;
+        for content in contents {
+            if content.is_null() {
+                column.nullable = true;
+                continue;
+            }
+            let type_ = self.describe(namespace, content, column)?;
+            match &common {
+                Some(common) if *common != type_ => {
+                    return Err(anyhow!(
+                        "Cannot derive a single column type from both {} and {} values",
+                        common,
+                        type_
+                    ))
+                }
+                Some(_) => {}
+                None => common = Some(type_),
+            }
+        }
+        Ok(common.unwrap_or_else(|| self.text_type()))
+    }
+
+    fn number_type(self, number: &NumberContent) -> &'static str {
+        match (self, number) {
+            (Self::Sqlite, NumberContent::F32(_) | NumberContent::F64(_)) => "REAL",
+            (Self::Sqlite, _) => "INTEGER",
+            (Self::Postgres, NumberContent::I8(_) | NumberContent::I16(_)) => "SMALLINT",
+            (Self::Postgres, NumberContent::I32(_)) => "INTEGER",
+            (Self::Postgres, NumberContent::I64(_) | NumberContent::U32(_)) => "BIGINT",
+            // Postgres has no unsigned integers, u64 values are inserted as i64 ones
+            (Self::Postgres, NumberContent::U64(_)) => "BIGINT",
+            (Self::Postgres, NumberContent::F32(_)) => "REAL",
+            (Self::Postgres, NumberContent::F64(_)) => "DOUBLE PRECISION",
+            (Self::MySql, NumberContent::I8(_)) => "TINYINT",
+            (Self::MySql, NumberContent::I16(_)) => "SMALLINT",
+            (Self::MySql, NumberContent::I32(_)) => "INT",
+            (Self::MySql, NumberContent::I64(_)) => "BIGINT",
+            (Self::MySql, NumberContent::U32(_)) => "INT UNSIGNED",
+            (Self::MySql, NumberContent::U64(_)) => "BIGINT UNSIGNED",
+            (Self::MySql, NumberContent::F32(_)) => "FLOAT",
+            (Self::MySql, NumberContent::F64(_)) => "DOUBLE",
+        }
+    }
+
+    fn date_time_type(self, type_: &ChronoValueType) -> &'static str {
+        match (self, type_) {
+            (_, ChronoValueType::NaiveDate) => "DATE",
+            (_, ChronoValueType::NaiveTime) => "TIME",
+            (Self::Postgres, ChronoValueType::NaiveDateTime) => "TIMESTAMP",
+            (Self::Postgres, ChronoValueType::DateTime) => "TIMESTAMPTZ",
+            (Self::MySql, ChronoValueType::NaiveDateTime) => "DATETIME",
+            (Self::MySql, ChronoValueType::DateTime) => "TIMESTAMP",
+            (Self::Sqlite, ChronoValueType::NaiveDateTime | ChronoValueType::DateTime) => {
+                "DATETIME"
+            }
+        }
+    }
+
+    fn text_type(self) -> String {
+        match self {
+            // MySQL cannot index `TEXT` columns without a prefix length, so they could not be unique
+            Self::MySql => "VARCHAR(255)",
+            Self::Postgres | Self::Sqlite => "TEXT",
+        }
+        .to_string()
+    }
+
+    fn json_type(self) -> &'static str {
+        match self {
+            Self::Postgres => "JSONB",
+            Self::MySql => "JSON",
+            Self::Sqlite => "TEXT",
+        }
+    }
+}
+
+/// The table and column referenced by a `same_as`, if it refers to a column of a table.
+fn referenced_column(namespace: &Namespace, reference: &FieldRef) -> Option<(String, String)> {
+    let fields: Vec<_> = reference.iter_fields().collect();
+    let column = match (
+        namespace.get_collection(reference.collection()).ok()?,
+        &fields[..],
+    ) {
+        (Content::Array(_), ["content", column]) | (Content::Object(_), [column]) => column,
+        _ => return None,
+    };
+    Some((reference.collection().to_string(), column.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn namespace() -> Namespace {
+        serde_json::from_value(serde_json::json!({
+            "users": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "u64", "id": {} },
+                    "email": {
+                        "type": "unique",
+                        "content": { "type": "string", "faker": { "generator": "safe_email" } }
+                    },
+                    "joined": {
+                        "type": "one_of",
+                        "variants": [
+                            { "type": "date_time", "format": "%Y-%m-%d", "subtype": "naive_date" },
+                            { "type": "null" }
+                        ]
+                    },
+                    "password": {
+                        "type": "hidden",
+                        "content": { "type": "string", "pattern": "[a-z]{8}" }
+                    }
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "unique_together": [["author", "score"]],
+                    "id": { "type": "number", "subtype": "i32", "id": {} },
+                    "author": { "type": "same_as", "ref": "users.content.id" },
+                    "score": { "type": "number", "subtype": "f64", "range": {} },
+                    "tags": {
+                        "type": "array",
+                        "length": 2,
+                        "content": { "type": "string", "pattern": "[a-z]{4}" }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn create_tables_postgres() {
+        let statements = create_tables(SqlDialect::Postgres, &namespace()).unwrap();
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE TABLE IF NOT EXISTS \"users\" (\n    \
+                \"email\" TEXT NOT NULL UNIQUE,\n    \
+                \"id\" BIGINT PRIMARY KEY,\n    \
+                \"joined\" DATE\n);",
+                "CREATE TABLE IF NOT EXISTS \"posts\" (\n    \
+                \"author\" BIGINT NOT NULL,\n    \
+                \"id\" INTEGER PRIMARY KEY,\n    \
+                \"score\" DOUBLE PRECISION NOT NULL,\n    \
+                \"tags\" TEXT[] NOT NULL,\n    \
+                UNIQUE (\"author\", \"score\"),\n    \
+                FOREIGN KEY (\"author\") REFERENCES \"users\" (\"id\")\n);",
+            ]
+        );
+    }
+
+    #[test]
+    fn create_tables_mysql_and_sqlite() {
+        let statements = create_tables(SqlDialect::MySql, &namespace()).unwrap();
+        assert!(statements[0].contains("`email` VARCHAR(255) NOT NULL UNIQUE"));
+        assert!(statements[1].contains("`score` DOUBLE NOT NULL"));
+        assert!(statements[1].contains("`tags` JSON NOT NULL"));
+
+        let statements = create_tables(SqlDialect::Sqlite, &namespace()).unwrap();
+        assert!(statements[0].contains("\"id\" INTEGER PRIMARY KEY"));
+        assert!(statements[1].contains("\"score\" REAL NOT NULL"));
+    }
+
+    #[test]
+    fn create_tables_in_dependency_order() {
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "employees": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "u64", "id": {} },
+                    "team": { "type": "same_as", "ref": "teams.content.id" }
+                }
+            },
+            "teams": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "u64", "id": {} },
+                    "lead": { "type": "number", "subtype": "u64", "range": {} }
+                }
+            }
+        }))
+        .unwrap();
+
+        let statements = create_tables(SqlDialect::Postgres, &namespace).unwrap();
+        assert!(statements[0].starts_with("CREATE TABLE IF NOT EXISTS \"teams\""));
+        assert!(statements[1].contains("REFERENCES \"teams\" (\"id\")"));
+    }
+}
@@ -3,28 +3,49 @@ use crate::cli::json::{JsonFileExportStrategy, JsonStdoutExportStrategy};
 use crate::cli::jsonl::{JsonLinesFileExportStrategy, JsonLinesStdoutExportStrategy};
 use crate::cli::mongo::MongoExportStrategy;
 use crate::cli::mysql::MySqlExportStrategy;
+use crate::cli::parquet::ParquetFileExportStrategy;
 use crate::cli::postgres::PostgresExportStrategy;
+use crate::cli::sql::{
+    SqlDialect, SqlDump, SqlFileExportStrategy, SqlFormat, SqlStdoutExportStrategy,
+};
+use crate::cli::sqlite::SqliteExportStrategy;
 
 use anyhow::{Context, Result};
 
+use sqlx::{Database, Encode, Executor, Transaction, Type};
+
 use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use std::path::PathBuf;
 
-use crate::datasource::DataSource;
-use crate::sampler::SamplerOutput;
+use crate::datasource::relational_datasource::{
+    back_fill, get_tables_metadata, insert_relational_data, truncate_tables, ColumnInfo,
+    DeferredKey, ForeignKey, PrimaryKey, SqlxDataSource, TransactionMode,
+};
+use crate::datasource::WriteMode;
+use crate::sampler::{Sample, SamplerOutput};
 use async_std::task;
-use synth_core::{DataSourceParams, Namespace, Value};
+use synth_core::graph::Graph;
+use synth_core::{Content, DataSourceParams, Namespace, Value};
+
+use super::{map_from_uri_query, uri_without_query_params};
 
-use super::map_from_uri_query;
+/// Query parameters of database URIs which configure synth rather than the connection.
+const DATABASE_QUERY_PARAMS: &[&str] = &["transaction", "write_mode"];
 
+/// A sink for generated data.
+///
+/// The sample is generated lazily as the exporter iterates over its chunks, so exporters should
+/// write each chunk before pulling the next one whenever the output format allows it.
 pub(crate) trait ExportStrategy {
-    fn export(&self, namespace: Namespace, sample: SamplerOutput) -> Result<()>;
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()>;
 }
 
 pub(crate) struct ExportStrategyBuilder<'a, W> {
     params: DataSourceParams<'a>,
     writer: W,
+    create_tables: bool,
 }
 
 impl<'a> TryFrom<DataSourceParams<'a>> for ExportStrategyBuilder<'a, std::io::Stdout> {
@@ -34,6 +55,7 @@ impl<'a> TryFrom<DataSourceParams<'a>> for ExportStrategyBuilder<'a, std::io::St
         Ok(Self {
             params,
             writer: std::io::stdout(),
+            create_tables: false,
         })
     }
 }
@@ -43,6 +65,15 @@ impl<'a, W> ExportStrategyBuilder<'a, W> {
         ExportStrategyBuilder {
             params: self.params,
             writer,
+            create_tables: self.create_tables,
+        }
+    }
+
+    /// Create the tables of the namespace before inserting into them (SQL databases only).
+    pub fn set_create_tables(self, create_tables: bool) -> Self {
+        Self {
+            create_tables,
+            ..self
         }
     }
 }
@@ -52,23 +83,52 @@ where
     W: Write + 'w,
 {
     pub fn build(self) -> Result<Box<dyn ExportStrategy + 'w>> {
-        let Self { params, writer } = self;
+        let Self {
+            params,
+            writer,
+            create_tables,
+        } = self;
 
         // Due to all the schemes used, with the exception of 'mongodb', being non-standard (including 'postgres' and
         // 'mysql' suprisingly) it seems simpler to just match based on the scheme string instead of on enum variants.
         let scheme = params.uri.scheme().as_str().to_lowercase();
         let query = map_from_uri_query(params.uri.query());
 
+        if create_tables
+            && !matches!(
+                scheme.as_str(),
+                "postgres" | "postgresql" | "mysql" | "mariadb" | "sqlite"
+            )
+            && !scheme.starts_with("sql+")
+        {
+            return Err(anyhow!(
+                "Tables can only be created in SQL databases and scripts ('postgres', 'mysql', 'mariadb', 'sqlite' and 'sql+' URI schemes)."
+            ));
+        }
+
         let export_strategy: Box<dyn ExportStrategy> = match scheme.as_str() {
             "postgres" | "postgresql" => Box::new(PostgresExportStrategy {
-                uri_string: params.uri.to_string(),
+                uri_string: uri_without_query_params(&params.uri, DATABASE_QUERY_PARAMS),
                 schema: params.schema,
+                create_tables,
+                transaction: transaction_mode(&query)?,
+                write_mode: write_mode(&query)?,
             }),
             "mongodb" => Box::new(MongoExportStrategy {
-                uri_string: params.uri.to_string(),
+                uri_string: uri_without_query_params(&params.uri, DATABASE_QUERY_PARAMS),
+                write_mode: write_mode(&query)?,
             }),
             "mysql" | "mariadb" => Box::new(MySqlExportStrategy {
-                uri_string: params.uri.to_string(),
+                uri_string: uri_without_query_params(&params.uri, DATABASE_QUERY_PARAMS),
+                create_tables,
+                transaction: transaction_mode(&query)?,
+                write_mode: write_mode(&query)?,
+            }),
+            "sqlite" => Box::new(SqliteExportStrategy {
+                uri_string: uri_without_query_params(&params.uri, DATABASE_QUERY_PARAMS),
+                create_tables,
+                transaction: transaction_mode(&query)?,
+                write_mode: write_mode(&query)?,
             }),
             "json" => {
                 if params.uri.path() == "" {
@@ -108,9 +168,55 @@ where
                     })
                 }
             }
+            "parquet" => {
+                if params.uri.path() == "" {
+                    return Err(anyhow!(
+                        "Parquet data cannot be written to stdout. Specify the directory into which to write Parquet files (e.g. 'parquet:/tmp/output')."
+                    ));
+                } else {
+                    Box::new(ParquetFileExportStrategy {
+                        to_dir: PathBuf::from(params.uri.path().to_string()),
+                    })
+                }
+            }
+            "sql+postgres" | "sql+postgresql" | "sql+mysql" | "sql+mariadb" | "sql+sqlite" => {
+                let dialect = match scheme.as_str() {
+                    "sql+mysql" | "sql+mariadb" => SqlDialect::MySql,
+                    "sql+sqlite" => SqlDialect::Sqlite,
+                    _ => SqlDialect::Postgres,
+                };
+                let format = match query.get("format").copied().unwrap_or("insert") {
+                    "insert" => SqlFormat::Insert,
+                    "copy" => SqlFormat::Copy,
+                    other => {
+                        return Err(anyhow!(
+                            "Unknown SQL format '{}'. Was expecting one of 'insert' or 'copy'.",
+                            other
+                        ))
+                    }
+                };
+                let dump = SqlDump::new(dialect, format, params.schema, create_tables)?;
+
+                if params.uri.path() == "" {
+                    Box::new(SqlStdoutExportStrategy {
+                        dump,
+                        writer: RefCell::new(writer),
+                    })
+                } else {
+                    Box::new(SqlFileExportStrategy {
+                        to_file: PathBuf::from(params.uri.path().to_string()),
+                        dump,
+                    })
+                }
+            }
+            "sql" => {
+                return Err(anyhow!(
+                    "The SQL dialect must be part of the URI scheme. Use 'sql+postgres', 'sql+mysql' or 'sql+sqlite' (e.g. 'sql+postgres:/tmp/seed.sql')."
+                ));
+            }
             _ => {
                 return Err(anyhow!(
-                    "Export URI scheme not recognised. Was expecting one of 'mongodb', 'postgres', 'mysql', 'mariadb', 'json', 'jsonl' or 'csv'."
+                    "Export URI scheme not recognised. Was expecting one of 'mongodb', 'postgres', 'mysql', 'mariadb', 'sqlite', 'json', 'jsonl', 'csv', 'parquet', 'sql+postgres', 'sql+mysql' or 'sql+sqlite'."
                 ));
             }
         };
@@ -118,33 +224,218 @@ where
     }
 }
 
-pub(crate) fn create_and_insert_values<T: DataSource>(
-    sample: SamplerOutput,
+/// The `transaction` query parameter of a database URI.
+fn transaction_mode(query: &HashMap<&str, &str>) -> Result<TransactionMode> {
+    match query.get("transaction") {
+        None | Some(&"single") => Ok(TransactionMode::Single),
+        Some(&"chunk") => Ok(TransactionMode::Chunk),
+        Some(other) => Err(anyhow!(
+            "Unknown transaction mode '{}'. Was expecting 'single' or 'chunk'.",
+            other
+        )),
+    }
+}
+
+/// The `write_mode` query parameter of a database URI.
+fn write_mode(query: &HashMap<&str, &str>) -> Result<WriteMode> {
+    match query.get("write_mode") {
+        None | Some(&"append") => Ok(WriteMode::Append),
+        Some(&"truncate") => Ok(WriteMode::Truncate),
+        Some(&"upsert") => Ok(WriteMode::Upsert),
+        Some(other) => Err(anyhow!(
+            "Unknown write mode '{}'. Was expecting 'append', 'truncate' or 'upsert'.",
+            other
+        )),
+    }
+}
+
+/// Insert the sample into `datasource`, inserting the collections of each chunk in the order in
+/// which they are generated so that referenced rows are inserted before the rows referencing them.
+///
+/// All the rows are inserted in a single transaction, or in a transaction per chunk with
+/// [`TransactionMode::Chunk`], which is rolled back if anything fails. With
+/// [`WriteMode::Truncate`], the tables of the generated collections are emptied first, as part of
+/// the same transaction or in a transaction of their own.
+pub(crate) fn create_and_insert_values<T: SqlxDataSource + Sync>(
+    namespace: &Namespace,
+    sample: Sample,
     datasource: &T,
-) -> Result<()> {
-    match sample {
-        SamplerOutput::Collection(name, value) => {
-            insert_data(datasource, name.as_ref(), value)?;
-        }
-        SamplerOutput::Namespace(namespace) => {
-            for (name, value) in namespace.into_iter() {
-                insert_data(datasource, name.as_ref(), value)?;
+    transaction_mode: TransactionMode,
+    write_mode: WriteMode,
+) -> Result<()>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    String: Type<T::DB>,
+    for<'d> String: Encode<'d, T::DB>,
+    ColumnInfo: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    PrimaryKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    ForeignKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    Value: Type<T::DB>,
+    for<'d> Value: Encode<'d, T::DB>,
+{
+    let mut transaction = None;
+    let mut rows_per_table = BTreeMap::new();
+    task::block_on(async {
+        let result = insert_sample(
+            namespace,
+            datasource,
+            sample,
+            transaction_mode,
+            write_mode,
+            &mut transaction,
+            &mut rows_per_table,
+        )
+        .await;
+
+        if let Err(err) = result {
+            if let Some(transaction) = transaction.take() {
+                // The error which caused the rollback is the one worth reporting
+                if let Err(rollback_err) = transaction.rollback().await {
+                    return Err(err.context(format!("Failed to roll back: {rollback_err}")));
+                }
             }
+            return Err(match transaction_mode {
+                TransactionMode::Single => err.context("Rolled back all the inserted rows"),
+                TransactionMode::Chunk => err.context(
+                    "Rolled back the rows of the failed chunk, the rows of the chunks inserted before it were kept",
+                ),
+            });
         }
-    };
+        Ok(())
+    })?;
+
+    info!("Inserted rows:");
+    for (table, rows) in rows_per_table {
+        info!("  {table}: {rows}");
+    }
 
     Ok(())
 }
 
-fn insert_data<T: DataSource>(
+async fn insert_sample<T: SqlxDataSource + Sync>(
+    namespace: &Namespace,
     datasource: &T,
-    collection_name: &str,
-    collection: Value,
-) -> Result<()> {
-    let to_insert = match collection {
-        Value::Array(elems) => elems,
-        non_array => vec![non_array],
+    sample: Sample<'_>,
+    transaction_mode: TransactionMode,
+    write_mode: WriteMode,
+    transaction: &mut Option<Transaction<'static, T::DB>>,
+    rows_per_table: &mut BTreeMap<String, usize>,
+) -> Result<()>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    String: Type<T::DB>,
+    for<'d> String: Encode<'d, T::DB>,
+    ColumnInfo: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    PrimaryKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    ForeignKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    Value: Type<T::DB>,
+    for<'d> Value: Encode<'d, T::DB>,
+{
+    let pool = datasource.get_multithread_pool();
+    let graph = Graph::from_namespace(namespace)?;
+    let ordered: Vec<&str> = graph
+        .iter_ordered()
+        .map(|iter| iter.collect())
+        .unwrap_or_default();
+
+    let mut sample = sample.peekable();
+    // The tables of the generated collections, the tables referencing others first
+    let mut table_names: Vec<&str> = match sample.peek() {
+        Some(Ok(SamplerOutput::Collection(name, _))) => vec![name.as_str()],
+        _ => namespace
+            .keys()
+            .filter(|name| !matches!(namespace.get_collection(name), Ok(Content::Hidden(_))))
+            .collect(),
+    };
+    table_names.sort_by_key(|name| ordered.iter().position(|ordered| ordered == name));
+    table_names.reverse();
+
+    // Queried before any transaction holds a connection, as SQLite only has one
+    let metadata = {
+        let mut connection = pool.acquire().await?;
+        get_tables_metadata(datasource, &mut connection, &table_names).await?
     };
-    task::block_on(datasource.insert_data(collection_name, &to_insert[..]))
-        .with_context(|| format!("Failed to insert data for collection {collection_name}"))
+
+    if write_mode == WriteMode::Truncate {
+        // Only the tables of the generated collections are truncated
+        let mut truncation = pool.begin().await?;
+        truncate_tables(datasource, &mut truncation, &table_names).await?;
+        match transaction_mode {
+            TransactionMode::Single => *transaction = Some(truncation),
+            TransactionMode::Chunk => truncation.commit().await?,
+        }
+    }
+
+    for chunk in sample {
+        let mut collections = chunk?.into_collections();
+        collections.sort_by_key(|(name, _)| ordered.iter().position(|ordered| ordered == name));
+        let names: Vec<_> = collections.iter().map(|(name, _)| name.clone()).collect();
+
+        // Foreign keys to the collections inserted after another one (which form a cycle) are
+        // back-filled once these are inserted
+        let mut deferred_keys = Vec::new();
+        for (index, (name, value)) in collections.into_iter().enumerate() {
+            let rows = match value {
+                Value::Array(elems) => elems,
+                non_array => vec![non_array],
+            };
+
+            // Shared collections are only generated in the first chunk, and the other ones only
+            // in the following chunks
+            let inserted = rows_per_table.entry(name.clone()).or_default();
+            if rows.is_empty() {
+                continue;
+            }
+            *inserted += rows.len();
+
+            if transaction.is_none() {
+                *transaction = Some(pool.begin().await?);
+            }
+            let connection = transaction.as_mut().unwrap();
+            let pending_tables: Vec<_> = names[index + 1..].iter().map(String::as_str).collect();
+            let table_metadata = metadata
+                .get(&name)
+                .with_context(|| format!("No table found for collection {name}"))?;
+            let keys = insert_relational_data(
+                datasource,
+                connection,
+                &name,
+                &rows,
+                table_metadata,
+                write_mode,
+                &pending_tables,
+            )
+            .await
+            .with_context(|| format!("Failed to insert data for collection {name}"))?;
+            deferred_keys.extend(keys);
+
+            let (referencing, others): (Vec<DeferredKey>, _) = deferred_keys
+                .into_iter()
+                .partition(|deferred_key| deferred_key.foreign_key.to_table == name);
+            deferred_keys = others;
+            for deferred_key in referencing {
+                back_fill(datasource, connection, &deferred_key)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to back-fill the foreign key {}.{} referencing collection {name}",
+                            deferred_key.foreign_key.from_table,
+                            deferred_key.foreign_key.from_column
+                        )
+                    })?;
+            }
+        }
+
+        if transaction_mode == TransactionMode::Chunk {
+            if let Some(transaction) = transaction.take() {
+                transaction.commit().await?;
+            }
+        }
+    }
+
+    if let Some(transaction) = transaction.take() {
+        transaction.commit().await?;
+    }
+
+    Ok(())
 }
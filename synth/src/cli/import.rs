@@ -10,7 +10,9 @@ use crate::cli::json::{JsonFileImportStrategy, JsonStdinImportStrategy};
 use crate::cli::jsonl::{JsonLinesFileImportStrategy, JsonLinesStdinImportStrategy};
 use crate::cli::mongo::MongoImportStrategy;
 use crate::cli::mysql::MySqlImportStrategy;
+use crate::cli::parquet::ParquetFileImportStrategy;
 use crate::cli::postgres::PostgresImportStrategy;
+use crate::cli::sqlite::SqliteImportStrategy;
 
 use super::map_from_uri_query;
 
@@ -45,6 +47,9 @@ impl TryFrom<DataSourceParams<'_>> for Box<dyn ImportStrategy> {
             "mysql" | "mariadb" => Box::new(MySqlImportStrategy {
                 uri_string: params.uri.to_string(),
             }),
+            "sqlite" => Box::new(SqliteImportStrategy {
+                uri_string: params.uri.to_string(),
+            }),
             "json" => {
                 if params.uri.path() == "" {
                     Box::new(JsonStdinImportStrategy)
@@ -88,9 +93,20 @@ impl TryFrom<DataSourceParams<'_>> for Box<dyn ImportStrategy> {
                     })
                 }
             }
+            "parquet" => {
+                if params.uri.path() == "" {
+                    return Err(anyhow!(
+                        "Parquet data cannot be read from stdin. Specify the directory from which to read Parquet files (e.g. 'parquet:/tmp/input')."
+                    ));
+                } else {
+                    Box::new(ParquetFileImportStrategy {
+                        from_dir: PathBuf::from(params.uri.path().to_string()),
+                    })
+                }
+            }
             _ => {
                 return Err(anyhow!(
-                    "Import URI scheme not recognised. Was expecting one of 'mongodb', 'postgres', 'mysql', 'mariadb', 'json', 'jsonl', or 'csv'."
+                    "Import URI scheme not recognised. Was expecting one of 'mongodb', 'postgres', 'mysql', 'mariadb', 'sqlite', 'json', 'jsonl', 'csv' or 'parquet'."
                 ));
             }
         };
@@ -1,5 +1,6 @@
 use crate::datasource::relational_datasource::{
-    get_columns_info, ColumnInfo, ForeignKey, PrimaryKey, SqlxDataSource, ValueWrapper,
+    get_columns_info, get_foreign_keys, get_primary_keys, get_unique_keys, ColumnInfo, ForeignKey,
+    PrimaryKey, SqlxDataSource, UniqueKey, ValueWrapper,
 };
 use crate::datasource::DataSource;
 use anyhow::{Context, Result};
@@ -8,7 +9,7 @@ use log::debug;
 use serde_json::Value;
 use sqlx::{Executor, Row};
 use synth_core::graph::json::synth_val_to_json;
-use synth_core::schema::content::number_content::U64;
+use synth_core::schema::content::number_content::{I16, I32, I64, I8, U32, U64};
 use synth_core::schema::{
     ArrayContent, FieldRef, NumberContent, ObjectContent, OptionalMergeStrategy, RangeStep,
     SameAsContent, UniqueContent,
@@ -32,6 +33,7 @@ where
     for<'d> String: sqlx::Decode<'d, T::DB> + sqlx::Encode<'d, T::DB>,
     usize: sqlx::ColumnIndex<<T::DB as sqlx::Database>::Row>,
     PrimaryKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
+    UniqueKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
     ForeignKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
     ValueWrapper: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
     ColumnInfo: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
@@ -47,6 +49,9 @@ where
     info!("Building namespace primary keys...");
     populate_namespace_primary_keys(&mut namespace, &table_names, datasource)?;
 
+    info!("Building namespace unique keys...");
+    populate_namespace_unique_keys(&mut namespace, &table_names, datasource)?;
+
     info!("Building namespace foreign keys...");
     populate_namespace_foreign_keys(&mut namespace, datasource)?;
 
@@ -90,7 +95,10 @@ where
     for table_name in table_names.iter() {
         info!("Building {} collection...", table_name);
 
-        let column_infos = task::block_on(get_columns_info(datasource, table_name.to_string()))?;
+        let column_infos = task::block_on(async {
+            let mut connection = datasource.get_pool().acquire().await?;
+            get_columns_info(datasource, &mut connection, table_name.to_string()).await
+        })?;
 
         namespace.put_collection(
             table_name.clone(),
@@ -113,18 +121,18 @@ where
     PrimaryKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
 {
     for table_name in table_names.iter() {
-        let primary_keys = task::block_on(get_primary_keys(datasource, table_name.to_string()))?;
+        let primary_keys = task::block_on(async {
+            let mut connection = datasource.get_pool().acquire().await?;
+            get_primary_keys(datasource, &mut connection, table_name.to_string()).await
+        })?;
 
         if primary_keys.len() > 1 {
-            bail!(
-                "{} primary keys found at collection {}. Synth does not currently support \
-            composite primary keys.",
-                primary_keys.len(),
-                table_name
-            )
-        }
-
-        if let Some(primary_key) = primary_keys.first() {
+            let columns = primary_keys
+                .into_iter()
+                .map(|primary_key| primary_key.column_name)
+                .collect();
+            add_unique_together(namespace, table_name, columns)?;
+        } else if let Some(primary_key) = primary_keys.first() {
             let field = FieldRef::new(format!(
                 "{}.content.{}",
                 table_name, primary_key.column_name
@@ -148,27 +156,84 @@ where
     Ok(())
 }
 
-async fn get_primary_keys<T: SqlxDataSource>(
+fn populate_namespace_unique_keys<T: SqlxDataSource>(
+    namespace: &mut Namespace,
+    table_names: &[String],
     datasource: &T,
-    table_name: String,
-) -> Result<Vec<PrimaryKey>>
+) -> Result<()>
 where
     for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
     String: sqlx::Type<T::DB>,
     for<'d> String: sqlx::Encode<'d, T::DB>,
-    PrimaryKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
+    UniqueKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
 {
-    let query = datasource.get_primary_keys_query();
-    let pool = datasource.get_pool();
+    for table_name in table_names.iter() {
+        let unique_keys = task::block_on(async {
+            let mut connection = datasource.get_pool().acquire().await?;
+            get_unique_keys(datasource, &mut connection, table_name.to_string()).await
+        })?;
+
+        debug!(
+            "{} unique keys found at collection {}.",
+            unique_keys.len(),
+            table_name
+        );
+
+        for mut columns in unique_keys {
+            if columns.len() > 1 {
+                add_unique_together(namespace, table_name, columns)?;
+                continue;
+            }
+
+            let field = FieldRef::new(format!("{}.content.{}", table_name, columns.remove(0)))?;
+            let node = namespace.get_s_node_mut(&field)?;
+            let is_id = matches!(
+                node,
+                Content::Number(
+                    NumberContent::I8(I8::Id(_))
+                        | NumberContent::I16(I16::Id(_))
+                        | NumberContent::I32(I32::Id(_))
+                        | NumberContent::I64(I64::Id(_))
+                        | NumberContent::U32(U32::Id(_))
+                        | NumberContent::U64(U64::Id(_))
+                )
+            );
+            if node.is_unique() || is_id {
+                continue;
+            }
+
+            // Unique columns can hold any number of nulls
+            *node = match node.as_nullable() {
+                Some(content) => content.clone().into_unique().into_nullable(),
+                None => node.clone().into_unique(),
+            };
+        }
+    }
 
-    datasource
-        .query(query)
-        .bind(table_name)
-        .fetch_all(&pool)
-        .await?
-        .into_iter()
-        .map(PrimaryKey::try_from)
-        .collect()
+    Ok(())
+}
+
+/// Makes the values of `columns` unique together in the rows of `table_name`.
+fn add_unique_together(
+    namespace: &mut Namespace,
+    table_name: &str,
+    mut columns: Vec<String>,
+) -> Result<()> {
+    let collection = namespace.get_collection_mut(table_name)?;
+    if let Content::Array(ArrayContent { content, .. }) = collection {
+        if let Content::Object(object) = content.as_mut() {
+            for column in &columns {
+                object.get(column)?;
+            }
+
+            columns.sort();
+            if !object.unique_together.contains(&columns) {
+                object.unique_together.push(columns);
+            }
+        }
+    }
+
+    Ok(())
 }
 
 fn populate_namespace_foreign_keys<T: SqlxDataSource>(
@@ -179,37 +244,30 @@ where
     for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
     ForeignKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
 {
-    let foreign_keys = task::block_on(get_foreign_keys(datasource))?;
+    let foreign_keys = task::block_on(async {
+        let mut connection = datasource.get_pool().acquire().await?;
+        get_foreign_keys(datasource, &mut connection).await
+    })?;
 
     debug!("{} foreign keys found.", foreign_keys.len());
 
     for fk in foreign_keys {
         let from_field = FieldRef::new(format!("{}.content.{}", fk.from_table, fk.from_column))?;
         let to_field = FieldRef::new(format!("{}.content.{}", fk.to_table, fk.to_column))?;
-        let node = namespace.get_s_node_mut(&from_field)?;
-        *node = Content::SameAs(SameAsContent { ref_: to_field });
+        let same_as = Content::SameAs(SameAsContent {
+            ref_: to_field,
+            cardinality: None,
+            distinct: false,
+        });
+        match namespace.get_s_node_mut(&from_field)? {
+            Content::Unique(unique) => *unique.content = same_as,
+            node => *node = same_as,
+        }
     }
 
     Ok(())
 }
 
-async fn get_foreign_keys<T: SqlxDataSource>(datasource: &T) -> Result<Vec<ForeignKey>>
-where
-    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
-    ForeignKey: TryFrom<<T::DB as sqlx::Database>::Row, Error = anyhow::Error>,
-{
-    let query = datasource.get_foreign_keys_query();
-    let pool = datasource.get_pool();
-
-    datasource
-        .query(query)
-        .fetch_all(&pool)
-        .await?
-        .into_iter()
-        .map(ForeignKey::try_from)
-        .collect()
-}
-
 fn populate_namespace_values<T: SqlxDataSource + Sync>(
     namespace: &mut Namespace,
     table_names: &[String],
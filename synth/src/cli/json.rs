@@ -1,6 +1,6 @@
 use crate::cli::export::ExportStrategy;
 use crate::cli::import::ImportStrategy;
-use crate::sampler::SamplerOutput;
+use crate::sampler::Sample;
 
 use synth_core::schema::{MergeStrategy, OptionalMergeStrategy};
 use synth_core::{Content, Namespace};
@@ -17,8 +17,11 @@ pub struct JsonFileExportStrategy {
 }
 
 impl ExportStrategy for JsonFileExportStrategy {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
-        std::fs::write(&self.from_file, sample.into_json().to_string())?;
+    fn export(&self, _namespace: Namespace, sample: Sample) -> Result<()> {
+        std::fs::write(
+            &self.from_file,
+            sample.into_output()?.into_json().to_string(),
+        )?;
 
         Ok(())
     }
@@ -30,9 +33,13 @@ pub struct JsonStdoutExportStrategy<W> {
 }
 
 impl<W: Write> ExportStrategy for JsonStdoutExportStrategy<W> {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
-        writeln!(self.writer.borrow_mut(), "{}", sample.into_json())
-            .expect("failed to write json output");
+    fn export(&self, _namespace: Namespace, sample: Sample) -> Result<()> {
+        writeln!(
+            self.writer.borrow_mut(),
+            "{}",
+            sample.into_output()?.into_json()
+        )
+        .expect("failed to write json output");
 
         Ok(())
     }
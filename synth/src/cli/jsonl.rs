@@ -1,6 +1,6 @@
 use crate::cli::export::ExportStrategy;
 use crate::cli::import::ImportStrategy;
-use crate::sampler::SamplerOutput;
+use crate::sampler::{Sample, SamplerOutput};
 
 use synth_core::graph::{json::synth_val_to_json, Value};
 use synth_core::schema::{MergeStrategy, OptionalMergeStrategy};
@@ -20,11 +20,13 @@ pub struct JsonLinesFileExportStrategy {
 }
 
 impl ExportStrategy for JsonLinesFileExportStrategy {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, _namespace: Namespace, sample: Sample) -> Result<()> {
         let mut f = std::io::BufWriter::new(std::fs::File::create(&self.from_file)?);
 
-        for val in json_lines_from_sampler_output(sample, &self.collection_field_name) {
-            f.write_all((val.to_string() + "\n").as_bytes())?;
+        for chunk in sample {
+            for val in json_lines_from_sampler_output(chunk?, &self.collection_field_name) {
+                f.write_all((val.to_string() + "\n").as_bytes())?;
+            }
         }
 
         Ok(())
@@ -38,10 +40,12 @@ pub struct JsonLinesStdoutExportStrategy<W> {
 }
 
 impl<W: Write> ExportStrategy for JsonLinesStdoutExportStrategy<W> {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, _namespace: Namespace, sample: Sample) -> Result<()> {
         // TODO: Warn user if the collection field name would overwrite an existing field in a collection.
-        for line in json_lines_from_sampler_output(sample, &self.collection_field_name) {
-            writeln!(self.writer.borrow_mut(), "{line}").expect("failed to write jsonl line");
+        for chunk in sample {
+            for line in json_lines_from_sampler_output(chunk?, &self.collection_field_name) {
+                writeln!(self.writer.borrow_mut(), "{line}").expect("failed to write jsonl line");
+            }
         }
 
         Ok(())
@@ -1,5 +1,6 @@
 #![allow(clippy::assertions_on_result_states)]
 mod csv;
+mod ddl;
 mod export;
 mod import;
 mod import_utils;
@@ -7,7 +8,10 @@ mod json;
 mod jsonl;
 mod mongo;
 mod mysql;
+mod parquet;
 mod postgres;
+mod sql;
+mod sqlite;
 mod store;
 
 use crate::cli::import::ImportStrategy;
@@ -18,11 +22,13 @@ use crate::version::print_version_message;
 use anyhow::{Context, Result};
 use rand::RngCore;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 use structopt::clap::AppSettings;
 use structopt::StructOpt;
+use synth_core::compile::NamespaceCompiler;
 use synth_core::DataSourceParams;
 use uriparse::URI;
 
@@ -78,10 +84,44 @@ impl<'w> Cli {
         }
     }
 
+    /// Split the arguments of `--size` into the number of values to generate across the
+    /// namespace, or the number of elements to generate for some collections.
+    fn derive_sizes(sizes: Vec<SizeArg>) -> Result<Sizes> {
+        let mut total = None;
+        let mut collections = BTreeMap::new();
+        for SizeArg { collection, size } in sizes {
+            match collection {
+                Some(collection) => {
+                    if collections.insert(collection.clone(), size).is_some() {
+                        return Err(anyhow!(
+                            "The size of collection '{}' is specified more than once.",
+                            collection
+                        ));
+                    }
+                }
+                None if total.is_some() => {
+                    return Err(anyhow!(
+                        "The --size flag can only be given once without a collection name."
+                    ));
+                }
+                None => total = Some(size),
+            }
+        }
+        match total {
+            Some(_) if !collections.is_empty() => Err(anyhow!(
+                "Cannot have both a --size for the namespace and sizes for collections."
+            )),
+            Some(total) => Ok(Sizes::Total(total)),
+            None if collections.is_empty() => Ok(Sizes::Total(1)),
+            None => Ok(Sizes::Collections(collections)),
+        }
+    }
+
     pub async fn run<W: Write + 'w>(&self, args: Args, writer: W) -> Result<()> {
         match args {
             Args::Generate(cmd) => self.generate(cmd, writer),
             Args::Import(cmd) => self.import(cmd),
+            Args::Validate(cmd) => self.validate(cmd, writer),
             #[cfg(feature = "telemetry")]
             Args::Telemetry(cmd) => self.telemetry(cmd, writer),
             Args::Version => {
@@ -164,6 +204,31 @@ impl<'w> Cli {
         }
     }
 
+    fn validate<W: Write>(&self, cmd: ValidateCommand, mut writer: W) -> Result<()> {
+        let namespace = self.store.get_ns(cmd.namespace.clone()).context(format!(
+            "Unable to open the namespace \"{}\"",
+            cmd.namespace
+                .to_str()
+                .expect("The provided namespace is not a valid UTF-8 string")
+        ))?;
+
+        let errors = NamespaceCompiler::new(&namespace).validate();
+        if errors.is_empty() {
+            writeln!(writer, "The namespace is valid.")?;
+            return Ok(());
+        }
+
+        for error in errors.iter() {
+            writeln!(writer, "error: {}", error)?;
+        }
+
+        Err(anyhow!(
+            "Found {} error(s) in the namespace \"{}\"",
+            errors.len(),
+            cmd.namespace.display()
+        ))
+    }
+
     fn generate<W: Write + 'w>(&self, cmd: GenerateCommand, writer: W) -> Result<()> {
         let mut namespace = self.store.get_ns(cmd.namespace.clone()).context(format!(
             "Unable to open the namespace \"{}\"",
@@ -179,7 +244,9 @@ impl<'w> Cli {
         }
         .try_into()?;
 
-        let builder = builder.set_writer(writer);
+        let builder = builder
+            .set_writer(writer)
+            .set_create_tables(cmd.create_tables);
 
         // `mut` is only used by the "telemetry" feature
         #[allow(unused_mut)]
@@ -205,8 +272,18 @@ impl<'w> Cli {
         }
 
         let seed = Self::derive_seed(cmd.random, cmd.seed)?;
-        let sample =
-            Sampler::try_from(&namespace)?.sample_seeded(cmd.collection.clone(), cmd.size, seed)?;
+        if cmd.threads == 0 {
+            return Err(anyhow!("The --threads flag must be at least 1."));
+        }
+        let sampler = Sampler::try_from(&namespace)?.with_threads(cmd.threads);
+        let sample = match Self::derive_sizes(cmd.size)? {
+            Sizes::Total(target) => sampler.sample_seeded(cmd.collection.clone(), target, seed)?,
+            Sizes::Collections(sizes) => sampler.with_collection_sizes(&sizes)?.sample_seeded(
+                cmd.collection.clone(),
+                0,
+                seed,
+            )?,
+        };
 
         export_strategy
             .export(namespace, sample)
@@ -230,6 +307,8 @@ pub enum Args {
     Generate(GenerateCommand),
     #[structopt(about = "Import data from an external source")]
     Import(ImportCommand),
+    #[structopt(about = "Check a namespace for errors without generating any data")]
+    Validate(ValidateCommand),
     #[cfg(feature = "telemetry")]
     #[structopt(about = "Toggle anonymous usage data collection")]
     Telemetry(TelemetryCommand),
@@ -237,12 +316,74 @@ pub enum Args {
     Version,
 }
 
+/// An argument of `--size`: either the number of values to generate across the namespace, or the
+/// number of elements to generate for a collection given as `<collection>=<size>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SizeArg {
+    #[serde(skip)]
+    pub collection: Option<String>,
+    pub size: usize,
+}
+
+impl From<usize> for SizeArg {
+    fn from(size: usize) -> Self {
+        Self {
+            collection: None,
+            size,
+        }
+    }
+}
+
+impl FromStr for SizeArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (collection, size) = match s.rsplit_once('=') {
+            Some((collection, size)) => (Some(collection.to_string()), size),
+            None => (None, s),
+        };
+        let size = size.parse().with_context(|| {
+            format!(
+                "invalid size '{}': expected <size> or <collection>=<size>",
+                s
+            )
+        })?;
+        Ok(Self { collection, size })
+    }
+}
+
+enum Sizes {
+    Total(usize),
+    Collections(BTreeMap<String, usize>),
+}
+
 fn map_from_uri_query<'a>(query_opt: Option<&'a uriparse::Query<'a>>) -> HashMap<&'a str, &'a str> {
     let query_str = query_opt.map(uriparse::Query::as_str).unwrap_or_default();
 
     HashMap::<_, _>::from_iter(querystring::querify(query_str))
 }
 
+/// Removes the query parameters which are options of synth rather than of the database driver, as
+/// some drivers reject parameters they do not know.
+fn uri_without_query_params(uri: &uriparse::URI, params: &[&str]) -> String {
+    let mut uri = uri.clone().into_owned();
+    let query = querystring::querify(uri.query().map(uriparse::Query::as_str).unwrap_or_default())
+        .into_iter()
+        .filter(|(key, _)| !params.contains(key))
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let query = (!query.is_empty()).then(|| {
+        uriparse::Query::try_from(query.as_str())
+            .expect("parameters of a valid query form a valid query")
+            .into_owned()
+    });
+    uri.set_query(query).expect("query is valid");
+
+    uri.to_string()
+}
+
 #[derive(StructOpt, Serialize)]
 pub struct GenerateCommand {
     #[structopt(
@@ -264,11 +405,16 @@ pub struct GenerateCommand {
     )]
     #[serde(skip)]
     pub scenario: Option<String>,
-    #[structopt(long, help = "the number of samples", default_value = "1")]
-    pub size: usize,
     #[structopt(
         long,
-        help = "The URI into which data will be generated. Can be a file-based URI scheme to output data to the filesystem or stdout ('json:', 'jsonl:' and 'csv:' allow outputting JSON, JSON Lines and CSV data respectively) or can be a database URI to write data directly to some database (supports Postgres, MongoDB, and MySQL). Defaults to writing JSON data to stdout. [example: jsonl:/tmp/generation_output]",
+        help = "the number of samples. Can instead be given once per collection as <collection>=<size> to generate exactly <size> elements of <collection> [example: --size users=10 --size events=1000]",
+        default_value = "1",
+        number_of_values = 1
+    )]
+    pub size: Vec<SizeArg>,
+    #[structopt(
+        long,
+        help = "The URI into which data will be generated. Can be a file-based URI scheme to output data to the filesystem or stdout ('json:', 'jsonl:', 'csv:' and 'parquet:' allow outputting JSON, JSON Lines, CSV and Parquet data respectively) or can be a database URI to write data directly to some database (supports Postgres, MongoDB, and MySQL). Defaults to writing JSON data to stdout. [example: jsonl:/tmp/generation_output]",
         default_value = "json:"
     )]
     #[serde(skip)]
@@ -283,12 +429,23 @@ pub struct GenerateCommand {
         help = "generation will use a random seed - this cannot be used with --seed"
     )]
     pub random: bool,
+    #[structopt(
+        long,
+        help = "The number of threads to generate on. The output for a given seed depends on the number of threads",
+        default_value = "1"
+    )]
+    pub threads: usize,
     #[structopt(
         long,
         help = "(Postgres only) Specify the schema into which to generate. Defaults to 'public'."
     )]
     #[serde(skip)]
     pub schema: Option<String>,
+    #[structopt(
+        long,
+        help = "(Postgres, MySQL and SQLite only) Create the tables of the namespace, if they do not exist, before inserting into them"
+    )]
+    pub create_tables: bool,
 }
 
 #[derive(StructOpt, Serialize)]
@@ -307,7 +464,7 @@ pub struct ImportCommand {
     pub collection: Option<String>,
     #[structopt(
         long,
-        help = "The source URI from which to import data. Can be a file-based URI scheme to read data from a file or stdin ('json:', 'jsonl:' and 'csv:' allow reading JSON, JSON Lines and CSV data respectively) or can be a database URI to read data directly from some database (supports Postgres, MongoDB, and MySQL). Defaults to reading JSON data from stdin. [example: jsonl:/tmp/test_data_input]",
+        help = "The source URI from which to import data. Can be a file-based URI scheme to read data from a file or stdin ('json:', 'jsonl:', 'csv:' and 'parquet:' allow reading JSON, JSON Lines, CSV and Parquet data respectively) or can be a database URI to read data directly from some database (supports Postgres, MongoDB, and MySQL). Defaults to reading JSON data from stdin. [example: jsonl:/tmp/test_data_input]",
         default_value = "json:"
     )]
     #[serde(skip)]
@@ -320,6 +477,16 @@ pub struct ImportCommand {
     pub schema: Option<String>,
 }
 
+#[derive(StructOpt, Serialize)]
+pub struct ValidateCommand {
+    #[structopt(
+        help = "The namespace directory from which to read schema files",
+        parse(from_os_str)
+    )]
+    #[serde(skip)]
+    pub namespace: PathBuf,
+}
+
 #[cfg(feature = "telemetry")]
 #[derive(StructOpt, Serialize)]
 pub enum TelemetryCommand {
@@ -342,4 +509,42 @@ pub mod tests {
         assert!(Cli::derive_seed(true, Some(5)).is_err());
         assert!(Cli::derive_seed(true, None).is_ok());
     }
+
+    #[test]
+    fn test_derive_sizes() {
+        let sizes = |args: &[&str]| {
+            Cli::derive_sizes(args.iter().map(|arg| arg.parse().unwrap()).collect())
+        };
+
+        assert!(matches!(sizes(&[]).unwrap(), Sizes::Total(1)));
+        assert!(matches!(sizes(&["10"]).unwrap(), Sizes::Total(10)));
+        match sizes(&["users=10", "events=100000"]).unwrap() {
+            Sizes::Collections(sizes) => assert_eq!(
+                sizes,
+                BTreeMap::from([("users".to_string(), 10), ("events".to_string(), 100000)])
+            ),
+            Sizes::Total(_) => panic!("expected the sizes of collections"),
+        }
+        assert!(sizes(&["10", "users=10"]).is_err());
+        assert!(sizes(&["users=10", "users=5"]).is_err());
+        assert!(sizes(&["10", "5"]).is_err());
+        assert!("users=ten".parse::<SizeArg>().is_err());
+    }
+
+    #[test]
+    fn test_uri_without_query_params() {
+        let without = |uri: &str| {
+            uri_without_query_params(&uriparse::URI::try_from(uri).unwrap(), &["transaction"])
+        };
+
+        assert_eq!(
+            without("postgres://localhost:5432/db?transaction=chunk"),
+            "postgres://localhost:5432/db"
+        );
+        assert_eq!(
+            without("sqlite:///tmp/test.db?mode=rwc&transaction=single"),
+            "sqlite:///tmp/test.db?mode=rwc"
+        );
+        assert_eq!(without("mysql://localhost/db"), "mysql://localhost/db");
+    }
 }
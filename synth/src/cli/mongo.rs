@@ -1,12 +1,13 @@
 use crate::cli::export::ExportStrategy;
 use crate::cli::import::ImportStrategy;
-use crate::sampler::SamplerOutput;
+use crate::datasource::WriteMode;
+use crate::sampler::Sample;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use mongodb::bson::Bson;
-use mongodb::options::FindOptions;
+use mongodb::bson::{doc, Bson};
+use mongodb::options::{FindOptions, ReplaceOptions};
 use mongodb::{bson::Document, options::ClientOptions, sync::Client};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use synth_core::graph::prelude::content::number_content::U64;
 use synth_core::graph::prelude::number_content::I64;
 use synth_core::graph::prelude::{ChronoValue, Number, NumberContent, ObjectContent, RangeStep};
@@ -20,6 +21,7 @@ use synth_core::{Content, Namespace, Value};
 #[derive(Clone, Debug)]
 pub struct MongoExportStrategy {
     pub uri_string: String,
+    pub write_mode: WriteMode,
 }
 
 #[derive(Clone, Debug)]
@@ -151,26 +153,42 @@ fn bson_to_content(bson: &Bson) -> Content {
 }
 
 impl ExportStrategy for MongoExportStrategy {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, _namespace: Namespace, sample: Sample) -> Result<()> {
         let mut client = Client::with_uri_str(&self.uri_string)?;
 
-        match sample {
-            SamplerOutput::Collection(name, value) => {
-                self.insert_data(name.as_ref(), value, &mut client)
-            }
-            SamplerOutput::Namespace(namespace) => {
-                for (name, value) in namespace {
-                    self.insert_data(name.as_ref(), value.clone(), &mut client)?;
+        let mut truncated = BTreeSet::new();
+        for chunk in sample {
+            for (name, value) in chunk?.into_collections() {
+                // Collections cannot reference each other, so each one is emptied right before
+                // its first documents are inserted
+                if self.write_mode == WriteMode::Truncate && truncated.insert(name.clone()) {
+                    self.truncate_collection(&name, &mut client)?;
                 }
-                Ok(())
+                self.insert_data(name.as_ref(), value, &mut client)?;
             }
-        }?;
+        }
 
         Ok(())
     }
 }
 
 impl MongoExportStrategy {
+    fn truncate_collection(&self, collection_name: &str, client: &mut Client) -> Result<()> {
+        let db_name = parse_db_name(&self.uri_string)?;
+
+        let result = client
+            .database(db_name)
+            .collection::<Document>(collection_name)
+            .delete_many(Document::new(), None)?;
+
+        info!(
+            "Deleted {} documents from collection {} ...",
+            result.deleted_count, collection_name
+        );
+
+        Ok(())
+    }
+
     fn insert_data(
         &self,
         collection_name: &str,
@@ -194,11 +212,26 @@ impl MongoExportStrategy {
         }
 
         let n_values = docs.len();
+        if n_values == 0 {
+            return Ok(());
+        }
 
-        client
-            .database(db_name)
-            .collection(collection_name)
-            .insert_many(docs, None)?;
+        let collection = client.database(db_name).collection(collection_name);
+        if self.write_mode == WriteMode::Upsert {
+            let options = ReplaceOptions::builder().upsert(true).build();
+            for doc in docs {
+                let id = match doc.get("_id") {
+                    Some(id) => id.clone(),
+                    None => bail!(
+                        "Cannot upsert into collection {} as its documents have no `_id` field",
+                        collection_name
+                    ),
+                };
+                collection.replace_one(doc! { "_id": id }, doc, options.clone())?;
+            }
+        } else {
+            collection.insert_many(docs, None)?;
+        }
 
         info!(
             "Inserted {} rows into collection {} ...",
@@ -1,22 +1,40 @@
+use crate::cli::ddl::create_tables;
 use crate::cli::export::{create_and_insert_values, ExportStrategy};
 use crate::cli::import::ImportStrategy;
 use crate::cli::import_utils::build_namespace_import;
+use crate::cli::sql::SqlDialect;
 use crate::datasource::mysql_datasource::MySqlDataSource;
-use crate::datasource::DataSource;
-use crate::sampler::SamplerOutput;
+use crate::datasource::relational_datasource::{execute_statements, TransactionMode};
+use crate::datasource::{DataSource, WriteMode};
+use crate::sampler::Sample;
 use anyhow::Result;
+use async_std::task;
 use synth_core::schema::Namespace;
 
 #[derive(Clone, Debug)]
 pub struct MySqlExportStrategy {
     pub uri_string: String,
+    pub create_tables: bool,
+    pub transaction: TransactionMode,
+    pub write_mode: WriteMode,
 }
 
 impl ExportStrategy for MySqlExportStrategy {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
         let datasource = MySqlDataSource::new(&self.uri_string)?;
 
-        create_and_insert_values(sample, &datasource)
+        if self.create_tables {
+            let statements = create_tables(SqlDialect::MySql, &namespace)?;
+            task::block_on(execute_statements(&datasource, &statements))?;
+        }
+
+        create_and_insert_values(
+            &namespace,
+            sample,
+            &datasource,
+            self.transaction,
+            self.write_mode,
+        )
     }
 }
 
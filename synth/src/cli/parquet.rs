@@ -0,0 +1,849 @@
+use crate::cli::export::ExportStrategy;
+use crate::cli::import::ImportStrategy;
+use crate::sampler::Sample;
+
+use synth_core::graph::json::synth_val_to_json;
+use synth_core::schema::content::number_content::{F32, F64, I16, I32, I64, I8, U32, U64};
+use synth_core::schema::content::{
+    ArrayContent, BoolContent, Categorical, ChronoValue, ChronoValueAndFormat, ChronoValueType,
+    DateTimeContent, NumberContent, ObjectContent, RangeStep, StringContent,
+};
+use synth_core::schema::{MergeStrategy, OptionalMergeStrategy};
+use synth_core::{Content, Namespace, Value};
+
+use anyhow::{Context, Result};
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    ArrowPrimitiveType, Date32Type, Date64Type, Decimal128Type, Float16Type, Float32Type,
+    Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, Time32MillisecondType,
+    Time32SecondType, Time64MicrosecondType, Time64NanosecondType, TimestampMicrosecondType,
+    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, PrimitiveArray, StructArray};
+use arrow_json::reader::Decoder;
+use arrow_json::ReaderBuilder;
+use arrow_schema::{DataType, Field, Fields, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The number of rows converted to Arrow and written to Parquet at a time.
+const BATCH_SIZE: usize = 8192;
+
+#[derive(Clone, Debug)]
+pub struct ParquetFileExportStrategy {
+    pub to_dir: PathBuf,
+}
+
+impl ExportStrategy for ParquetFileExportStrategy {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
+        if self.to_dir.exists() {
+            return Err(anyhow!("Output directory already exists"));
+        } else {
+            std::fs::create_dir_all(&self.to_dir)?;
+        }
+
+        let mut writers: Vec<(String, CollectionWriter)> = Vec::new();
+
+        for chunk in sample {
+            for (name, value) in chunk?.into_collections() {
+                let idx = match writers.iter().position(|(existing, _)| *existing == name) {
+                    Some(idx) => idx,
+                    None => {
+                        let path = self.to_dir.join(format!("{name}.parquet"));
+                        let writer =
+                            CollectionWriter::new(&path, &name, &namespace).with_context(|| {
+                                format!("Failed to write the collection `{name}` to Parquet")
+                            })?;
+                        writers.push((name.clone(), writer));
+                        writers.len() - 1
+                    }
+                };
+                writers[idx].1.write(&name, value).with_context(|| {
+                    format!("Failed to write the collection `{name}` to Parquet")
+                })?;
+            }
+        }
+
+        for (name, writer) in writers {
+            writer
+                .close()
+                .with_context(|| format!("Failed to write the collection `{name}` to Parquet"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the values of a collection to a Parquet file as they are generated.
+struct CollectionWriter {
+    is_array: bool,
+    is_object: bool,
+    writer: ArrowWriter<std::fs::File>,
+    decoder: Decoder,
+}
+
+impl CollectionWriter {
+    fn new(path: &Path, name: &str, namespace: &Namespace) -> Result<Self> {
+        let (row_content, is_array) = match namespace.get_collection(name)? {
+            Content::Array(array_content) => (array_content.content.as_ref(), true),
+            content => (content, false),
+        };
+
+        let (schema, is_object) = schema_from_content(name, row_content, namespace)?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let writer = ArrowWriter::try_new(
+            std::fs::File::create(path)?,
+            schema.clone(),
+            Some(properties),
+        )?;
+        let decoder = ReaderBuilder::new(schema)
+            .with_batch_size(BATCH_SIZE)
+            .build_decoder()?;
+
+        Ok(Self {
+            is_array,
+            is_object,
+            writer,
+            decoder,
+        })
+    }
+
+    fn write(&mut self, name: &str, value: Value) -> Result<()> {
+        let rows = match value {
+            Value::Array(elements) if self.is_array => elements,
+            value => vec![value],
+        };
+
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let row = synth_val_to_arrow_json(row);
+                if self.is_object {
+                    row
+                } else {
+                    serde_json::json!({ name: row })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for chunk in rows.chunks(BATCH_SIZE) {
+            self.decoder.serialize(chunk)?;
+            if let Some(batch) = self.decoder.flush()? {
+                self.writer.write(&batch)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn close(self) -> Result<()> {
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+/// Build the Arrow schema of a collection's rows. The columns of the schema are the fields of the
+/// rows if they are objects, otherwise the schema has a single column named after the collection.
+fn schema_from_content(
+    name: &str,
+    content: &Content,
+    namespace: &Namespace,
+) -> Result<(SchemaRef, bool)> {
+    let (data_type, nullable) = data_type_from_content(content, namespace)?;
+    Ok(match data_type {
+        DataType::Struct(fields) => (Arc::new(Schema::new(fields)), true),
+        data_type => {
+            let field = Field::new(name, data_type, nullable);
+            (Arc::new(Schema::new(vec![field])), false)
+        }
+    })
+}
+
+/// Map a [`Content`] node to the Arrow type of the values it generates, along with whether or not
+/// those values can be null.
+fn data_type_from_content(content: &Content, namespace: &Namespace) -> Result<(DataType, bool)> {
+    let data_type = match content {
+        Content::Null(_) | Content::Empty(_) => return Ok((DataType::Null, true)),
+        Content::Bool(_) => DataType::Boolean,
+        Content::Number(number_content) => match number_content {
+            NumberContent::U32(_) => DataType::UInt32,
+            NumberContent::U64(_) => DataType::UInt64,
+            NumberContent::I8(_) => DataType::Int8,
+            NumberContent::I16(_) => DataType::Int16,
+            NumberContent::I32(_) => DataType::Int32,
+            NumberContent::I64(_) => DataType::Int64,
+            NumberContent::F32(_) => DataType::Float32,
+            NumberContent::F64(_) => DataType::Float64,
+        },
+        Content::String(_) => DataType::Utf8,
+        Content::DateTime(date_time_content) => match date_time_content.type_ {
+            ChronoValueType::NaiveDate => DataType::Date32,
+            ChronoValueType::NaiveTime => DataType::Time64(TimeUnit::Microsecond),
+            ChronoValueType::NaiveDateTime => DataType::Timestamp(TimeUnit::Microsecond, None),
+            ChronoValueType::DateTime => {
+                DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+            }
+        },
+        Content::Series(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+        Content::Array(array_content) => {
+            let (inner, nullable) = data_type_from_content(&array_content.content, namespace)?;
+            DataType::new_list(inner, nullable)
+        }
+        Content::Object(object_content) => {
+            let fields = object_content
+                .iter()
+                .filter(|(_, field)| !matches!(field, Content::Hidden(_)))
+                .map(|(name, field)| {
+                    let (data_type, nullable) = data_type_from_content(field, namespace)
+                        .with_context(|| format!("in the field `{name}`"))?;
+                    Ok(Field::new(
+                        name,
+                        data_type,
+                        nullable || object_content.skip_when_null,
+                    ))
+                })
+                .collect::<Result<Fields>>()?;
+            DataType::Struct(fields)
+        }
+        Content::SameAs(same_as_content) => {
+            return data_type_from_content(namespace.get_s_node(&same_as_content.ref_)?, namespace)
+        }
+        Content::Unique(unique_content) => {
+            return data_type_from_content(&unique_content.content, namespace)
+        }
+        Content::Hidden(hidden_content) => {
+            return data_type_from_content(&hidden_content.content, namespace)
+        }
+        Content::OneOf(one_of_content) => {
+            return common_data_type(one_of_content.iter(), "a `one_of` with variants", namespace)
+        }
+        Content::Switch(switch_content) => {
+            return common_data_type(switch_content.iter(), "a `switch` with cases", namespace)
+        }
+        Content::Datasource(_) => {
+            return Err(anyhow!(
+                "the type of values from a `datasource` cannot be determined for a Parquet export"
+            ))
+        }
+        Content::Expression(_) => {
+            return Err(anyhow!(
+                "the type of values from an `expression` cannot be determined for a Parquet export"
+            ))
+        }
+    };
+    Ok((data_type, false))
+}
+
+/// The Arrow type shared by all the alternatives of a `one_of` or `switch`, where `null`
+/// alternatives only make the result nullable.
+fn common_data_type<'a, I>(
+    contents: I,
+    what: &str,
+    namespace: &Namespace,
+) -> Result<(DataType, bool)>
+where
+    I: IntoIterator<Item = &'a Content>,
+{
+    let mut nullable = false;
+    let mut data_type = None;
+    for content in contents {
+        let (content_type, content_nullable) = data_type_from_content(content, namespace)?;
+        nullable |= content_nullable;
+        match (&data_type, content_type) {
+            (_, DataType::Null) => {}
+            (None, content_type) => data_type = Some(content_type),
+            (Some(data_type), content_type) if *data_type == content_type => {}
+            (Some(data_type), content_type) => {
+                return Err(anyhow!(
+                    "{} of different types ({} and {}) cannot be exported to Parquet",
+                    what,
+                    data_type,
+                    content_type
+                ))
+            }
+        }
+    }
+    Ok((data_type.unwrap_or(DataType::Null), nullable))
+}
+
+/// Convert a generated [`Value`] to JSON the way `arrow_json` expects it for the types from
+/// [`data_type_from_content`]: date and times are numbers in the unit of their Arrow type.
+fn synth_val_to_arrow_json(val: Value) -> serde_json::Value {
+    match val {
+        Value::DateTime(ChronoValueAndFormat { value, .. }) => {
+            let as_number = match value {
+                ChronoValue::NaiveDate(date) => date
+                    .signed_duration_since(chrono::NaiveDate::default())
+                    .num_days(),
+                ChronoValue::NaiveTime(time) => time
+                    .signed_duration_since(chrono::NaiveTime::MIN)
+                    .num_microseconds()
+                    .unwrap(),
+                ChronoValue::NaiveDateTime(date_time) => date_time.and_utc().timestamp_micros(),
+                ChronoValue::DateTime(date_time) => date_time.timestamp_micros(),
+            };
+            serde_json::Value::from(as_number)
+        }
+        Value::Object(object) => serde_json::Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| (key, synth_val_to_arrow_json(value)))
+                .collect(),
+        ),
+        Value::Array(array) => {
+            serde_json::Value::Array(array.into_iter().map(synth_val_to_arrow_json).collect())
+        }
+        scalar => synth_val_to_json(scalar),
+    }
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S";
+const NAIVE_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%z";
+
+#[derive(Clone, Debug)]
+pub struct ParquetFileImportStrategy {
+    pub from_dir: PathBuf,
+}
+
+impl ImportStrategy for ParquetFileImportStrategy {
+    fn import(&self) -> Result<Namespace> {
+        let mut namespace = Namespace::new();
+
+        for entry in std::fs::read_dir(&self.from_dir)? {
+            let path = entry?.path();
+
+            if path.is_file() && path.extension().map_or(false, |ext| ext == "parquet") {
+                let name = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .ok_or_else(|| {
+                        anyhow!("Failed to interpret collection name when importing a Parquet namespace")
+                    })?
+                    .to_string();
+
+                let collection = import_parquet_collection(&path, &name)
+                    .with_context(|| format!("While importing the collection `{name}`"))?;
+
+                namespace.put_collection(name, collection)?;
+            }
+        }
+
+        Ok(namespace)
+    }
+}
+
+fn import_parquet_collection(path: &Path, name: &str) -> Result<Content> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(path)?)?;
+    let schema = builder.schema().clone();
+    let batches = builder.build()?.collect::<Result<Vec<_>, _>>()?;
+
+    // A file with a single column named after the collection holds scalar values, as written by
+    // `ParquetFileExportStrategy`
+    let (row_field, columns) = match schema.fields().as_ref() {
+        [field] if field.name() == name && !matches!(field.data_type(), DataType::Struct(_)) => {
+            let columns = batches
+                .iter()
+                .map(|batch| batch.column(0).clone())
+                .collect::<Vec<_>>();
+            (field.as_ref().clone(), columns)
+        }
+        fields => {
+            let columns = batches
+                .into_iter()
+                .map(|batch| Arc::new(StructArray::from(batch)) as ArrayRef)
+                .collect::<Vec<_>>();
+            let row_field = Field::new(name, DataType::Struct(fields.into()), false);
+            (row_field, columns)
+        }
+    };
+
+    let mut content = Content::Array(ArrayContent::from_content_default_length(
+        content_from_columns(&row_field, &columns)?,
+    ));
+
+    let values = columns
+        .iter()
+        .map(array_to_json)
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    OptionalMergeStrategy.try_merge(&mut content, &serde_json::Value::Array(values))?;
+
+    Ok(content)
+}
+
+/// Seed the [`Content`] of a field from its Parquet (logical) type. Ranges, categories and bounds
+/// are left empty for the values of the field to be merged in later.
+///
+/// `columns` are the arrays of the field in every batch of the file: a nullable field is only made
+/// nullable if it actually contains nulls.
+fn content_from_columns(field: &Field, columns: &[ArrayRef]) -> Result<Content> {
+    let date_time = |format: &str, type_| {
+        Content::DateTime(DateTimeContent {
+            format: format.to_string(),
+            type_,
+            begin: None,
+            end: None,
+        })
+    };
+
+    let content = match field.data_type() {
+        DataType::Null => Content::null(),
+        DataType::Boolean => Content::Bool(BoolContent::default()),
+        DataType::Int8 => Content::Number(NumberContent::I8(I8::Range(observed_range()))),
+        DataType::Int16 => Content::Number(NumberContent::I16(I16::Range(observed_range()))),
+        DataType::Int32 => Content::Number(NumberContent::I32(I32::Range(observed_range()))),
+        DataType::Int64 => Content::Number(NumberContent::I64(I64::Range(observed_range()))),
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 => {
+            Content::Number(NumberContent::U32(U32::Range(observed_range())))
+        }
+        DataType::UInt64 => Content::Number(NumberContent::U64(U64::Range(observed_range()))),
+        DataType::Float16 | DataType::Float32 => {
+            Content::Number(NumberContent::F32(F32::Range(observed_range())))
+        }
+        DataType::Float64 | DataType::Decimal128(_, _) => {
+            Content::Number(NumberContent::F64(F64::Range(observed_range())))
+        }
+        DataType::Utf8 | DataType::LargeUtf8 => {
+            Content::String(StringContent::Categorical(Categorical::default()))
+        }
+        DataType::Date32 | DataType::Date64 => date_time(DATE_FORMAT, ChronoValueType::NaiveDate),
+        DataType::Time32(_) | DataType::Time64(_) => {
+            date_time(TIME_FORMAT, ChronoValueType::NaiveTime)
+        }
+        DataType::Timestamp(_, None) => {
+            date_time(NAIVE_DATE_TIME_FORMAT, ChronoValueType::NaiveDateTime)
+        }
+        DataType::Timestamp(_, Some(_)) => date_time(DATE_TIME_FORMAT, ChronoValueType::DateTime),
+        DataType::List(item) | DataType::LargeList(item) | DataType::FixedSizeList(item, _) => {
+            let items = columns
+                .iter()
+                .map(|column| match column.data_type() {
+                    DataType::List(_) => column.as_list::<i32>().values().clone(),
+                    DataType::LargeList(_) => column.as_list::<i64>().values().clone(),
+                    _ => column.as_fixed_size_list().values().clone(),
+                })
+                .collect::<Vec<_>>();
+            Content::Array(ArrayContent::from_content_default_length(
+                content_from_columns(item, &items)?,
+            ))
+        }
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| {
+                    let children = columns
+                        .iter()
+                        .map(|column| column.as_struct().column(idx).clone())
+                        .collect::<Vec<_>>();
+                    Ok((
+                        field.name().clone(),
+                        content_from_columns(field, &children)?,
+                    ))
+                })
+                .collect::<Result<_>>()?;
+            Content::Object(ObjectContent {
+                fields,
+                ..Default::default()
+            })
+        }
+        unsupported => bail!(
+            "Parquet columns of type {} are not supported (at field `{}`)",
+            unsupported,
+            field.name()
+        ),
+    };
+
+    let has_nulls = columns.iter().any(|column| column.null_count() > 0);
+    if field.is_nullable() && has_nulls && !content.is_null() {
+        Ok(content.into_nullable())
+    } else {
+        Ok(content)
+    }
+}
+
+/// An empty range, including its high bound as it is set to the highest observed value.
+fn observed_range<N>() -> RangeStep<N> {
+    RangeStep {
+        include_high: true,
+        ..Default::default()
+    }
+}
+
+/// Convert every row of an Arrow array to JSON, the way [`OptionalMergeStrategy`] expects the
+/// values of the [`Content`] from [`content_from_columns`].
+fn array_to_json(array: &ArrayRef) -> Result<Vec<serde_json::Value>> {
+    fn primitives<T, F>(array: &ArrayRef, to_json: F) -> Vec<serde_json::Value>
+    where
+        T: ArrowPrimitiveType,
+        F: Fn(&PrimitiveArray<T>, usize) -> serde_json::Value,
+    {
+        let array = array.as_primitive::<T>();
+        (0..array.len())
+            .map(|idx| {
+                if array.is_null(idx) {
+                    serde_json::Value::Null
+                } else {
+                    to_json(array, idx)
+                }
+            })
+            .collect()
+    }
+
+    fn numbers<T>(array: &ArrayRef) -> Vec<serde_json::Value>
+    where
+        T: ArrowPrimitiveType,
+        serde_json::Value: From<T::Native>,
+    {
+        primitives::<T, _>(array, |array, idx| array.value(idx).into())
+    }
+
+    fn formatted<T: std::fmt::Display>(value: Option<T>) -> serde_json::Value {
+        value.map(|value| value.to_string()).into()
+    }
+
+    let values = match array.data_type() {
+        DataType::Null => vec![serde_json::Value::Null; array.len()],
+        DataType::Boolean => array
+            .as_boolean()
+            .iter()
+            .map(serde_json::Value::from)
+            .collect(),
+        DataType::Int8 => numbers::<Int8Type>(array),
+        DataType::Int16 => numbers::<Int16Type>(array),
+        DataType::Int32 => numbers::<Int32Type>(array),
+        DataType::Int64 => numbers::<Int64Type>(array),
+        DataType::UInt8 => numbers::<UInt8Type>(array),
+        DataType::UInt16 => numbers::<UInt16Type>(array),
+        DataType::UInt32 => numbers::<UInt32Type>(array),
+        DataType::UInt64 => numbers::<UInt64Type>(array),
+        DataType::Float16 => {
+            primitives::<Float16Type, _>(array, |array, idx| array.value(idx).to_f64().into())
+        }
+        DataType::Float32 => numbers::<Float32Type>(array),
+        DataType::Float64 => numbers::<Float64Type>(array),
+        DataType::Decimal128(_, scale) => {
+            let scale = 10f64.powi(*scale as i32);
+            primitives::<Decimal128Type, _>(array, |array, idx| {
+                (array.value(idx) as f64 / scale).into()
+            })
+        }
+        DataType::Utf8 => array
+            .as_string::<i32>()
+            .iter()
+            .map(serde_json::Value::from)
+            .collect(),
+        DataType::LargeUtf8 => array
+            .as_string::<i64>()
+            .iter()
+            .map(serde_json::Value::from)
+            .collect(),
+        DataType::Date32 => primitives::<Date32Type, _>(array, |array, idx| {
+            formatted(
+                array
+                    .value_as_date(idx)
+                    .map(|date| date.format(DATE_FORMAT)),
+            )
+        }),
+        DataType::Date64 => primitives::<Date64Type, _>(array, |array, idx| {
+            formatted(
+                array
+                    .value_as_date(idx)
+                    .map(|date| date.format(DATE_FORMAT)),
+            )
+        }),
+        DataType::Time32(TimeUnit::Second) => {
+            primitives::<Time32SecondType, _>(array, |array, idx| {
+                formatted(
+                    array
+                        .value_as_time(idx)
+                        .map(|time| time.format(TIME_FORMAT)),
+                )
+            })
+        }
+        DataType::Time32(_) => primitives::<Time32MillisecondType, _>(array, |array, idx| {
+            formatted(
+                array
+                    .value_as_time(idx)
+                    .map(|time| time.format(TIME_FORMAT)),
+            )
+        }),
+        DataType::Time64(TimeUnit::Microsecond) => {
+            primitives::<Time64MicrosecondType, _>(array, |array, idx| {
+                formatted(
+                    array
+                        .value_as_time(idx)
+                        .map(|time| time.format(TIME_FORMAT)),
+                )
+            })
+        }
+        DataType::Time64(_) => primitives::<Time64NanosecondType, _>(array, |array, idx| {
+            formatted(
+                array
+                    .value_as_time(idx)
+                    .map(|time| time.format(TIME_FORMAT)),
+            )
+        }),
+        DataType::Timestamp(unit, tz) => {
+            let format = if tz.is_some() {
+                DATE_TIME_FORMAT
+            } else {
+                NAIVE_DATE_TIME_FORMAT
+            };
+            // Timestamps are stored as UTC, the `%z` of `DATE_TIME_FORMAT` then formats to `+0000`
+            macro_rules! timestamps {
+                ($ty:ty) => {
+                    primitives::<$ty, _>(array, |array, idx| {
+                        formatted(
+                            array
+                                .value_as_datetime(idx)
+                                .map(|date_time| date_time.and_utc().format(format)),
+                        )
+                    })
+                };
+            }
+            match unit {
+                TimeUnit::Second => timestamps!(TimestampSecondType),
+                TimeUnit::Millisecond => timestamps!(TimestampMillisecondType),
+                TimeUnit::Microsecond => timestamps!(TimestampMicrosecondType),
+                TimeUnit::Nanosecond => timestamps!(TimestampNanosecondType),
+            }
+        }
+        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _) => {
+            let lists: Vec<Option<ArrayRef>> = match array.data_type() {
+                DataType::List(_) => array.as_list::<i32>().iter().collect(),
+                DataType::LargeList(_) => array.as_list::<i64>().iter().collect(),
+                _ => array.as_fixed_size_list().iter().collect(),
+            };
+            lists
+                .into_iter()
+                .map(|list| match list {
+                    Some(list) => array_to_json(&list).map(serde_json::Value::Array),
+                    None => Ok(serde_json::Value::Null),
+                })
+                .collect::<Result<_>>()?
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let mut columns = array
+                .columns()
+                .iter()
+                .map(|column| array_to_json(column).map(Vec::into_iter))
+                .collect::<Result<Vec<_>>>()?;
+            (0..array.len())
+                .map(|idx| {
+                    let object = fields
+                        .iter()
+                        .zip(columns.iter_mut())
+                        .map(|(field, column)| (field.name().clone(), column.next().unwrap()))
+                        .collect();
+                    if array.is_null(idx) {
+                        serde_json::Value::Null
+                    } else {
+                        serde_json::Value::Object(object)
+                    }
+                })
+                .collect()
+        }
+        unsupported => bail!("Parquet columns of type {} are not supported", unsupported),
+    };
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampler::Sampler;
+
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Date32Type, UInt64Type};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn namespace() -> Namespace {
+        serde_json::from_value(serde_json::json!({
+            "users": {
+                "type": "array",
+                "length": 5,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "id": {}
+                    },
+                    "name": {
+                        "type": "one_of",
+                        "variants": [
+                            { "type": "null" },
+                            { "type": "string", "pattern": "[a-z]{4}" }
+                        ]
+                    },
+                    "joined": {
+                        "type": "date_time",
+                        "format": "%Y-%m-%d",
+                        "subtype": "naive_date",
+                        "begin": "2020-01-01",
+                        "end": "2020-01-01"
+                    },
+                    "tags": {
+                        "type": "array",
+                        "length": 2,
+                        "content": { "type": "string", "pattern": "tag" }
+                    }
+                }
+            },
+            "scores": {
+                "type": "array",
+                "length": 3,
+                "content": {
+                    "type": "number",
+                    "subtype": "f32",
+                    "constant": 1.5
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_schema_from_content() {
+        let namespace = namespace();
+
+        let (schema, is_object) = schema_from_content(
+            "users",
+            namespace
+                .get_s_node(&"users.content".parse().unwrap())
+                .unwrap(),
+            &namespace,
+        )
+        .unwrap();
+        assert!(is_object);
+        assert_eq!(
+            *schema,
+            Schema::new(vec![
+                Field::new("id", DataType::UInt64, false),
+                Field::new("joined", DataType::Date32, false),
+                Field::new("name", DataType::Utf8, true),
+                Field::new("tags", DataType::new_list(DataType::Utf8, false), false),
+            ])
+        );
+
+        let (schema, is_object) = schema_from_content(
+            "scores",
+            namespace
+                .get_s_node(&"scores.content".parse().unwrap())
+                .unwrap(),
+            &namespace,
+        )
+        .unwrap();
+        assert!(!is_object);
+        assert_eq!(
+            *schema,
+            Schema::new(vec![Field::new("scores", DataType::Float32, false)])
+        );
+    }
+
+    #[test]
+    fn test_export() {
+        let namespace = namespace();
+        let dir = tempfile::tempdir().unwrap();
+        let to_dir = dir.path().join("out");
+
+        let sample = Sampler::try_from(&namespace)
+            .unwrap()
+            .sample_seeded(None, 5, 0)
+            .unwrap();
+        ParquetFileExportStrategy {
+            to_dir: to_dir.clone(),
+        }
+        .export(namespace, sample)
+        .unwrap();
+
+        let file = std::fs::File::open(to_dir.join("users.parquet")).unwrap();
+        let batches = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 5);
+        let ids = batch
+            .column_by_name("id")
+            .unwrap()
+            .as_primitive::<UInt64Type>();
+        assert_eq!(ids.values().to_vec(), vec![1, 2, 3, 4, 5]);
+        let joined = batch
+            .column_by_name("joined")
+            .unwrap()
+            .as_primitive::<Date32Type>();
+        assert!(joined.values().iter().all(|days| *days == 18262));
+
+        assert!(to_dir.join("scores.parquet").exists());
+    }
+
+    #[test]
+    fn test_import() {
+        let namespace = namespace();
+        let dir = tempfile::tempdir().unwrap();
+        let to_dir = dir.path().join("out");
+
+        let sample = Sampler::try_from(&namespace)
+            .unwrap()
+            .sample_seeded(None, 5, 0)
+            .unwrap();
+        ParquetFileExportStrategy {
+            to_dir: to_dir.clone(),
+        }
+        .export(namespace, sample)
+        .unwrap();
+
+        let imported = ParquetFileImportStrategy { from_dir: to_dir }
+            .import()
+            .unwrap();
+
+        let users = serde_json::to_value(imported.get_collection("users").unwrap()).unwrap();
+        let user = &users["content"];
+        assert_eq!(user["id"]["subtype"], "u64");
+        assert_eq!(user["id"]["range"]["low"], 1);
+        assert_eq!(user["id"]["range"]["high"], 5);
+        assert_eq!(user["id"]["range"]["include_high"], true);
+        assert_eq!(user["joined"]["type"], "date_time");
+        assert_eq!(user["joined"]["subtype"], "naive_date");
+        assert_eq!(user["joined"]["begin"], "2020-01-01");
+        assert_eq!(user["joined"]["end"], "2020-01-01");
+        assert_eq!(user["name"]["type"], "one_of");
+        assert_eq!(user["tags"]["type"], "array");
+        assert_eq!(user["tags"]["content"]["type"], "string");
+        assert_eq!(
+            user["tags"]["content"]["categorical"],
+            serde_json::json!({ "tag": 10 })
+        );
+
+        let scores = serde_json::to_value(imported.get_collection("scores").unwrap()).unwrap();
+        assert_eq!(scores["content"]["subtype"], "f32");
+        assert_eq!(scores["content"]["range"]["low"], 1.5);
+
+        // The imported namespace can be generated from
+        Sampler::try_from(&imported)
+            .unwrap()
+            .sample_seeded(None, 5, 0)
+            .unwrap();
+    }
+}
@@ -1,20 +1,27 @@
+use crate::cli::ddl::create_tables;
 use crate::cli::export::{create_and_insert_values, ExportStrategy};
 use crate::cli::import::ImportStrategy;
 use crate::cli::import_utils::build_namespace_import;
+use crate::cli::sql::SqlDialect;
 use crate::datasource::postgres_datasource::{PostgresConnectParams, PostgresDataSource};
-use crate::datasource::DataSource;
-use crate::sampler::SamplerOutput;
+use crate::datasource::relational_datasource::{execute_statements, TransactionMode};
+use crate::datasource::{DataSource, WriteMode};
+use crate::sampler::Sample;
 use anyhow::Result;
+use async_std::task;
 use synth_core::schema::Namespace;
 
 #[derive(Clone, Debug)]
 pub struct PostgresExportStrategy {
     pub uri_string: String,
     pub schema: Option<String>,
+    pub create_tables: bool,
+    pub transaction: TransactionMode,
+    pub write_mode: WriteMode,
 }
 
 impl ExportStrategy for PostgresExportStrategy {
-    fn export(&self, _namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
         let connect_params = PostgresConnectParams {
             uri: self.uri_string.clone(),
             schema: self.schema.clone(),
@@ -22,7 +29,18 @@ impl ExportStrategy for PostgresExportStrategy {
 
         let datasource = PostgresDataSource::new(&connect_params)?;
 
-        create_and_insert_values(sample, &datasource)
+        if self.create_tables {
+            let statements = create_tables(SqlDialect::Postgres, &namespace)?;
+            task::block_on(execute_statements(&datasource, &statements))?;
+        }
+
+        create_and_insert_values(
+            &namespace,
+            sample,
+            &datasource,
+            self.transaction,
+            self.write_mode,
+        )
     }
 }
 
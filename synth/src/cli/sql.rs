@@ -0,0 +1,387 @@
+use crate::cli::ddl::create_tables;
+use crate::cli::export::ExportStrategy;
+use crate::datasource::mysql_datasource::MySqlDataSource;
+use crate::datasource::postgres_datasource::{
+    copy_field, date_time_text, number_text, postgres_cast, PostgresDataSource,
+};
+use crate::datasource::relational_datasource::{SqlxDataSource, DEFAULT_INSERT_BATCH_SIZE};
+use crate::datasource::sqlite_datasource::SqliteDataSource;
+use crate::sampler::Sample;
+
+use anyhow::Result;
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::path::PathBuf;
+
+use synth_core::graph::json::synth_val_to_json;
+use synth_core::schema::ChronoValue;
+use synth_core::{Namespace, Value};
+
+/// The database a SQL dump file is written for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl std::fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Postgres => write!(f, "Postgres"),
+            Self::MySql => write!(f, "MySQL"),
+            Self::Sqlite => write!(f, "SQLite"),
+        }
+    }
+}
+
+/// The statements used to write rows to a SQL dump file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlFormat {
+    /// Multi-row `INSERT` statements.
+    Insert,
+    /// `COPY ... FROM stdin` blocks, as written by `pg_dump` (Postgres only).
+    Copy,
+}
+
+/// Writes generated data as a SQL script which can be loaded without synth, e.g. with `psql` or
+/// `mysql`.
+#[derive(Clone, Debug)]
+pub struct SqlDump {
+    pub dialect: SqlDialect,
+    pub format: SqlFormat,
+    /// (Postgres only) The schema into which the script inserts.
+    pub schema: Option<String>,
+    /// Whether the script starts by creating the tables of the namespace.
+    pub create_tables: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct SqlFileExportStrategy {
+    pub to_file: PathBuf,
+    pub dump: SqlDump,
+}
+
+impl ExportStrategy for SqlFileExportStrategy {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
+        let mut f = std::io::BufWriter::new(std::fs::File::create(&self.to_file)?);
+        self.dump.write(&namespace, sample, &mut f)?;
+        f.flush()?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SqlStdoutExportStrategy<W> {
+    pub dump: SqlDump,
+    pub writer: RefCell<W>,
+}
+
+impl<W: Write> ExportStrategy for SqlStdoutExportStrategy<W> {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
+        self.dump
+            .write(&namespace, sample, &mut *self.writer.borrow_mut())
+    }
+}
+
+impl SqlDump {
+    pub fn new(
+        dialect: SqlDialect,
+        format: SqlFormat,
+        schema: Option<String>,
+        create_tables: bool,
+    ) -> Result<Self> {
+        if format == SqlFormat::Copy && dialect != SqlDialect::Postgres {
+            return Err(anyhow!(
+                "The 'copy' format is only supported for Postgres. Use the 'insert' format instead."
+            ));
+        }
+        Ok(Self {
+            dialect,
+            format,
+            schema,
+            create_tables,
+        })
+    }
+
+    fn write<W: Write>(&self, namespace: &Namespace, sample: Sample, writer: &mut W) -> Result<()> {
+        if let (SqlDialect::Postgres, Some(schema)) = (self.dialect, &self.schema) {
+            writeln!(
+                writer,
+                "SET search_path = {};",
+                self.dialect.quote_string(schema)
+            )?;
+        }
+
+        if self.create_tables {
+            for statement in create_tables(self.dialect, namespace)? {
+                writeln!(writer, "{statement}")?;
+            }
+            writeln!(writer)?;
+        }
+
+        for chunk in sample {
+            for (name, value) in chunk?.into_collections() {
+                let rows = match value {
+                    Value::Array(elements) => elements,
+                    non_array => vec![non_array],
+                };
+                self.write_rows(&name, &rows, writer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_rows<W: Write>(
+        &self,
+        collection_name: &str,
+        rows: &[Value],
+        writer: &mut W,
+    ) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        // Fields can be missing from some of the rows (e.g. with `skip_when_null`)
+        let mut columns = BTreeSet::new();
+        for row in rows {
+            columns.extend(as_row(collection_name, row)?.keys().cloned());
+        }
+        let columns = columns.into_iter().collect::<Vec<_>>();
+
+        let table_name = self.dialect.quote_identifier(collection_name);
+        let column_names = columns
+            .iter()
+            .map(|column| self.dialect.quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        match self.format {
+            SqlFormat::Insert => {
+                for batch in rows.chunks(DEFAULT_INSERT_BATCH_SIZE) {
+                    writeln!(writer, "INSERT INTO {table_name} ({column_names}) VALUES")?;
+                    for (i, row) in batch.iter().enumerate() {
+                        let literals = row_fields(collection_name, &columns, row)?
+                            .into_iter()
+                            .map(|value| self.dialect.literal(value))
+                            .collect::<Result<Vec<_>>>()?
+                            .join(",");
+                        let end = if i == batch.len() - 1 { ";" } else { "," };
+                        writeln!(writer, "({literals}){end}")?;
+                    }
+                }
+            }
+            SqlFormat::Copy => {
+                writeln!(writer, "COPY {table_name} ({column_names}) FROM stdin;")?;
+                for row in rows {
+                    let fields = row_fields(collection_name, &columns, row)?
+                        .into_iter()
+                        .map(copy_field)
+                        .collect::<Vec<_>>()
+                        .join("\t");
+                    writeln!(writer, "{fields}")?;
+                }
+                writeln!(writer, "\\.")?;
+            }
+        }
+        writeln!(writer)?;
+
+        Ok(())
+    }
+}
+
+/// The values of `columns` in `row`, in order.
+fn row_fields<'r>(
+    collection_name: &str,
+    columns: &[String],
+    row: &'r Value,
+) -> Result<Vec<&'r Value>> {
+    const NULL: &Value = &Value::Null(());
+
+    let row = as_row(collection_name, row)?;
+    Ok(columns
+        .iter()
+        .map(|column| row.get(column).unwrap_or(NULL))
+        .collect())
+}
+
+fn as_row<'r>(collection_name: &str, row: &'r Value) -> Result<&'r BTreeMap<String, Value>> {
+    row.as_object().ok_or_else(|| {
+        anyhow!(
+            "Cannot write collection '{}' as SQL: was expecting its elements to be objects. Instead found {}",
+            collection_name,
+            row.type_()
+        )
+    })
+}
+
+impl SqlDialect {
+    pub(crate) fn quote_identifier(self, identifier: &str) -> String {
+        let quote = match self {
+            Self::Postgres => PostgresDataSource::IDENTIFIER_QUOTE,
+            Self::MySql => MySqlDataSource::IDENTIFIER_QUOTE,
+            Self::Sqlite => SqliteDataSource::IDENTIFIER_QUOTE,
+        };
+        let escaped = identifier.replace(quote, &quote.to_string().repeat(2));
+        format!("{quote}{escaped}{quote}")
+    }
+
+    fn quote_string(self, s: &str) -> String {
+        let escaped = match self {
+            Self::Postgres | Self::Sqlite => s.replace('\'', "''"),
+            // MySQL treats backslashes in strings as escape characters by default
+            Self::MySql => s.replace('\\', "\\\\").replace('\'', "''"),
+        };
+        format!("'{escaped}'")
+    }
+
+    /// Render `value` as a literal which the database converts to the same value as the one bound
+    /// to queries when inserting into a live database.
+    fn literal(self, value: &Value) -> Result<String> {
+        let literal = match value {
+            Value::Null(_) => "NULL".to_string(),
+            Value::Bool(true) => "TRUE".to_string(),
+            Value::Bool(false) => "FALSE".to_string(),
+            Value::Number(num) => match number_text(num) {
+                Ok(text) => text,
+                Err(text) if self == Self::Postgres => self.quote_string(&text),
+                Err(text) => {
+                    return Err(anyhow!("{} does not support {} numbers", self, text));
+                }
+            },
+            Value::String(s) => self.quote_string(s),
+            Value::DateTime(date_time) => self.quote_string(&self.date_time_text(&date_time.value)),
+            Value::Object(_) => self.quote_string(&synth_val_to_json(value.clone()).to_string()),
+            Value::Array(_) => match self {
+                Self::Postgres => format!(
+                    "{}{}",
+                    self.quote_string(&value.to_postgres_string()),
+                    postgres_cast(value)
+                ),
+                Self::MySql | Self::Sqlite => {
+                    self.quote_string(&synth_val_to_json(value.clone()).to_string())
+                }
+            },
+        };
+        Ok(literal)
+    }
+
+    fn date_time_text(self, value: &ChronoValue) -> String {
+        match (self, value) {
+            // MySQL has no time zones in its date times, they are inserted as UTC
+            (Self::MySql, ChronoValue::DateTime(date_time)) => date_time
+                .naive_utc()
+                .format("%Y-%m-%d %H:%M:%S%.f")
+                .to_string(),
+            (Self::Sqlite, ChronoValue::DateTime(date_time)) => date_time.to_rfc3339(),
+            _ => date_time_text(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sampler::SamplerOutput;
+
+    fn value(json: serde_json::Value) -> Value {
+        match json {
+            serde_json::Value::Null => Value::Null(()),
+            serde_json::Value::Array(elements) => {
+                Value::Array(elements.into_iter().map(value).collect())
+            }
+            serde_json::Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, field)| (name, value(field)))
+                    .collect(),
+            ),
+            other => serde_json::from_value(other).unwrap(),
+        }
+    }
+
+    fn dump(dialect: SqlDialect, format: SqlFormat, rows: serde_json::Value) -> String {
+        let rows = value(rows);
+        let sample = Sample::new(std::iter::once(Ok(SamplerOutput::Namespace(vec![(
+            "users".to_string(),
+            rows,
+        )]))));
+
+        let mut output = Vec::new();
+        SqlDump::new(dialect, format, None, false)
+            .unwrap()
+            .write(&Namespace::new(), sample, &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn sql_insert() {
+        let rows = serde_json::json!([
+            { "id": 1, "name": "O'Brien", "active": true, "score": 1.5 },
+            { "id": 2, "name": "back\\slash", "active": false, "score": null }
+        ]);
+
+        assert_eq!(
+            dump(SqlDialect::Postgres, SqlFormat::Insert, rows.clone()),
+            "INSERT INTO \"users\" (\"active\",\"id\",\"name\",\"score\") VALUES\n\
+            (TRUE,1,'O''Brien',1.5),\n\
+            (FALSE,2,'back\\slash',NULL);\n\n"
+        );
+        assert_eq!(
+            dump(SqlDialect::MySql, SqlFormat::Insert, rows),
+            "INSERT INTO `users` (`active`,`id`,`name`,`score`) VALUES\n\
+            (TRUE,1,'O''Brien',1.5),\n\
+            (FALSE,2,'back\\\\slash',NULL);\n\n"
+        );
+    }
+
+    #[test]
+    fn sql_copy() {
+        let rows = serde_json::json!([
+            { "id": 1, "bio": "line\nbreak\ttab", "tags": ["a", "b"] },
+            { "id": 2, "bio": null, "tags": [] }
+        ]);
+
+        assert_eq!(
+            dump(SqlDialect::Postgres, SqlFormat::Copy, rows),
+            "COPY \"users\" (\"bio\",\"id\",\"tags\") FROM stdin;\n\
+            line\\nbreak\\ttab\t1\t{\"a\", \"b\"}\n\
+            \\N\t2\t{}\n\
+            \\.\n\n"
+        );
+        assert!(SqlDump::new(SqlDialect::MySql, SqlFormat::Copy, None, false).is_err());
+    }
+
+    #[test]
+    fn sql_missing_fields() {
+        let rows = serde_json::json!([
+            { "id": 1 },
+            { "id": 2, "email": "a@b.c" }
+        ]);
+
+        assert_eq!(
+            dump(SqlDialect::Sqlite, SqlFormat::Insert, rows.clone()),
+            "INSERT INTO \"users\" (\"email\",\"id\") VALUES\n\
+            (NULL,1),\n\
+            ('a@b.c',2);\n\n"
+        );
+        assert_eq!(
+            dump(SqlDialect::Postgres, SqlFormat::Copy, rows),
+            "COPY \"users\" (\"email\",\"id\") FROM stdin;\n\
+            \\N\t1\n\
+            a@b.c\t2\n\
+            \\.\n\n"
+        );
+    }
+
+    #[test]
+    fn sql_array_cast() {
+        let rows = serde_json::json!([{ "ids": [1, 2] }]);
+        let output = dump(SqlDialect::Postgres, SqlFormat::Insert, rows);
+        assert!(output.contains("('{1, 2}'::int8[]);"), "{}", output);
+    }
+}
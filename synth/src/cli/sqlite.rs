@@ -0,0 +1,517 @@
+use crate::cli::ddl::create_tables;
+use crate::cli::export::{create_and_insert_values, ExportStrategy};
+use crate::cli::import::ImportStrategy;
+use crate::cli::import_utils::build_namespace_import;
+use crate::cli::sql::SqlDialect;
+use crate::datasource::relational_datasource::{execute_statements, TransactionMode};
+use crate::datasource::sqlite_datasource::SqliteDataSource;
+use crate::datasource::{DataSource, WriteMode};
+use crate::sampler::Sample;
+use anyhow::Result;
+use async_std::task;
+use synth_core::schema::Namespace;
+
+#[derive(Clone, Debug)]
+pub struct SqliteExportStrategy {
+    pub uri_string: String,
+    pub create_tables: bool,
+    pub transaction: TransactionMode,
+    pub write_mode: WriteMode,
+}
+
+impl ExportStrategy for SqliteExportStrategy {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
+        let datasource = SqliteDataSource::new(&self.uri_string)?;
+
+        if self.create_tables {
+            let statements = create_tables(SqlDialect::Sqlite, &namespace)?;
+            task::block_on(execute_statements(&datasource, &statements))?;
+        }
+
+        create_and_insert_values(
+            &namespace,
+            sample,
+            &datasource,
+            self.transaction,
+            self.write_mode,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SqliteImportStrategy {
+    pub uri_string: String,
+}
+
+impl ImportStrategy for SqliteImportStrategy {
+    fn import(&self) -> Result<Namespace> {
+        let datasource = SqliteDataSource::new(&self.uri_string)?;
+
+        build_namespace_import(&datasource)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::relational_datasource::SqlxDataSource;
+    use crate::sampler::Sampler;
+    use async_std::task;
+    use sqlx::sqlite::SqliteRow;
+    use sqlx::FromRow;
+    use synth_core::Content;
+    use tempfile::TempDir;
+
+    /// A new database in a temporary directory (deleted when dropped), and its URI.
+    fn database() -> (TempDir, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let uri_string = format!("sqlite:{}?mode=rwc", dir.path().join("test.db").display());
+        (dir, uri_string)
+    }
+
+    fn create(uri_string: &str, ddl: &str) {
+        let datasource = SqliteDataSource::new(&uri_string.to_string()).unwrap();
+        task::block_on(sqlx::raw_sql(ddl).execute(&datasource.get_pool())).unwrap();
+    }
+
+    fn sample(namespace: &Namespace, target: usize, seed: u64) -> Sample<'static> {
+        Sampler::try_from(namespace)
+            .unwrap()
+            .sample_seeded(None, target, seed)
+            .unwrap()
+    }
+
+    fn export_to(
+        uri_string: &str,
+        namespace: &Namespace,
+        sample: Sample,
+        transaction: TransactionMode,
+        write_mode: WriteMode,
+    ) -> Result<()> {
+        SqliteExportStrategy {
+            uri_string: uri_string.to_string(),
+            create_tables: false,
+            transaction,
+            write_mode,
+        }
+        .export(namespace.clone(), sample)
+    }
+
+    fn fetch_one<T>(uri_string: &str, query: &str) -> T
+    where
+        T: for<'r> FromRow<'r, SqliteRow> + Send + Unpin,
+    {
+        let datasource = SqliteDataSource::new(&uri_string.to_string()).unwrap();
+        task::block_on(sqlx::query_as(query).fetch_one(&datasource.get_pool())).unwrap()
+    }
+
+    #[test]
+    fn sqlite_import_and_export() {
+        let (_dir, uri_string) = database();
+        create(
+            &uri_string,
+            r"CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR(16) NOT NULL, joined DATETIME);
+            CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users, score REAL);",
+        );
+
+        let namespace = SqliteImportStrategy {
+            uri_string: uri_string.clone(),
+        }
+        .import()
+        .unwrap();
+
+        assert!(matches!(
+            namespace.get_s_node(&"posts.content.user_id".parse().unwrap()),
+            Ok(Content::SameAs(_))
+        ));
+
+        let sample = sample(&namespace, 2000, 0);
+        export_to(
+            &uri_string,
+            &namespace,
+            sample,
+            TransactionMode::default(),
+            WriteMode::default(),
+        )
+        .unwrap();
+
+        let (users, orphans): (i64, i64) = fetch_one(
+            &uri_string,
+            r"SELECT (SELECT COUNT(*) FROM users),
+            (SELECT COUNT(*) FROM posts WHERE user_id NOT IN (SELECT id FROM users))",
+        );
+        assert!(users > 0);
+        assert_eq!(orphans, 0);
+    }
+
+    #[test]
+    fn sqlite_import_unique_keys() {
+        let (_dir, uri_string) = database();
+        create(
+            &uri_string,
+            r"CREATE TABLE users (id INTEGER PRIMARY KEY, email VARCHAR(8) UNIQUE, tenant INTEGER NOT NULL, nick VARCHAR(8), code INTEGER);
+            CREATE UNIQUE INDEX users_nick ON users (tenant, nick);
+            CREATE UNIQUE INDEX users_code ON users (code) WHERE code > 0;
+            CREATE TABLE follows (follower INTEGER REFERENCES users, followee INTEGER REFERENCES users, PRIMARY KEY (follower, followee));",
+        );
+
+        let namespace = SqliteImportStrategy {
+            uri_string: uri_string.clone(),
+        }
+        .import()
+        .unwrap();
+
+        let users = namespace.get_s_node(&"users.content".parse().unwrap());
+        let Ok(Content::Object(users)) = users else {
+            panic!("users are not objects: {users:?}")
+        };
+        assert_eq!(
+            users.unique_together,
+            vec![vec!["nick".to_string(), "tenant".to_string()]]
+        );
+        assert!(users
+            .get("email")
+            .unwrap()
+            .as_nullable()
+            .unwrap()
+            .is_unique());
+        assert!(!users
+            .get("code")
+            .unwrap()
+            .as_nullable()
+            .unwrap()
+            .is_unique());
+
+        let follows = namespace.get_s_node(&"follows.content".parse().unwrap());
+        let Ok(Content::Object(follows)) = follows else {
+            panic!("follows are not objects: {follows:?}")
+        };
+        assert_eq!(
+            follows.unique_together,
+            vec![vec!["followee".to_string(), "follower".to_string()]]
+        );
+
+        let sizes = [("users".to_string(), 300), ("follows".to_string(), 300)].into();
+        let sample = Sampler::try_from(&namespace)
+            .unwrap()
+            .with_collection_sizes(&sizes)
+            .unwrap()
+            .sample_seeded(None, 1, 0)
+            .unwrap();
+        export_to(
+            &uri_string,
+            &namespace,
+            sample,
+            TransactionMode::default(),
+            WriteMode::default(),
+        )
+        .unwrap();
+
+        let (users, follows): (i64, i64) = fetch_one(
+            &uri_string,
+            r"SELECT (SELECT COUNT(*) FROM users), (SELECT COUNT(*) FROM follows)",
+        );
+        assert_eq!(users, 300);
+        assert_eq!(follows, 300);
+    }
+
+    #[test]
+    fn sqlite_create_tables() {
+        let (_dir, uri_string) = database();
+
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "users": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "u64", "id": {} },
+                    "name": { "type": "string", "pattern": "[a-z]{8}" }
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 20,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "u64", "id": {} },
+                    "user_id": { "type": "same_as", "ref": "users.content.id" }
+                }
+            }
+        }))
+        .unwrap();
+
+        let sample = sample(&namespace, 30, 0);
+        SqliteExportStrategy {
+            uri_string: uri_string.clone(),
+            create_tables: true,
+            transaction: TransactionMode::default(),
+            write_mode: WriteMode::default(),
+        }
+        .export(namespace, sample)
+        .unwrap();
+
+        let (posts,): (i64,) = fetch_one(
+            &uri_string,
+            "SELECT COUNT(*) FROM posts JOIN users ON posts.user_id = users.id",
+        );
+        assert!(posts > 0);
+    }
+
+    #[test]
+    fn sqlite_self_referencing_foreign_key() {
+        let (_dir, uri_string) = database();
+        create(
+            &uri_string,
+            "CREATE TABLE employees (id INTEGER PRIMARY KEY, manager INTEGER REFERENCES employees)",
+        );
+
+        // Managers are inserted after the employees they manage, in another batch
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "employees": {
+                "type": "array",
+                "length": 1500,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} },
+                    "manager": { "type": "number", "subtype": "i64", "range": { "low": 1001, "high": 1500, "step": 1 } }
+                }
+            }
+        }))
+        .unwrap();
+
+        export_to(
+            &uri_string,
+            &namespace,
+            sample(&namespace, 1500, 0),
+            TransactionMode::default(),
+            WriteMode::default(),
+        )
+        .unwrap();
+
+        let (employees, managed): (i64, i64) = fetch_one(
+            &uri_string,
+            "SELECT COUNT(*), COUNT(manager) FROM employees",
+        );
+        assert_eq!(employees, 1500);
+        assert_eq!(managed, 1500);
+    }
+
+    #[test]
+    fn sqlite_cyclic_foreign_keys() {
+        let (_dir, uri_string) = database();
+        create(
+            &uri_string,
+            r"CREATE TABLE departments (id INTEGER PRIMARY KEY, head INTEGER REFERENCES employees);
+            CREATE TABLE employees (id INTEGER PRIMARY KEY, department INTEGER NOT NULL REFERENCES departments);",
+        );
+
+        // Departments are inserted first since employees reference them, but their heads are
+        // employees which are only inserted afterwards
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "departments": {
+                "type": "array",
+                "length": 1500,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} },
+                    "head": { "type": "number", "subtype": "i64", "range": { "low": 1, "high": 1500, "step": 1 } }
+                }
+            },
+            "employees": {
+                "type": "array",
+                "length": 1500,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} },
+                    "department": "@departments.content.id"
+                }
+            }
+        }))
+        .unwrap();
+
+        export_to(
+            &uri_string,
+            &namespace,
+            sample(&namespace, 3000, 0),
+            TransactionMode::Chunk,
+            WriteMode::default(),
+        )
+        .unwrap();
+
+        let (departments, headed): (i64, i64) =
+            fetch_one(&uri_string, "SELECT COUNT(*), COUNT(head) FROM departments");
+        assert_eq!(departments, 1500);
+        assert_eq!(headed, 1500);
+
+        let (dangling,): (i64,) = fetch_one(
+            &uri_string,
+            "SELECT COUNT(*) FROM departments WHERE head NOT IN (SELECT id FROM employees)",
+        );
+        assert_eq!(dangling, 0);
+    }
+
+    #[test]
+    fn sqlite_rollback_on_failure() {
+        // The posts violate the NOT NULL constraint once the users have been inserted
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "users": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} }
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} },
+                    "user_id": { "type": "same_as", "ref": "users.content.id" },
+                    "body": { "type": "null" }
+                }
+            }
+        }))
+        .unwrap();
+
+        // The users and posts are in the same chunk, so they are rolled back together
+        for transaction in [TransactionMode::Single, TransactionMode::Chunk] {
+            let (_dir, uri_string) = database();
+            create(
+                &uri_string,
+                r"CREATE TABLE users (id INTEGER PRIMARY KEY);
+                CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users, body TEXT NOT NULL);",
+            );
+
+            let result = export_to(
+                &uri_string,
+                &namespace,
+                sample(&namespace, 20, 0),
+                transaction,
+                WriteMode::default(),
+            );
+            assert!(result.is_err());
+
+            let (users, posts): (i64, i64) = fetch_one(
+                &uri_string,
+                "SELECT (SELECT COUNT(*) FROM users), (SELECT COUNT(*) FROM posts)",
+            );
+            assert_eq!(users, 0, "{:?}", transaction);
+            assert_eq!(posts, 0, "{:?}", transaction);
+        }
+    }
+
+    #[test]
+    fn sqlite_rollback_across_chunks() {
+        // The items of the second chunk (of 10000 values) violate the CHECK constraint
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "items": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} }
+                }
+            }
+        }))
+        .unwrap();
+
+        for (transaction, expected_items) in [
+            (TransactionMode::Single, 0),
+            (TransactionMode::Chunk, 10000),
+        ] {
+            let (_dir, uri_string) = database();
+            create(
+                &uri_string,
+                "CREATE TABLE items (id INTEGER PRIMARY KEY CHECK (id <= 15000))",
+            );
+
+            let result = export_to(
+                &uri_string,
+                &namespace,
+                sample(&namespace, 25000, 0),
+                transaction,
+                WriteMode::default(),
+            );
+            let err = result.unwrap_err().to_string();
+            assert!(err.starts_with("Rolled back"), "{err}");
+
+            let (items,): (i64,) = fetch_one(&uri_string, "SELECT COUNT(*) FROM items");
+            assert_eq!(items, expected_items, "{:?}", transaction);
+        }
+    }
+
+    #[test]
+    fn sqlite_write_modes() {
+        let (_dir, uri_string) = database();
+        create(
+            &uri_string,
+            r"CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+            CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users);",
+        );
+
+        let namespace: Namespace = serde_json::from_value(serde_json::json!({
+            "users": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} },
+                    "name": { "type": "string", "pattern": "[a-z]{8}" }
+                }
+            },
+            "posts": {
+                "type": "array",
+                "length": 10,
+                "content": {
+                    "type": "object",
+                    "id": { "type": "number", "subtype": "i64", "id": {} },
+                    "user_id": { "type": "same_as", "ref": "users.content.id" }
+                }
+            }
+        }))
+        .unwrap();
+
+        let export = |write_mode, seed| {
+            export_to(
+                &uri_string,
+                &namespace,
+                sample(&namespace, 20, seed),
+                TransactionMode::default(),
+                write_mode,
+            )
+        };
+        let datasource = SqliteDataSource::new(&uri_string).unwrap();
+        let names = || -> Vec<String> {
+            task::block_on(
+                sqlx::query_scalar("SELECT name FROM users ORDER BY id")
+                    .fetch_all(&datasource.get_pool()),
+            )
+            .unwrap()
+        };
+
+        export(WriteMode::Append, 0).unwrap();
+        // The same ids are generated again
+        assert!(export(WriteMode::Append, 0).is_err());
+
+        let appended = names();
+        export(WriteMode::Upsert, 1).unwrap();
+        let upserted = names();
+        assert_eq!(upserted.len(), 10);
+        assert_ne!(upserted, appended);
+
+        task::block_on(
+            sqlx::query("INSERT INTO users VALUES (100, 'extra')").execute(&datasource.get_pool()),
+        )
+        .unwrap();
+        export(WriteMode::Truncate, 2).unwrap();
+        let (users, posts): (i64, i64) = fetch_one(
+            &uri_string,
+            "SELECT (SELECT COUNT(*) FROM users), (SELECT COUNT(*) FROM posts)",
+        );
+        assert_eq!(users, 10);
+        assert_eq!(posts, 10);
+    }
+}
@@ -87,7 +87,7 @@ impl Store {
             }
         }
 
-        Ok(ns)
+        Ok(ns.with_directory(ns_path))
     }
 
     pub fn get_scenario(
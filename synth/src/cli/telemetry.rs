@@ -9,13 +9,13 @@ use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::io::{self, BufRead, Read, Write};
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use uuid::Uuid;
 
 use crate::cli::export::ExportStrategy;
 use crate::cli::{config, GenerateCommand, ImportCommand};
-use crate::sampler::SamplerOutput;
+use crate::sampler::{Sample, SamplerOutput};
 use crate::version::version;
 
 use synth_core::{
@@ -158,6 +158,7 @@ struct TelemetryCrawler<'t, 'a> {
     state: &'t mut CompilerState<'a, Graph>,
     position: Address,
     context: Rc<RefCell<TelemetryContext>>,
+    directory: Option<&'a Path>,
 }
 
 impl<'t, 'a: 't> TelemetryCrawler<'t, 'a> {
@@ -167,6 +168,7 @@ impl<'t, 'a: 't> TelemetryCrawler<'t, 'a> {
             state: self.state.entry(field).or_init(content),
             position,
             context: Rc::clone(&self.context),
+            directory: self.directory,
         }
     }
 
@@ -199,6 +201,10 @@ impl<'t, 'a: 't> Compiler<'a> for TelemetryCrawler<'t, 'a> {
     fn get<S: Into<Address>>(&mut self, _target: S) -> Result<Graph> {
         Ok(Graph::dummy())
     }
+
+    fn directory(&self) -> Option<&Path> {
+        self.directory
+    }
 }
 
 pub(super) struct TelemetryExportStrategy<'w> {
@@ -233,6 +239,7 @@ impl<'w> TelemetryExportStrategy<'w> {
             state: &mut CompilerState::namespace(namespace),
             position: Address::new_root(),
             context: Rc::clone(&context),
+            directory: namespace.directory(),
         };
 
         if let Some(name) = collection {
@@ -269,18 +276,16 @@ impl<'w> TelemetryExportStrategy<'w> {
         Ok(())
     }
 
-    fn fill_telemetry_bytes(&self, output: SamplerOutput) -> Result<()> {
+    fn fill_telemetry_bytes(context: &RefCell<TelemetryContext>, output: SamplerOutput) {
         let j = output.into_json();
-        let s = serde_json::to_string(&j)?;
-
-        self.telemetry_context.borrow_mut().bytes = Some(s.len());
-
-        Ok(())
+        if let Ok(s) = serde_json::to_string(&j) {
+            *context.borrow_mut().bytes.get_or_insert(0) += s.len();
+        }
     }
 }
 
 impl<'w> ExportStrategy for TelemetryExportStrategy<'w> {
-    fn export(&self, namespace: Namespace, sample: SamplerOutput) -> Result<()> {
+    fn export(&self, namespace: Namespace, sample: Sample) -> Result<()> {
         Self::fill_telemetry(
             Rc::clone(&self.telemetry_context),
             &namespace,
@@ -288,7 +293,13 @@ impl<'w> ExportStrategy for TelemetryExportStrategy<'w> {
             self.ns_path.clone(),
         )?;
 
-        self.fill_telemetry_bytes(sample.clone())?;
+        // The size of the output is accumulated as chunks are generated
+        let context = Rc::clone(&self.telemetry_context);
+        let sample = Sample::new(sample.inspect(move |chunk| {
+            if let Ok(chunk) = chunk {
+                Self::fill_telemetry_bytes(&context, chunk.clone());
+            }
+        }));
 
         self.exporter.export(namespace, sample)
     }
@@ -320,6 +331,7 @@ where
     let command_name = match &args {
         Args::Generate { .. } => "generate",
         Args::Import { .. } => "import",
+        Args::Validate { .. } => "validate",
         Args::Telemetry(TelemetryCommand::Enable) => "telemetry::enable",
         Args::Telemetry(TelemetryCommand::Disable) => "telemetry::disable",
         Args::Telemetry(TelemetryCommand::Status) => "telemetry::status",
@@ -527,7 +539,7 @@ impl TelemetryClient {
 #[cfg(test)]
 pub mod tests {
     use super::{
-        ExportStrategy, Namespace, SamplerOutput, TelemetryClient, TelemetryContext,
+        ExportStrategy, Namespace, Sample, TelemetryClient, TelemetryContext,
         TelemetryExportStrategy,
     };
     use crate::sampler::Sampler;
@@ -549,7 +561,10 @@ pub mod tests {
     pub struct DummyExportStrategy {}
 
     impl ExportStrategy for DummyExportStrategy {
-        fn export(&self, _namespace: Namespace, _sample: SamplerOutput) -> Result<()> {
+        fn export(&self, _namespace: Namespace, sample: Sample) -> Result<()> {
+            for chunk in sample {
+                chunk?;
+            }
             Ok(())
         }
     }
@@ -5,6 +5,20 @@ use synth_core::Value;
 pub(crate) mod mysql_datasource;
 pub(crate) mod postgres_datasource;
 pub(crate) mod relational_datasource;
+pub(crate) mod sqlite_datasource;
+
+/// How generated data is written into a database which may already hold data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WriteMode {
+    /// The generated data is added to the existing data.
+    #[default]
+    Append,
+    /// The existing data is deleted before the generated data is inserted.
+    Truncate,
+    /// The existing rows (or documents) with the same primary key (or `_id`) as generated ones
+    /// are replaced.
+    Upsert,
+}
 
 #[allow(clippy::too_long_first_doc_paragraph)]
 /// This trait encompasses all data source types, whether it's SQL or No-SQL. APIs should be defined
@@ -1,7 +1,8 @@
 use crate::datasource::relational_datasource::{
-    insert_relational_data, ColumnInfo, ForeignKey, PrimaryKey, SqlxDataSource, ValueWrapper,
+    get_tables_metadata, insert_relational_data, ColumnInfo, ForeignKey, PrimaryKey,
+    SqlxDataSource, UniqueKey, ValueWrapper,
 };
-use crate::datasource::DataSource;
+use crate::datasource::{DataSource, WriteMode};
 use anyhow::{Context, Result};
 use async_std::task;
 use async_trait::async_trait;
@@ -42,13 +43,27 @@ impl DataSource for MySqlDataSource {
     }
 
     async fn insert_data(&self, collection_name: &str, collection: &[Value]) -> Result<()> {
-        insert_relational_data(self, collection_name, collection).await
+        let mut connection = self.get_multithread_pool().acquire().await?;
+        let metadata = get_tables_metadata(self, &mut connection, &[collection_name])
+            .await?
+            .remove(collection_name)
+            .unwrap_or_default();
+        insert_relational_data(
+            self,
+            &mut connection,
+            collection_name,
+            collection,
+            &metadata,
+            WriteMode::Append,
+            &[],
+        )
+        .await?;
+        Ok(())
     }
 }
 
 impl SqlxDataSource for MySqlDataSource {
     type DB = MySql;
-    type Arguments = sqlx::mysql::MySqlArguments;
     type Connection = sqlx::mysql::MySqlConnection;
 
     const IDENTIFIER_QUOTE: char = '`';
@@ -72,6 +87,20 @@ impl SqlxDataSource for MySqlDataSource {
             WHERE table_schema = DATABASE() AND table_name = ? AND column_key = 'PRI'"
     }
 
+    fn get_unique_keys_query(&self) -> &str {
+        // Functional key parts have no column name
+        r"SELECT index_name, column_name
+            FROM information_schema.statistics s
+            WHERE table_schema = DATABASE() AND table_name = ?
+            AND non_unique = 0 AND index_name <> 'PRIMARY'
+            AND NOT EXISTS (
+                SELECT 1 FROM information_schema.statistics f
+                WHERE f.table_schema = s.table_schema AND f.table_name = s.table_name
+                AND f.index_name = s.index_name AND f.column_name IS NULL
+            )
+            ORDER BY index_name, seq_in_index"
+    }
+
     fn get_foreign_keys_query(&self) -> &str {
         r"SELECT table_name, column_name, referenced_table_name, referenced_column_name
             FROM information_schema.key_column_usage
@@ -86,6 +115,20 @@ impl SqlxDataSource for MySqlDataSource {
         table_name.to_string()
     }
 
+    fn get_upsert_clause(&self, column_names: &[&str], primary_keys: &[String]) -> String {
+        // Updating a key to itself leaves the existing row as it is
+        let updates: Vec<String> = column_names
+            .iter()
+            .filter(|column| !primary_keys.iter().any(|key| key == *column))
+            .map(|column| format!("`{column}` = VALUES(`{column}`)"))
+            .collect();
+        if updates.is_empty() {
+            format!("ON DUPLICATE KEY UPDATE `{0}` = `{0}`", primary_keys[0])
+        } else {
+            format!("ON DUPLICATE KEY UPDATE {}", updates.join(","))
+        }
+    }
+
     fn decode_to_content(&self, column_info: &ColumnInfo) -> Result<Content> {
         let content = match column_info.data_type.to_lowercase().as_str() {
             "char" | "varchar" | "text" | "binary" | "varbinary" | "enum" | "set"
@@ -160,6 +203,8 @@ impl TryFrom<MySqlRow> for ColumnInfo {
             data_type: row.try_get::<String, usize>(3)?,
             character_maximum_length: extract_column_char_max_len(4, row)?,
             is_custom_type: false,
+            enum_values: None,
+            check_constraints: Vec::new(),
         })
     }
 }
@@ -188,6 +233,17 @@ impl TryFrom<MySqlRow> for PrimaryKey {
     }
 }
 
+impl TryFrom<MySqlRow> for UniqueKey {
+    type Error = anyhow::Error;
+
+    fn try_from(row: MySqlRow) -> Result<Self, Self::Error> {
+        Ok(UniqueKey {
+            index_name: row.try_get(0)?,
+            column_name: row.try_get(1)?,
+        })
+    }
+}
+
 impl TryFrom<MySqlRow> for ForeignKey {
     type Error = anyhow::Error;
 
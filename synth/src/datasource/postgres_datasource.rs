@@ -1,22 +1,31 @@
 use crate::datasource::relational_datasource::{
-    insert_relational_data, ColumnInfo, ForeignKey, PrimaryKey, SqlxDataSource, ValueWrapper,
+    get_tables_metadata, insert_relational_data, ColumnInfo, ForeignKey, PrimaryKey,
+    SqlxDataSource, UniqueKey, ValueWrapper, DEFAULT_INSERT_BATCH_SIZE,
 };
-use crate::datasource::DataSource;
+use crate::datasource::{DataSource, WriteMode};
 use anyhow::{Context, Result};
 use async_std::sync::Arc;
 use async_std::task;
 use async_trait::async_trait;
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use sqlx::postgres::{PgColumn, PgPoolOptions, PgRow, PgTypeInfo, PgTypeKind};
+use sqlx::postgres::{PgArguments, PgColumn, PgPoolOptions, PgRow, PgTypeInfo, PgTypeKind};
 use sqlx::{Column, Executor, Pool, Postgres, Row, TypeInfo};
 use std::collections::BTreeMap;
+use synth_core::graph::json::synth_val_to_json;
 use synth_core::schema::number_content::{F32, F64, I16, I32, I64};
 use synth_core::schema::{
     ArrayContent, BoolContent, Categorical, ChronoValue, ChronoValueAndFormat, ChronoValueType,
     DateTimeContent, NumberContent, ObjectContent, RangeStep, RegexContent, StringContent, Uuid,
 };
 use synth_core::{Content, Value};
+use synth_gen::value::Number;
+
+/// The maximum number of parameters of a single query.
+const MAX_QUERY_PARAMETERS: usize = 65535;
+
+/// The size of the messages in which rows are sent to `COPY`.
+const COPY_MESSAGE_SIZE: usize = 1 << 20;
 
 pub struct PostgresConnectParams {
     pub(crate) uri: String,
@@ -81,7 +90,22 @@ impl DataSource for PostgresDataSource {
     }
 
     async fn insert_data(&self, collection_name: &str, collection: &[Value]) -> Result<()> {
-        insert_relational_data(self, collection_name, collection).await
+        let mut connection = self.get_multithread_pool().acquire().await?;
+        let metadata = get_tables_metadata(self, &mut connection, &[collection_name])
+            .await?
+            .remove(collection_name)
+            .unwrap_or_default();
+        insert_relational_data(
+            self,
+            &mut connection,
+            collection_name,
+            collection,
+            &metadata,
+            WriteMode::Append,
+            &[],
+        )
+        .await?;
+        Ok(())
     }
 }
 
@@ -114,7 +138,6 @@ impl PostgresDataSource {
 #[async_trait]
 impl SqlxDataSource for PostgresDataSource {
     type DB = Postgres;
-    type Arguments = sqlx::postgres::PgArguments;
     type Connection = sqlx::postgres::PgConnection;
 
     const IDENTIFIER_QUOTE: char = '\"';
@@ -127,7 +150,7 @@ impl SqlxDataSource for PostgresDataSource {
         Pool::clone(&self.pool)
     }
 
-    fn query<'q>(&self, query: &'q str) -> sqlx::query::Query<'q, Self::DB, Self::Arguments> {
+    fn query<'q>(&self, query: &'q str) -> sqlx::query::Query<'q, Self::DB, PgArguments> {
         sqlx::query(query).bind(self.schema.clone())
     }
 
@@ -146,6 +169,20 @@ impl SqlxDataSource for PostgresDataSource {
         WHERE  i.indrelid = cast(concat('"', $2, '"') as regclass) AND i.indisprimary"#
     }
 
+    fn get_unique_keys_query(&self) -> &str {
+        // Partial and expression indexes do not constrain the values of whole columns
+        r"SELECT ic.relname, a.attname
+        FROM pg_index i
+        JOIN pg_class ic ON ic.oid = i.indexrelid
+        CROSS JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS k(attnum, position)
+        JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+        WHERE i.indrelid = format('%I.%I', $1, $2)::regclass
+        AND i.indisunique AND NOT i.indisprimary
+        AND i.indpred IS NULL AND i.indexprs IS NULL
+        AND k.position <= i.indnkeyatts
+        ORDER BY ic.relname, k.position"
+    }
+
     fn get_foreign_keys_query(&self) -> &str {
         r"SELECT tc.table_name, kcu.column_name, ccu.table_name AS foreign_table_name,
             ccu.column_name AS foreign_column_name
@@ -176,7 +213,73 @@ impl SqlxDataSource for PostgresDataSource {
         format!("\"{table_name}\"")
     }
 
+    fn get_insert_batch_size(&self, columns: usize) -> usize {
+        (MAX_QUERY_PARAMETERS / columns.max(1)).clamp(1, DEFAULT_INSERT_BATCH_SIZE)
+    }
+
+    async fn bulk_insert(
+        &self,
+        connection: &mut Self::Connection,
+        table_name: &str,
+        column_names: &[&str],
+        rows: &[Vec<Value>],
+    ) -> Result<bool> {
+        if !rows.iter().flatten().all(is_copy_text_supported) {
+            return Ok(false);
+        }
+
+        let column_names = column_names
+            .iter()
+            .map(|column_name| format!("\"{column_name}\""))
+            .collect::<Vec<_>>()
+            .join(",");
+        let statement = format!(
+            "COPY {} ({column_names}) FROM STDIN",
+            self.get_table_name_for_insert(table_name)
+        );
+
+        let mut copy = connection.copy_in_raw(&statement).await?;
+        let mut message = String::new();
+        for row in rows {
+            let fields = row.iter().map(copy_field).collect::<Vec<_>>().join("\t");
+            message.push_str(&fields);
+            message.push('\n');
+            if message.len() >= COPY_MESSAGE_SIZE {
+                copy.send(std::mem::take(&mut message).into_bytes()).await?;
+            }
+        }
+        if !message.is_empty() {
+            copy.send(message.into_bytes()).await?;
+        }
+        copy.finish().await?;
+
+        Ok(true)
+    }
+
+    fn get_truncate_tables_statements(&self, table_names: &[&str]) -> Vec<String> {
+        // Tables referenced by others can only be truncated together with them
+        if table_names.is_empty() {
+            return Vec::new();
+        }
+        let table_names: Vec<String> = table_names
+            .iter()
+            .map(|table_name| self.get_table_name_for_insert(table_name))
+            .collect();
+        vec![format!("TRUNCATE {}", table_names.join(", "))]
+    }
+
     fn decode_to_content(&self, column_info: &ColumnInfo) -> Result<Content> {
+        // Arrays of enumerated types are decoded to arrays of their elements below
+        if let Some(enum_values) = &column_info.enum_values {
+            if !column_info.data_type.starts_with('_') {
+                let mut categorical = Categorical::default();
+                for value in enum_values {
+                    categorical.push(value.clone());
+                }
+                return Ok(Content::String(StringContent::Categorical(categorical)));
+            }
+        }
+
         if column_info.is_custom_type {
             return Ok(Content::String(StringContent::Categorical(
                 Categorical::default(),
@@ -227,10 +330,7 @@ impl SqlxDataSource for PostgresDataSource {
                 begin: None,
                 end: None,
             }),
-            "json" | "jsonb" => Content::Object(ObjectContent {
-                skip_when_null: false,
-                fields: BTreeMap::new(),
-            }),
+            "json" | "jsonb" => Content::Object(ObjectContent::default()),
             "uuid" => Content::String(StringContent::Uuid(Uuid)),
             _ => {
                 if let Some(data_type) = column_info.data_type.strip_prefix('_') {
@@ -249,33 +349,382 @@ impl SqlxDataSource for PostgresDataSource {
             }
         };
 
-        Ok(content)
+        let bounds = CheckBounds::parse(&column_info.column_name, &column_info.check_constraints);
+        Ok(bounds.apply(content))
     }
 
     fn get_function_argument_placeholder(current: usize, index: usize, value: &Value) -> String {
-        let extra = if let Value::Array(_) = value {
-            let (typ, depth) = value.get_postgres_type();
-            if typ == "unknown" {
-                "".to_string() // This is currently not supported
-            } else if typ == "jsonb" {
-                "::jsonb".to_string() // Cannot have an array of jsonb - ie jsonb[]
+        format!("${}{}", current + index + 1, postgres_cast(value))
+    }
+
+    fn get_columns_info_query(&self) -> &str {
+        // The type of a column with a domain is the domain's base type, but its CHECK constraints
+        // are the domain's too
+        r"SELECT c.column_name, c.ordinal_position, c.is_nullable, c.udt_name,
+        c.character_maximum_length, c.data_type,
+        (SELECT array_agg(e.enumlabel::text ORDER BY e.enumsortorder)
+            FROM pg_type t
+            JOIN pg_namespace n ON n.oid = t.typnamespace
+            JOIN pg_enum e ON e.enumtypid = t.oid OR e.enumtypid = t.typelem
+            WHERE t.typname = c.udt_name AND n.nspname = c.udt_schema) AS enum_values,
+        ARRAY(SELECT pg_get_constraintdef(k.oid)
+            FROM pg_constraint k
+            WHERE k.contype = 'c'
+            AND ((k.conrelid = format('%I.%I', c.table_schema, c.table_name)::regclass
+                AND k.conkey = ARRAY[c.ordinal_position::smallint])
+            OR k.contypid = (SELECT d.oid
+                FROM pg_type d
+                JOIN pg_namespace n ON n.oid = d.typnamespace
+                WHERE d.typname = c.domain_name AND n.nspname = c.domain_schema))) AS check_constraints
+        FROM information_schema.columns c
+        WHERE c.table_name = $2
+        AND c.table_schema = $1
+        AND c.table_catalog = current_catalog"
+    }
+}
+
+/// The bounds which the CHECK constraints of a column set on its values, as the bound and whether
+/// it is inclusive.
+#[derive(Debug, Default, PartialEq)]
+struct CheckBounds {
+    low: Option<(f64, bool)>,
+    high: Option<(f64, bool)>,
+}
+
+impl CheckBounds {
+    /// Parse the bounds of the simple range checks among `constraints` (as written by
+    /// `pg_get_constraintdef`), which compare `column_name` (or `VALUE` for domains) to constants.
+    /// Other checks are ignored.
+    fn parse(column_name: &str, constraints: &[String]) -> Self {
+        let mut bounds = Self::default();
+        for constraint in constraints {
+            let Some(expression) = constraint.strip_prefix("CHECK ") else {
+                continue;
+            };
+            let Some(terms) = split_conjunction(expression) else {
+                continue;
+            };
+            for term in terms {
+                for (op, constant) in parse_comparisons(column_name, term) {
+                    match op {
+                        ">" => bounds.raise_low(constant, false),
+                        ">=" => bounds.raise_low(constant, true),
+                        "<" => bounds.lower_high(constant, false),
+                        "<=" => bounds.lower_high(constant, true),
+                        _ => {
+                            bounds.raise_low(constant, true);
+                            bounds.lower_high(constant, true);
+                        }
+                    }
+                }
+            }
+        }
+        bounds
+    }
+
+    fn raise_low(&mut self, bound: f64, inclusive: bool) {
+        match self.low {
+            Some((low, low_inclusive))
+                if low > bound || (low == bound && (!low_inclusive || inclusive)) => {}
+            _ => self.low = Some((bound, inclusive)),
+        }
+    }
+
+    fn lower_high(&mut self, bound: f64, inclusive: bool) {
+        match self.high {
+            Some((high, high_inclusive))
+                if high < bound || (high == bound && (!high_inclusive || inclusive)) => {}
+            _ => self.high = Some((bound, inclusive)),
+        }
+    }
+
+    /// Restrict the range of numbers generated by `content` to the bounds.
+    fn apply(&self, content: Content) -> Content {
+        if *self == Self::default() {
+            return content;
+        }
+
+        let number = match content {
+            Content::Number(NumberContent::I16(I16::Range(range))) => {
+                NumberContent::I16(I16::Range(self.integer_range(range, |n| n as i16)))
+            }
+            Content::Number(NumberContent::I32(I32::Range(range))) => {
+                NumberContent::I32(I32::Range(self.integer_range(range, |n| n as i32)))
+            }
+            Content::Number(NumberContent::I64(I64::Range(range))) => {
+                NumberContent::I64(I64::Range(self.integer_range(range, |n| n as i64)))
+            }
+            Content::Number(NumberContent::F32(F32::Range(range))) => {
+                NumberContent::F32(F32::Range(self.float_range(range, |n| n as f32)))
+            }
+            Content::Number(NumberContent::F64(F64::Range(range))) => {
+                NumberContent::F64(F64::Range(self.float_range(range, |n| n)))
+            }
+            other => return other,
+        };
+        Content::Number(number)
+    }
+
+    fn integer_range<N>(
+        &self,
+        mut range: RangeStep<N>,
+        from_f64: impl Fn(f64) -> N,
+    ) -> RangeStep<N> {
+        if let Some((low, inclusive)) = self.low {
+            let low = if inclusive {
+                low.ceil()
+            } else {
+                low.floor() + 1.0
+            };
+            range.low = Some(from_f64(low));
+            range.include_low = true;
+        }
+        if let Some((high, inclusive)) = self.high {
+            let high = if inclusive {
+                high.floor()
             } else {
-                format!("::{}{}", typ, "[]".repeat(depth))
+                high.ceil() - 1.0
+            };
+            range.high = Some(from_f64(high));
+            range.include_high = true;
+        }
+        range
+    }
+
+    fn float_range<N: Copy + Into<f64>>(
+        &self,
+        mut range: RangeStep<N>,
+        from_f64: impl Fn(f64) -> N,
+    ) -> RangeStep<N> {
+        if let Some((low, inclusive)) = self.low {
+            // Float ranges only exclude their low with a step, so start right above it instead
+            let mut delta = low.abs().max(1.0) * f64::EPSILON;
+            while !inclusive && from_f64(low + delta).into() <= low {
+                delta *= 2.0;
             }
-        } else {
-            "".to_string()
+            range.low = Some(from_f64(if inclusive { low } else { low + delta }));
+            // Floats are otherwise generated in [0, 1), which may not be within the bounds
+            if self.high.is_none() && low >= 1.0 {
+                range.high = Some(from_f64(low + 1.0));
+            }
+        }
+        if let Some((high, inclusive)) = self.high {
+            range.high = Some(from_f64(high));
+            range.include_high = inclusive;
+            if self.low.is_none() && high <= 0.0 {
+                range.low = Some(from_f64(high - 1.0));
+            }
+        }
+        range
+    }
+}
+
+/// Split `expression` into the terms of its top-level `AND`s, keeping `BETWEEN ... AND ...`
+/// together. Returns `None` if the terms are combined in any other way.
+fn split_conjunction(expression: &str) -> Option<Vec<&str>> {
+    let expression = strip_parentheses(expression);
+
+    let mut terms = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut is_between = false;
+    for (i, c) in expression.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth == 0 && expression[i..].starts_with(" BETWEEN ") => is_between = true,
+            _ if depth == 0 && expression[i..].starts_with(" OR ") => return None,
+            _ if depth == 0 && expression[i..].starts_with(" AND ") => {
+                if is_between {
+                    is_between = false;
+                } else {
+                    terms.push(strip_parentheses(&expression[start..i]));
+                    start = i + " AND ".len();
+                }
+            }
+            _ => {}
+        }
+    }
+    terms.push(strip_parentheses(&expression[start..]));
+    Some(terms)
+}
+
+/// The comparisons made by `term` of `column_name` to constants, as the operator (with the column
+/// on its left) and the constant.
+fn parse_comparisons(column_name: &str, term: &str) -> Vec<(&'static str, f64)> {
+    let is_column = |operand: &str| {
+        let operand = strip_parentheses(operand);
+        operand == "VALUE"
+            || operand == column_name
+            || operand == format!("\"{}\"", column_name.replace('"', "\"\""))
+    };
+
+    if let Some((operand, range)) = term.split_once(" BETWEEN ") {
+        let (low, high) = match range.split_once(" AND ") {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+        return match (
+            is_column(operand),
+            parse_constant(low),
+            parse_constant(high),
+        ) {
+            (true, Some(low), Some(high)) => vec![(">=", low), ("<=", high)],
+            _ => Vec::new(),
         };
+    }
 
-        format!("${}{}", current + index + 1, extra)
+    for (op, flipped) in [
+        (">=", "<="),
+        ("<=", ">="),
+        ("<>", "<>"),
+        (">", "<"),
+        ("<", ">"),
+        ("=", "="),
+    ] {
+        if let Some((left, right)) = term.split_once(&format!(" {op} ")) {
+            if op == "<>" {
+                return Vec::new();
+            }
+            if is_column(left) {
+                return parse_constant(right).map(|c| (op, c)).into_iter().collect();
+            }
+            if is_column(right) {
+                return parse_constant(left)
+                    .map(|c| (flipped, c))
+                    .into_iter()
+                    .collect();
+            }
+            return Vec::new();
+        }
     }
+    Vec::new()
+}
 
-    fn get_columns_info_query(&self) -> &str {
-        r"SELECT column_name, ordinal_position, is_nullable, udt_name,
-        character_maximum_length, data_type
-        FROM information_schema.columns
-        WHERE table_name = $2
-        AND table_schema = $1
-        AND table_catalog = current_catalog"
+/// Parse a numeric constant, which may be quoted and cast (e.g. `('-40'::integer)::real`).
+fn parse_constant(constant: &str) -> Option<f64> {
+    let mut constant = strip_parentheses(constant);
+    while let Some((value, _)) = constant
+        .rsplit_once("::")
+        .filter(|(_, type_name)| !type_name.contains(['(', ')', '\'']))
+    {
+        constant = strip_parentheses(value);
+    }
+    let constant = constant
+        .strip_prefix('\'')
+        .and_then(|constant| constant.strip_suffix('\''))
+        .unwrap_or(constant);
+    constant
+        .parse()
+        .ok()
+        .filter(|constant: &f64| constant.is_finite())
+}
+
+/// Strip the parentheses enclosing the whole of `s`.
+fn strip_parentheses(s: &str) -> &str {
+    let mut s = s.trim();
+    while let Some(inner) = s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        // Make sure the parentheses match each other, unlike in `(a) AND (b)`
+        let mut depth = 0;
+        let is_enclosing = inner.chars().all(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        });
+        if !is_enclosing {
+            break;
+        }
+        s = inner.trim();
+    }
+    s
+}
+
+/// Whether the text format of `COPY` represents `value` as it is encoded when bound to a query.
+/// Unsigned integers are encoded as the signed integers of the same size, wrapping around past
+/// their maximum.
+fn is_copy_text_supported(value: &Value) -> bool {
+    match value {
+        Value::Number(Number::U8(u)) => i8::try_from(*u).is_ok(),
+        Value::Number(Number::U16(u)) => i16::try_from(*u).is_ok(),
+        Value::Number(Number::U32(u)) => i32::try_from(*u).is_ok(),
+        Value::Number(Number::U64(u)) => i64::try_from(*u).is_ok(),
+        _ => true,
+    }
+}
+
+/// The text of a date time as Postgres parses it.
+pub(crate) fn date_time_text(value: &ChronoValue) -> String {
+    match value {
+        ChronoValue::NaiveDate(date) => date.format("%Y-%m-%d").to_string(),
+        ChronoValue::NaiveTime(time) => time.format("%H:%M:%S%.f").to_string(),
+        ChronoValue::NaiveDateTime(date_time) => {
+            date_time.format("%Y-%m-%d %H:%M:%S%.f").to_string()
+        }
+        ChronoValue::DateTime(date_time) => date_time.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string(),
+    }
+}
+
+/// The text of a number, or an error with the text of a float which is not finite (and so needs
+/// quoting in Postgres).
+pub(crate) fn number_text(num: &Number) -> std::result::Result<String, String> {
+    let float = match *num {
+        Number::F32(f) => *f as f64,
+        Number::F64(f) => *f,
+        _ => return Ok(num.to_string()),
+    };
+    if float.is_nan() {
+        Err("NaN".to_string())
+    } else if float.is_infinite() {
+        Err(if float > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+    } else {
+        match *num {
+            Number::F32(f) => Ok((*f).to_string()),
+            _ => Ok(float.to_string()),
+        }
+    }
+}
+
+/// Render `value` as a field of the text format of Postgres' `COPY`.
+pub(crate) fn copy_field(value: &Value) -> String {
+    let text = match value {
+        Value::Null(_) => return "\\N".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(num) => number_text(num).unwrap_or_else(|text| text),
+        Value::String(s) => s.clone(),
+        Value::DateTime(date_time) => date_time_text(&date_time.value),
+        Value::Object(_) => synth_val_to_json(value.clone()).to_string(),
+        Value::Array(_) => value.to_postgres_string(),
+    };
+
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The cast to apply to `value` once encoded, since arrays are encoded as text.
+pub(crate) fn postgres_cast(value: &Value) -> String {
+    if let Value::Array(_) = value {
+        let (typ, depth) = value.get_postgres_type();
+        if typ == "unknown" {
+            "".to_string() // This is currently not supported
+        } else if typ == "jsonb" {
+            "::jsonb".to_string() // Cannot have an array of jsonb - ie jsonb[]
+        } else {
+            format!("::{}{}", typ, "[]".repeat(depth))
+        }
+    } else {
+        "".to_string()
     }
 }
 
@@ -290,6 +739,8 @@ impl TryFrom<PgRow> for ColumnInfo {
             data_type: row.try_get(3)?,
             character_maximum_length: row.try_get(4)?,
             is_custom_type: row.try_get::<String, usize>(5)? == "USER-DEFINED",
+            enum_values: row.try_get(6)?,
+            check_constraints: row.try_get(7)?,
         })
     }
 }
@@ -305,6 +756,17 @@ impl TryFrom<PgRow> for PrimaryKey {
     }
 }
 
+impl TryFrom<PgRow> for UniqueKey {
+    type Error = anyhow::Error;
+
+    fn try_from(row: PgRow) -> Result<Self, Self::Error> {
+        Ok(UniqueKey {
+            index_name: row.try_get(0)?,
+            column_name: row.try_get(1)?,
+        })
+    }
+}
+
 impl TryFrom<PgRow> for ForeignKey {
     type Error = anyhow::Error;
 
@@ -512,3 +974,174 @@ impl From<EnumType> for String {
         enum_type.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(column_name: &str, constraints: &[&str]) -> CheckBounds {
+        let constraints: Vec<String> = constraints.iter().map(|c| c.to_string()).collect();
+        CheckBounds::parse(column_name, &constraints)
+    }
+
+    #[test]
+    fn parse_check_bounds() {
+        assert_eq!(
+            bounds("age", &["CHECK (((age >= 0) AND (age <= 150)))"]),
+            CheckBounds {
+                low: Some((0.0, true)),
+                high: Some((150.0, true))
+            }
+        );
+        assert_eq!(
+            bounds("age", &["CHECK ((age BETWEEN 18 AND 65))"]),
+            CheckBounds {
+                low: Some((18.0, true)),
+                high: Some((65.0, true))
+            }
+        );
+        assert_eq!(
+            bounds(
+                "score",
+                &["CHECK (((score >= '-5.5'::numeric) AND (score < (10)::numeric)))"]
+            ),
+            CheckBounds {
+                low: Some((-5.5, true)),
+                high: Some((10.0, false))
+            }
+        );
+        assert_eq!(
+            bounds(
+                "temp",
+                &["CHECK ((temp > ('-40'::integer)::double precision))"]
+            ),
+            CheckBounds {
+                low: Some((-40.0, false)),
+                high: None
+            }
+        );
+        assert_eq!(
+            bounds(
+                "Weird Col",
+                &["CHECK ((0 < \"Weird Col\"))", "CHECK ((VALUE <= 3))"]
+            ),
+            CheckBounds {
+                low: Some((0.0, false)),
+                high: Some((3.0, true))
+            }
+        );
+
+        // The tightest bounds win
+        assert_eq!(
+            bounds(
+                "x",
+                &["CHECK ((x > 0))", "CHECK ((x >= 0))", "CHECK ((x >= 5))"]
+            ),
+            CheckBounds {
+                low: Some((5.0, true)),
+                high: None
+            }
+        );
+
+        // Anything else is ignored
+        for constraint in [
+            "CHECK (((c > 0) OR (c < '-10'::integer)))",
+            "CHECK ((a < b))",
+            "CHECK ((x <> 0))",
+            "CHECK (((VALUE)::text = ANY ((ARRAY['YES'::character varying])::text[])))",
+        ] {
+            assert_eq!(bounds("x", &[constraint]), CheckBounds::default());
+        }
+    }
+
+    #[test]
+    fn apply_check_bounds() {
+        let bounds = CheckBounds {
+            low: Some((0.0, false)),
+            high: Some((10.5, true)),
+        };
+
+        let content = bounds.apply(Content::Number(NumberContent::I32(I32::Range(
+            RangeStep::default(),
+        ))));
+        match content {
+            Content::Number(NumberContent::I32(I32::Range(range))) => {
+                assert_eq!((range.low, range.high), (Some(1), Some(10)));
+                assert!(range.include_low && range.include_high);
+            }
+            other => panic!("expected an i32 range, found {other:?}"),
+        }
+
+        let content = bounds.apply(Content::Number(NumberContent::F32(F32::Range(
+            RangeStep::default(),
+        ))));
+        match content {
+            Content::Number(NumberContent::F32(F32::Range(range))) => {
+                assert!(range.low.unwrap() > 0.0 && range.include_low);
+                assert_eq!(range.high, Some(10.5));
+                assert!(range.include_high);
+            }
+            other => panic!("expected an f32 range, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn copy_text_supported() {
+        for value in [
+            Value::Null(()),
+            Value::Bool(true),
+            Value::Number(Number::U8(i8::MAX as u8)),
+            Value::Number(Number::U16(i16::MAX as u16)),
+            Value::Number(Number::U32(i32::MAX as u32)),
+            Value::Number(Number::U64(i64::MAX as u64)),
+            Value::Number(Number::I64(i64::MIN)),
+            Value::Number(Number::F64(f64::NAN.into())),
+            Value::String("text".to_string()),
+            Value::Array(vec![Value::Number(Number::I32(1))]),
+        ] {
+            assert!(is_copy_text_supported(&value), "{value:?}");
+        }
+
+        // Unsigned integers past the maximum of the signed ones wrap around when bound
+        for value in [
+            Value::Number(Number::U8(i8::MAX as u8 + 1)),
+            Value::Number(Number::U16(u16::MAX)),
+            Value::Number(Number::U32(i32::MAX as u32 + 1)),
+            Value::Number(Number::U64(u64::MAX)),
+        ] {
+            assert!(!is_copy_text_supported(&value), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn copy_field_escaping() {
+        assert_eq!(copy_field(&Value::Null(())), "\\N");
+        // The text of a null is escaped so it is not read as one
+        assert_eq!(copy_field(&Value::String("\\N".to_string())), "\\\\N");
+        assert_eq!(
+            copy_field(&Value::String("a\tb\nc\rd\\e".to_string())),
+            "a\\tb\\nc\\rd\\\\e"
+        );
+        assert_eq!(copy_field(&Value::Bool(false)), "false");
+        assert_eq!(copy_field(&Value::Number(Number::I32(-42))), "-42");
+        assert_eq!(
+            copy_field(&Value::Number(Number::F64(f64::INFINITY.into()))),
+            "Infinity"
+        );
+        assert_eq!(
+            copy_field(&Value::Array(vec![
+                Value::Number(Number::I32(1)),
+                Value::Number(Number::I32(2)),
+            ])),
+            "{1, 2}"
+        );
+        // The tab is escaped for `COPY`, which hands it to the array parser unescaped
+        assert_eq!(
+            copy_field(&Value::Array(vec![
+                Value::String("a\tb".to_string()),
+                Value::String("c".to_string()),
+            ])),
+            "{\"a\\tb\", \"c\"}"
+        );
+    }
+}
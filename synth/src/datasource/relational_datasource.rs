@@ -1,15 +1,23 @@
-use crate::datasource::DataSource;
-use anyhow::Result;
+use crate::datasource::{DataSource, WriteMode};
+use anyhow::{Context, Result};
 use async_trait::async_trait;
-use beau_collector::BeauCollector;
-use futures::future::join_all;
-use sqlx::{
-    query::Query, Arguments, Connection, Database, Encode, Executor, IntoArguments, Pool, Type,
-};
+use sqlx::database::HasArguments;
+use sqlx::{query::Query, Connection, Database, Encode, Executor, IntoArguments, Pool, Type};
+use std::collections::BTreeMap;
 use synth_core::{Content, Value};
 use synth_gen::value::Number;
 
-const DEFAULT_INSERT_BATCH_SIZE: usize = 1000;
+pub(crate) const DEFAULT_INSERT_BATCH_SIZE: usize = 1000;
+
+/// How the rows inserted into a database are grouped in transactions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransactionMode {
+    /// All the rows are inserted in a single transaction.
+    #[default]
+    Single,
+    /// The rows of each chunk of the sample are inserted in their own transaction.
+    Chunk,
+}
 
 //TODO: Remove this once https://github.com/rust-lang/rust/issues/88900 gets fixed
 #[allow(dead_code)]
@@ -21,6 +29,10 @@ pub struct ColumnInfo {
     pub(crate) is_custom_type: bool,
     pub(crate) data_type: String,
     pub(crate) character_maximum_length: Option<i32>,
+    /// The values of the column's type, if it is an enumerated type (or an array of one)
+    pub(crate) enum_values: Option<Vec<String>>,
+    /// The definitions of the CHECK constraints on the column alone (or on its domain)
+    pub(crate) check_constraints: Vec<String>,
 }
 
 #[allow(dead_code)]
@@ -30,7 +42,14 @@ pub struct PrimaryKey {
     pub(crate) type_name: String,
 }
 
+/// A column of a unique index, other than the primary key.
 #[derive(Debug)]
+pub struct UniqueKey {
+    pub(crate) index_name: String,
+    pub(crate) column_name: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct ForeignKey {
     pub(crate) from_table: String,
     pub(crate) from_column: String,
@@ -38,6 +57,53 @@ pub struct ForeignKey {
     pub(crate) to_column: String,
 }
 
+/// The columns and keys of a table which inserting into it depends on, queried once per export
+/// with [`get_tables_metadata`].
+#[derive(Debug, Default)]
+pub struct TableMetadata {
+    pub(crate) column_infos: Vec<ColumnInfo>,
+    pub(crate) primary_keys: Vec<PrimaryKey>,
+    /// The foreign keys from the columns of the table.
+    pub(crate) foreign_keys: Vec<ForeignKey>,
+}
+
+/// A nullable foreign key whose values were inserted as NULL, because they reference rows which
+/// were not inserted yet, to be back-filled with [`back_fill`] once they are.
+#[derive(Debug)]
+pub struct DeferredKey {
+    pub(crate) foreign_key: ForeignKey,
+    /// The column identifying the rows to back-fill.
+    row_column: String,
+    /// The identifier of each row to back-fill, and the value of its foreign key.
+    values: Vec<(Value, Value)>,
+}
+
+impl DeferredKey {
+    fn is_self_referencing(&self) -> bool {
+        self.foreign_key.from_table == self.foreign_key.to_table
+    }
+
+    /// Whether the value of the foreign key in `row` references a row which may not be inserted
+    /// yet, rather than nothing or the row itself.
+    ///
+    /// Rows without a value for the column identifying them cannot be back-filled, so their
+    /// foreign key is inserted as is.
+    fn defers(&self, row: &Value) -> bool {
+        let row = row
+            .as_object()
+            .expect("This is always an object (sampler contract)");
+        if !row.contains_key(&self.row_column) {
+            return false;
+        }
+        match row.get(&self.foreign_key.from_column) {
+            None | Some(Value::Null(_)) => false,
+            Some(value) => {
+                !self.is_self_referencing() || row.get(&self.foreign_key.to_column) != Some(value)
+            }
+        }
+    }
+}
+
 /// Wrapper around `Value` since we can't impl `TryFrom` on a struct in a non-owned crate
 #[derive(Debug)]
 pub struct ValueWrapper(pub(crate) Value);
@@ -46,9 +112,9 @@ pub struct ValueWrapper(pub(crate) Value);
 /// their own implementations.
 #[async_trait]
 pub trait SqlxDataSource: DataSource {
-    type DB: Database<Arguments = Self::Arguments, Connection = Self::Connection>;
-    type Arguments: for<'q> Arguments<'q, Database = Self::DB> + for<'q> IntoArguments<'q, Self::DB>;
-    type Connection: Connection<Database = Self::DB>;
+    type DB: Database<Connection = Self::Connection>
+        + for<'q> HasArguments<'q, Arguments: IntoArguments<'q, Self::DB>>;
+    type Connection: Connection<Database = Self::DB> + 'static;
 
     const IDENTIFIER_QUOTE: char;
 
@@ -59,7 +125,10 @@ pub trait SqlxDataSource: DataSource {
     fn get_multithread_pool(&self) -> Pool<Self::DB>;
 
     /// Prepare a single query with data source specifics
-    fn query<'q>(&self, query: &'q str) -> Query<'q, Self::DB, Self::Arguments> {
+    fn query<'q>(
+        &self,
+        query: &'q str,
+    ) -> Query<'q, Self::DB, <Self::DB as HasArguments<'q>>::Arguments> {
         sqlx::query(query)
     }
 
@@ -69,6 +138,10 @@ pub trait SqlxDataSource: DataSource {
     /// Get query for primary keys
     fn get_primary_keys_query(&self) -> &str;
 
+    /// Get query for the columns of the unique indexes of a table, other than its primary key,
+    /// ordered by index name and position of the column in the index
+    fn get_unique_keys_query(&self) -> &str;
+
     /// Get query for foreign keys
     fn get_foreign_keys_query(&self) -> &str;
 
@@ -89,11 +162,70 @@ pub trait SqlxDataSource: DataSource {
     /// Decodes column to our Content
     fn decode_to_content(&self, column_info: &ColumnInfo) -> Result<Content>;
 
+    /// Get the number of rows to insert with each query, for rows of `columns` columns
+    fn get_insert_batch_size(&self, _columns: usize) -> usize {
+        DEFAULT_INSERT_BATCH_SIZE
+    }
+
     /// Get the function arguments for datasource
     fn get_function_argument_placeholder(_current: usize, _index: usize, _value: &Value) -> String {
         "?".to_string()
     }
 
+    /// Insert `rows` of `column_names` into `table_name` in bulk, which is faster than inserting
+    /// them with multi-row INSERT queries. Returns `false` without inserting anything if the data
+    /// source cannot insert the rows this way.
+    async fn bulk_insert(
+        &self,
+        _connection: &mut Self::Connection,
+        _table_name: &str,
+        _column_names: &[&str],
+        _rows: &[Vec<Value>],
+    ) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Get the statements deleting all the rows of `table_names`, which are ordered so that the
+    /// tables referencing others come before them
+    fn get_truncate_tables_statements(&self, table_names: &[&str]) -> Vec<String> {
+        table_names
+            .iter()
+            .map(|table_name| format!("DELETE FROM {}", self.get_table_name_for_insert(table_name)))
+            .collect()
+    }
+
+    /// Get the clause of an insert query into `column_names` which replaces the existing rows
+    /// with the same `primary_keys`
+    fn get_upsert_clause(&self, column_names: &[&str], primary_keys: &[String]) -> String {
+        let quote = |column: &str| {
+            format!(
+                "{}{}{}",
+                Self::IDENTIFIER_QUOTE,
+                column,
+                Self::IDENTIFIER_QUOTE
+            )
+        };
+        let conflict = primary_keys
+            .iter()
+            .map(|key| quote(key))
+            .collect::<Vec<_>>()
+            .join(",");
+        let updates = column_names
+            .iter()
+            .filter(|column| !primary_keys.iter().any(|key| key == *column))
+            .map(|column| format!("{} = EXCLUDED.{}", quote(column), quote(column)))
+            .collect::<Vec<_>>();
+
+        if updates.is_empty() {
+            format!("ON CONFLICT ({conflict}) DO NOTHING")
+        } else {
+            format!(
+                "ON CONFLICT ({conflict}) DO UPDATE SET {}",
+                updates.join(",")
+            )
+        }
+    }
+
     // Returns extended query string + current index
     fn extend_parameterised_query(query: &mut String, curr_index: usize, query_params: Vec<Value>) {
         let extend = query_params.len();
@@ -112,6 +244,7 @@ pub trait SqlxDataSource: DataSource {
 
     async fn execute_query(
         &self,
+        connection: &mut Self::Connection,
         query: String,
         query_params: Vec<Value>,
     ) -> Result<<Self::DB as Database>::QueryResult>
@@ -126,36 +259,80 @@ pub trait SqlxDataSource: DataSource {
             query = query.bind(param);
         }
 
-        let result = query.execute(&self.get_multithread_pool()).await?;
+        let result = query.execute(connection).await?;
 
         Ok(result)
     }
 }
 
+/// Insert `collection` into the table `collection_name`, running all the queries on `connection`
+/// so that they can be part of a transaction.
+///
+/// With [`WriteMode::Upsert`], the rows whose primary key already exists are replaced. Deleting
+/// the existing rows for [`WriteMode::Truncate`] is left to [`truncate_tables`], since it must
+/// happen for all the tables before inserting into any of them.
+///
+/// Nullable foreign keys referencing the same table, or one of `pending_tables` which are inserted
+/// after it, are inserted as NULL. Those referencing the same table are back-filled once all the
+/// rows are in, the others are returned to be back-filled once their table is inserted.
 pub async fn insert_relational_data<T: SqlxDataSource + Sync>(
     datasource: &T,
+    connection: &mut T::Connection,
     collection_name: &str,
     collection: &[Value],
-) -> Result<()>
+    metadata: &TableMetadata,
+    write_mode: WriteMode,
+    pending_tables: &[&str],
+) -> Result<Vec<DeferredKey>>
 where
     for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
-    String: Type<T::DB>,
-    for<'d> String: Encode<'d, T::DB>,
-    ColumnInfo: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
     Value: Type<T::DB>,
     for<'d> Value: Encode<'d, T::DB>,
 {
-    let batch_size = DEFAULT_INSERT_BATCH_SIZE;
-
     if collection.is_empty() {
         println!("Collection {collection_name} generated 0 values. Skipping insertion...",);
-        return Ok(());
+        return Ok(Vec::new());
     }
 
-    let column_infos = get_columns_info(datasource, collection_name.to_string()).await?;
+    let column_infos = &metadata.column_infos;
     let first_valueset = collection[0]
         .as_object()
         .expect("This is always an object (sampler contract)");
+    let batch_size = datasource.get_insert_batch_size(first_valueset.len());
+
+    // Rows may reference rows of the same table which are inserted after them (or in another
+    // batch), or rows of tables inserted after this one (when foreign keys form a cycle), so
+    // nullable foreign keys to those are inserted as NULL and back-filled later. The rows are
+    // identified by the referenced column for keys to the same table, and by their primary key
+    // otherwise.
+    let mut deferred_keys = Vec::new();
+    for foreign_key in &metadata.foreign_keys {
+        let is_nullable = column_infos.iter().any(|column_info| {
+            column_info.column_name == foreign_key.from_column && column_info.is_nullable
+        });
+        if !is_nullable {
+            continue;
+        }
+
+        let row_column = if foreign_key.to_table == collection_name {
+            foreign_key.to_column.clone()
+        } else if pending_tables.contains(&foreign_key.to_table.as_str()) {
+            match metadata.primary_keys.as_slice() {
+                [primary_key] => primary_key.column_name.clone(),
+                _ => continue,
+            }
+        } else {
+            continue;
+        };
+
+        if first_valueset.contains_key(&row_column) {
+            deferred_keys.push(DeferredKey {
+                foreign_key: foreign_key.clone(),
+                row_column,
+                values: Vec::new(),
+            });
+        }
+    }
 
     for column_info in column_infos {
         if let Some(value) = first_valueset.get(&column_info.column_name) {
@@ -202,53 +379,339 @@ where
         }
     }
 
-    let column_names = first_valueset
-        .keys()
+    let column_names: Vec<&str> = first_valueset.keys().map(String::as_str).collect();
+    let upsert_clause = match write_mode {
+        WriteMode::Upsert => {
+            let primary_keys: Vec<String> = metadata
+                .primary_keys
+                .iter()
+                .map(|primary_key| primary_key.column_name.clone())
+                .collect();
+            if primary_keys.is_empty() {
+                bail!("Cannot upsert into table {collection_name} as it has no primary key");
+            }
+            Some(datasource.get_upsert_clause(&column_names, &primary_keys))
+        }
+        WriteMode::Append | WriteMode::Truncate => None,
+    };
+
+    let row_values = |row: &Value| -> Vec<Value> {
+        row.as_object()
+            .expect("This is always an object (sampler contract)")
+            .iter()
+            .map(|(column, value)| {
+                let is_deferred = deferred_keys.iter().any(|deferred_key| {
+                    deferred_key.foreign_key.from_column == *column && deferred_key.defers(row)
+                });
+                if is_deferred {
+                    Value::Null(())
+                } else {
+                    value.clone()
+                }
+            })
+            .collect()
+    };
+
+    // Collections which would take more than one query to insert are inserted in bulk instead
+    // when possible, which does not support replacing existing rows
+    let is_bulk_inserted = upsert_clause.is_none()
+        && collection.len() > batch_size
+        && datasource
+            .bulk_insert(
+                &mut *connection,
+                collection_name,
+                &column_names,
+                &collection.iter().map(row_values).collect::<Vec<_>>(),
+            )
+            .await
+            .context("Failed to bulk insert the rows")?;
+
+    let quoted_column_names = column_names
+        .iter()
         .map(|k| format!("{}{}{}", T::IDENTIFIER_QUOTE, k, T::IDENTIFIER_QUOTE))
         .collect::<Vec<String>>()
         .join(",");
 
-    let mut futures = Vec::with_capacity(collection.len());
+    if !is_bulk_inserted {
+        for rows in collection.chunks(batch_size) {
+            let table_name = datasource.get_table_name_for_insert(collection_name);
+            let mut query = format!("INSERT INTO {table_name} ({quoted_column_names}) VALUES \n");
+
+            let mut curr_index = 0;
+            let mut query_params = vec![];
+
+            for (i, row) in rows.iter().enumerate() {
+                let mut curr_query_params = row_values(row);
+                T::extend_parameterised_query(&mut query, curr_index, curr_query_params.clone());
+                curr_index += curr_query_params.len();
+                query_params.append(&mut curr_query_params);
+
+                if i != rows.len() - 1 {
+                    query.push_str(",\n");
+                }
+            }
+            if let Some(upsert_clause) = &upsert_clause {
+                query.push('\n');
+                query.push_str(upsert_clause);
+            }
+            query.push_str(";\n");
+            datasource
+                .execute_query(&mut *connection, query, query_params)
+                .await
+                .context("Failed to insert a batch of rows")?;
+        }
+    }
 
-    for rows in collection.chunks(batch_size) {
-        let table_name = datasource.get_table_name_for_insert(collection_name);
-        let mut query = format!("INSERT INTO {table_name} ({column_names}) VALUES \n");
+    info!("Inserted {} rows...", collection.len());
 
-        let mut curr_index = 0;
-        let mut query_params = vec![];
+    for deferred_key in &mut deferred_keys {
+        deferred_key.values = collection
+            .iter()
+            .filter(|row| deferred_key.defers(row))
+            .filter_map(|row| {
+                let row = row
+                    .as_object()
+                    .expect("This is always an object (sampler contract)");
+                Some((
+                    row.get(&deferred_key.row_column)?.clone(),
+                    row.get(&deferred_key.foreign_key.from_column)?.clone(),
+                ))
+            })
+            .collect();
+    }
 
-        for (i, row) in rows.iter().enumerate() {
-            let row_obj = row
-                .as_object()
-                .expect("This is always an object (sampler contract)");
+    let (self_referencing, other_keys): (Vec<_>, Vec<_>) = deferred_keys
+        .into_iter()
+        .partition(DeferredKey::is_self_referencing);
+    for deferred_key in &self_referencing {
+        back_fill(datasource, &mut *connection, deferred_key)
+            .await
+            .context("Failed to back-fill a self-referencing foreign key")?;
+    }
 
-            let mut curr_query_params: Vec<Value> = row_obj.values().cloned().collect();
-            T::extend_parameterised_query(&mut query, curr_index, curr_query_params.clone());
-            curr_index += curr_query_params.len();
-            query_params.append(&mut curr_query_params);
+    Ok(other_keys)
+}
 
-            if i == rows.len() - 1 {
-                query.push_str(";\n");
-            } else {
-                query.push_str(",\n");
-            }
+/// Set the values of a foreign key which were inserted as NULL, once the rows they reference are
+/// inserted, with a query per batch of rows.
+pub async fn back_fill<T: SqlxDataSource + Sync>(
+    datasource: &T,
+    connection: &mut T::Connection,
+    deferred_key: &DeferredKey,
+) -> Result<()>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    Value: Type<T::DB>,
+    for<'d> Value: Encode<'d, T::DB>,
+{
+    let table_name = datasource.get_table_name_for_insert(&deferred_key.foreign_key.from_table);
+    let quote = |column: &str| format!("{}{}{}", T::IDENTIFIER_QUOTE, column, T::IDENTIFIER_QUOTE);
+    let from_column = quote(&deferred_key.foreign_key.from_column);
+    let row_column = quote(&deferred_key.row_column);
+
+    // Three parameters per row: its identifier and foreign key for the `CASE`, and its identifier
+    // again for the `WHERE`
+    for rows in deferred_key
+        .values
+        .chunks(datasource.get_insert_batch_size(3))
+    {
+        let mut query_params = Vec::with_capacity(rows.len() * 3);
+        let mut cases = String::new();
+        for (row, value) in rows {
+            cases.push_str(&format!(
+                " WHEN {} THEN {}",
+                T::get_function_argument_placeholder(query_params.len(), 0, row),
+                T::get_function_argument_placeholder(query_params.len(), 1, value)
+            ));
+            query_params.push(row.clone());
+            query_params.push(value.clone());
+        }
+        let mut keys = Vec::with_capacity(rows.len());
+        for (row, _) in rows {
+            keys.push(T::get_function_argument_placeholder(
+                query_params.len(),
+                0,
+                row,
+            ));
+            query_params.push(row.clone());
         }
-        let future = datasource.execute_query(query, query_params);
-        futures.push(future);
+
+        // The `ELSE` branch gives the `CASE` the type of the column, which the parameters are
+        // converted to
+        let query = format!(
+            "UPDATE {table_name} SET {from_column} = CASE {row_column}{cases} ELSE {from_column} END WHERE {row_column} IN ({})",
+            keys.join(",")
+        );
+        datasource
+            .execute_query(&mut *connection, query, query_params)
+            .await?;
     }
 
-    let results = join_all(futures).await;
+    Ok(())
+}
 
-    if let Err(e) = results.into_iter().bcollect::<Vec<_>>() {
-        bail!("One or more database inserts failed: {:?}", e)
+/// Execute `statements` one after the other, e.g. to create tables before inserting into them.
+pub async fn execute_statements<T: SqlxDataSource + Sync>(
+    datasource: &T,
+    statements: &[String],
+) -> Result<()>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    Value: Type<T::DB>,
+    for<'d> Value: Encode<'d, T::DB>,
+{
+    let mut connection = datasource.get_pool().acquire().await?;
+    for statement in statements {
+        datasource
+            .execute_query(&mut connection, statement.clone(), Vec::new())
+            .await
+            .with_context(|| format!("Failed to execute statement:\n{statement}"))?;
+    }
+
+    Ok(())
+}
+
+/// Delete all the rows of `table_names`, which are ordered so that the tables referencing others
+/// come before them.
+pub async fn truncate_tables<T: SqlxDataSource + Sync>(
+    datasource: &T,
+    connection: &mut T::Connection,
+    table_names: &[&str],
+) -> Result<()>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    Value: Type<T::DB>,
+    for<'d> Value: Encode<'d, T::DB>,
+{
+    for statement in datasource.get_truncate_tables_statements(table_names) {
+        datasource
+            .execute_query(&mut *connection, statement.clone(), Vec::new())
+            .await
+            .with_context(|| format!("Failed to truncate the tables with:\n{statement}"))?;
     }
 
-    info!("Inserted {} rows...", collection.len());
     Ok(())
 }
 
+/// Gets the metadata of each of `table_names`, querying the foreign keys of the database once for
+/// all of them.
+pub async fn get_tables_metadata<T: SqlxDataSource>(
+    datasource: &T,
+    connection: &mut T::Connection,
+    table_names: &[&str],
+) -> Result<BTreeMap<String, TableMetadata>>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    String: Type<T::DB>,
+    for<'d> String: Encode<'d, T::DB>,
+    ColumnInfo: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    PrimaryKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+    ForeignKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+{
+    let foreign_keys = get_foreign_keys(datasource, &mut *connection).await?;
+
+    let mut metadata = BTreeMap::new();
+    for table_name in table_names {
+        let table_metadata = TableMetadata {
+            column_infos: get_columns_info(datasource, &mut *connection, table_name.to_string())
+                .await?,
+            primary_keys: get_primary_keys(datasource, &mut *connection, table_name.to_string())
+                .await?,
+            foreign_keys: foreign_keys
+                .iter()
+                .filter(|foreign_key| foreign_key.from_table == *table_name)
+                .cloned()
+                .collect(),
+        };
+        metadata.insert(table_name.to_string(), table_metadata);
+    }
+
+    Ok(metadata)
+}
+
+pub async fn get_primary_keys<T: SqlxDataSource>(
+    datasource: &T,
+    connection: &mut T::Connection,
+    table_name: String,
+) -> Result<Vec<PrimaryKey>>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    String: Type<T::DB>,
+    for<'d> String: Encode<'d, T::DB>,
+    PrimaryKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+{
+    let query = datasource.get_primary_keys_query();
+
+    datasource
+        .query(query)
+        .bind(table_name)
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(PrimaryKey::try_from)
+        .collect()
+}
+
+/// Gets the columns of each unique index of `table_name`, other than its primary key.
+pub async fn get_unique_keys<T: SqlxDataSource>(
+    datasource: &T,
+    connection: &mut T::Connection,
+    table_name: String,
+) -> Result<Vec<Vec<String>>>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    String: Type<T::DB>,
+    for<'d> String: Encode<'d, T::DB>,
+    UniqueKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+{
+    let query = datasource.get_unique_keys_query();
+
+    let unique_keys = datasource
+        .query(query)
+        .bind(table_name)
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(UniqueKey::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut indexes: Vec<(String, Vec<String>)> = Vec::new();
+    for UniqueKey {
+        index_name,
+        column_name,
+    } in unique_keys
+    {
+        match indexes.last_mut() {
+            Some((name, columns)) if *name == index_name => columns.push(column_name),
+            _ => indexes.push((index_name, vec![column_name])),
+        }
+    }
+
+    Ok(indexes.into_iter().map(|(_, columns)| columns).collect())
+}
+
+pub async fn get_foreign_keys<T: SqlxDataSource>(
+    datasource: &T,
+    connection: &mut T::Connection,
+) -> Result<Vec<ForeignKey>>
+where
+    for<'c> &'c mut T::Connection: Executor<'c, Database = T::DB>,
+    ForeignKey: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
+{
+    let query = datasource.get_foreign_keys_query();
+
+    datasource
+        .query(query)
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(ForeignKey::try_from)
+        .collect()
+}
+
 pub async fn get_columns_info<T: SqlxDataSource>(
     datasource: &T,
+    connection: &mut T::Connection,
     table_name: String,
 ) -> Result<Vec<ColumnInfo>>
 where
@@ -258,14 +721,52 @@ where
     ColumnInfo: TryFrom<<T::DB as Database>::Row, Error = anyhow::Error>,
 {
     let query = datasource.get_columns_info_query();
-    let pool = datasource.get_pool();
 
     datasource
         .query(query)
         .bind(table_name)
-        .fetch_all(&pool)
+        .fetch_all(connection)
         .await?
         .into_iter()
         .map(ColumnInfo::try_from)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(fields: &[(&str, i64)]) -> Value {
+        let fields: BTreeMap<String, Value> = fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), Value::Number(Number::I64(*value))))
+            .collect();
+        Value::Object(fields)
+    }
+
+    #[test]
+    fn deferred_rows() {
+        let deferred_key = |to_table: &str, row_column: &str| DeferredKey {
+            foreign_key: ForeignKey {
+                from_table: "employees".to_string(),
+                from_column: "manager".to_string(),
+                to_table: to_table.to_string(),
+                to_column: "id".to_string(),
+            },
+            row_column: row_column.to_string(),
+            values: Vec::new(),
+        };
+
+        let self_referencing = deferred_key("employees", "id");
+        assert!(self_referencing.defers(&row(&[("id", 1), ("manager", 2)])));
+        // Rows referencing themselves or nothing are inserted as they are
+        assert!(!self_referencing.defers(&row(&[("id", 1), ("manager", 1)])));
+        assert!(!self_referencing.defers(&row(&[("id", 1)])));
+        // So are the rows which cannot be identified to back-fill them
+        assert!(!self_referencing.defers(&row(&[("manager", 2)])));
+
+        let cyclic = deferred_key("managers", "code");
+        assert!(cyclic.defers(&row(&[("code", 1), ("manager", 1)])));
+        assert!(!cyclic.defers(&row(&[("id", 1), ("manager", 1)])));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::datasource::relational_datasource::{
+    get_tables_metadata, insert_relational_data, ColumnInfo, ForeignKey, PrimaryKey,
+    SqlxDataSource, UniqueKey, ValueWrapper, DEFAULT_INSERT_BATCH_SIZE,
+};
+use crate::datasource::{DataSource, WriteMode};
+use anyhow::{Context, Result};
+use async_std::task;
+use async_trait::async_trait;
+use sqlx::sqlite::{SqliteColumn, SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
+use sqlx::{Column, Pool, Row, Sqlite, TypeInfo, ValueRef};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use synth_core::schema::number_content::{F64, I64};
+use synth_core::schema::{
+    BoolContent, ChronoValueType, DateTimeContent, NumberContent, RangeStep, RegexContent,
+    StringContent,
+};
+use synth_core::{Content, Value};
+use synth_gen::prelude::*;
+
+/// The maximum number of parameters of a single query, since SQLite 3.32.0.
+const MAX_QUERY_PARAMETERS: usize = 32766;
+
+pub struct SqliteDataSource {
+    pool: Pool<Sqlite>,
+}
+
+#[async_trait]
+impl DataSource for SqliteDataSource {
+    type ConnectParams = String;
+
+    fn new(connect_params: &Self::ConnectParams) -> Result<Self> {
+        task::block_on(async {
+            let options = SqliteConnectOptions::from_str(connect_params)?;
+            // SQLite only allows a single writer at a time
+            let pool = SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect_with(options)
+                .await?;
+
+            Ok::<Self, anyhow::Error>(SqliteDataSource { pool })
+        })
+    }
+
+    async fn insert_data(&self, collection_name: &str, collection: &[Value]) -> Result<()> {
+        let mut connection = self.get_multithread_pool().acquire().await?;
+        let metadata = get_tables_metadata(self, &mut connection, &[collection_name])
+            .await?
+            .remove(collection_name)
+            .unwrap_or_default();
+        insert_relational_data(
+            self,
+            &mut connection,
+            collection_name,
+            collection,
+            &metadata,
+            WriteMode::Append,
+            &[],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+impl SqlxDataSource for SqliteDataSource {
+    type DB = Sqlite;
+    type Connection = sqlx::sqlite::SqliteConnection;
+
+    const IDENTIFIER_QUOTE: char = '\"';
+
+    fn get_pool(&self) -> Pool<Self::DB> {
+        Pool::clone(&self.pool)
+    }
+
+    fn get_multithread_pool(&self) -> Pool<Self::DB> {
+        Pool::clone(&self.pool)
+    }
+
+    fn get_insert_batch_size(&self, columns: usize) -> usize {
+        (MAX_QUERY_PARAMETERS / columns.max(1)).clamp(1, DEFAULT_INSERT_BATCH_SIZE)
+    }
+
+    fn get_table_names_query(&self) -> &str {
+        r"SELECT name FROM sqlite_master
+            WHERE type = 'table' AND name NOT LIKE 'sqlite_%'
+            ORDER BY name"
+    }
+
+    fn get_primary_keys_query(&self) -> &str {
+        r"SELECT name, type FROM pragma_table_info(?) WHERE pk > 0 ORDER BY pk"
+    }
+
+    fn get_unique_keys_query(&self) -> &str {
+        // Expression key parts have no column name
+        r#"SELECT l.name, i.name
+            FROM pragma_index_list(?) l JOIN pragma_index_info(l.name) i
+            WHERE l."unique" AND l.origin <> 'pk' AND NOT l.partial
+            AND NOT EXISTS (SELECT 1 FROM pragma_index_info(l.name) WHERE name IS NULL)
+            ORDER BY l.name, i.seqno"#
+    }
+
+    fn get_foreign_keys_query(&self) -> &str {
+        // A foreign key without columns references the primary key of the other table
+        r#"SELECT m.name, f."from", f."table",
+            COALESCE(f."to", (SELECT p.name FROM pragma_table_info(f."table") p WHERE p.pk = 1))
+            FROM sqlite_master m JOIN pragma_foreign_key_list(m.name) f
+            WHERE m.type = 'table'"#
+    }
+
+    fn get_deterministic_samples_query(&self, table_name: String) -> String {
+        // There is no way to seed `random()` in SQLite
+        format!(
+            "SELECT * FROM {} LIMIT 10",
+            self.get_table_name_for_insert(&table_name)
+        )
+    }
+
+    fn get_table_name_for_insert(&self, table_name: &str) -> String {
+        format!("\"{}\"", table_name.replace('"', "\"\""))
+    }
+
+    fn decode_to_content(&self, column_info: &ColumnInfo) -> Result<Content> {
+        let data_type = column_info.data_type.to_uppercase();
+
+        // Types which SQLite has no storage class for, but which are commonly declared and
+        // understood by sqlx
+        let content = match data_type.as_str() {
+            "BOOLEAN" | "BOOL" => Content::Bool(BoolContent::default()),
+            "DATE" => Content::DateTime(DateTimeContent {
+                format: "%Y-%m-%d".to_string(),
+                type_: ChronoValueType::NaiveDate,
+                begin: None,
+                end: None,
+            }),
+            "DATETIME" | "TIMESTAMP" => Content::DateTime(DateTimeContent {
+                format: "%Y-%m-%d %H:%M:%S".to_string(),
+                type_: ChronoValueType::NaiveDateTime,
+                begin: None,
+                end: None,
+            }),
+            "TIME" => Content::DateTime(DateTimeContent {
+                format: "%H:%M:%S".to_string(),
+                type_: ChronoValueType::NaiveTime,
+                begin: None,
+                end: None,
+            }),
+            // Otherwise, follow the rules of type affinity
+            // (https://www.sqlite.org/datatype3.html#determination_of_column_affinity)
+            t if t.contains("INT") => {
+                Content::Number(NumberContent::I64(I64::Range(RangeStep::default())))
+            }
+            t if ["CHAR", "CLOB", "TEXT", "BLOB"]
+                .iter()
+                .any(|s| t.contains(s))
+                || t.is_empty() =>
+            {
+                let pattern = "[a-zA-Z0-9]{0, {}}".replace(
+                    "{}",
+                    &format!("{}", column_info.character_maximum_length.unwrap_or(1)),
+                );
+                Content::String(StringContent::Pattern(
+                    RegexContent::pattern(pattern).context("pattern will always compile")?,
+                ))
+            }
+            // Both REAL and NUMERIC affinities
+            _ => Content::Number(NumberContent::F64(F64::Range(RangeStep::default()))),
+        };
+
+        Ok(content)
+    }
+
+    fn get_columns_info_query(&self) -> &str {
+        r#"SELECT name, cid + 1, "notnull" = 0 AND pk = 0, type
+            FROM pragma_table_info(?)
+            ORDER BY cid"#
+    }
+}
+
+impl TryFrom<SqliteRow> for ColumnInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        let data_type = row.try_get::<String, usize>(3)?;
+        Ok(ColumnInfo {
+            column_name: row.try_get::<String, usize>(0)?,
+            ordinal_position: row.try_get::<i32, usize>(1)?,
+            is_nullable: row.try_get::<bool, usize>(2)?,
+            character_maximum_length: extract_column_char_max_len(&data_type),
+            data_type,
+            is_custom_type: false,
+            enum_values: None,
+            check_constraints: Vec::new(),
+        })
+    }
+}
+
+/// Extracts a column's max character length from its declared type, e.g. 255 for
+/// `VARCHAR(255)`. SQLite does not enforce it, but it is a good hint of what to generate.
+fn extract_column_char_max_len(data_type: &str) -> Option<i32> {
+    let (_, length) = data_type.split_once('(')?;
+    length.strip_suffix(')')?.trim().parse().ok()
+}
+
+impl TryFrom<SqliteRow> for PrimaryKey {
+    type Error = anyhow::Error;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        Ok(PrimaryKey {
+            column_name: row.try_get(0)?,
+            type_name: row.try_get(1)?,
+        })
+    }
+}
+
+impl TryFrom<SqliteRow> for UniqueKey {
+    type Error = anyhow::Error;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        Ok(UniqueKey {
+            index_name: row.try_get(0)?,
+            column_name: row.try_get(1)?,
+        })
+    }
+}
+
+impl TryFrom<SqliteRow> for ForeignKey {
+    type Error = anyhow::Error;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        Ok(ForeignKey {
+            from_table: row.try_get(0)?,
+            from_column: row.try_get(1)?,
+            to_table: row.try_get(2)?,
+            to_column: row.try_get(3)?,
+        })
+    }
+}
+
+impl TryFrom<SqliteRow> for ValueWrapper {
+    type Error = anyhow::Error;
+
+    fn try_from(row: SqliteRow) -> Result<Self, Self::Error> {
+        let mut kv = BTreeMap::new();
+
+        for column in row.columns() {
+            let value = try_match_value(&row, column).unwrap_or(Value::Null(()));
+            kv.insert(column.name().to_string(), value);
+        }
+
+        Ok(ValueWrapper(Value::Object(kv)))
+    }
+}
+
+fn try_match_value(row: &SqliteRow, column: &SqliteColumn) -> Result<Value> {
+    let raw = row.try_get_raw(column.ordinal())?;
+    if raw.is_null() {
+        return Ok(Value::Null(()));
+    }
+
+    // Any column can hold values of any type, so rely on the declared type only when it is not
+    // one of SQLite's storage classes
+    let value = match column.type_info().name() {
+        "BOOLEAN" => Value::Bool(row.try_get::<bool, usize>(column.ordinal())?),
+        _ => match raw.type_info().name() {
+            "INTEGER" => Value::Number(Number::from(row.try_get::<i64, usize>(column.ordinal())?)),
+            "REAL" => Value::Number(Number::from(row.try_get::<f64, usize>(column.ordinal())?)),
+            "TEXT" => Value::String(row.try_get::<String, usize>(column.ordinal())?),
+            _ => {
+                bail!(
+                    "Could not convert value. Converter not implemented for {}",
+                    raw.type_info().name()
+                );
+            }
+        },
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_column_char_max_len() {
+        assert_eq!(extract_column_char_max_len("VARCHAR(255)"), Some(255));
+        assert_eq!(extract_column_char_max_len("NUMERIC(10, 2)"), None);
+        assert_eq!(extract_column_char_max_len("TEXT"), None);
+    }
+}
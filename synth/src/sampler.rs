@@ -1,15 +1,29 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
-use rand::SeedableRng;
-use std::collections::BTreeMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::thread;
+use synth_core::compile::NamespaceCompiler;
 use synth_core::graph::json::synth_val_to_json;
-use synth_core::{Graph, Namespace, Value};
+use synth_core::graph::unsigned_from_ok;
+use synth_core::{Content, Graph, Namespace, Partition, Value};
 use synth_gen::prelude::*;
 
+/// The number of values generated before a chunk is handed over to the exporter.
+const CHUNK_SIZE: usize = 10_000;
+
 pub(crate) struct Sampler {
+    namespace: Namespace,
     graph: Graph,
+    threads: usize,
+    /// The number of elements of each collection, when set with
+    /// [`Sampler::with_collection_sizes`].
+    sizes: Option<BTreeMap<String, usize>>,
 }
 
+/// A chunk of generated values.
 #[derive(Clone)]
 pub(crate) enum SamplerOutput {
     Namespace(Vec<(String, Value)>),
@@ -27,6 +41,83 @@ impl SamplerOutput {
         };
         synth_val_to_json(as_synth)
     }
+
+    /// The values of each collection in this chunk.
+    pub(crate) fn into_collections(self) -> Vec<(String, Value)> {
+        match self {
+            Self::Namespace(key_values) => key_values,
+            Self::Collection(name, value) => vec![(name, value)],
+        }
+    }
+
+    /// Append the values of a later chunk to this one.
+    fn extend(&mut self, other: Self) {
+        match (self, other) {
+            (Self::Namespace(key_values), Self::Namespace(others)) => {
+                for (name, value) in others {
+                    match key_values
+                        .iter_mut()
+                        .find(|(existing, _)| *existing == name)
+                    {
+                        Some((_, existing)) => extend_value(existing, value),
+                        None => key_values.push((name, value)),
+                    }
+                }
+            }
+            (Self::Collection(_, existing), Self::Collection(_, value)) => {
+                extend_value(existing, value)
+            }
+            _ => unreachable!("chunks of a sample are either all namespaces or all collections"),
+        }
+    }
+}
+
+fn extend_value(existing: &mut Value, value: Value) {
+    match (existing, value) {
+        (Value::Array(to_extend), Value::Array(elements)) => to_extend.extend(elements),
+        (existing, value) => *existing = value,
+    }
+}
+
+/// A sample being generated, as an iterator over chunks of [`SamplerOutput`].
+///
+/// Values are only generated when the next chunk is requested, so that exporters can write each
+/// chunk before the following one is generated and memory usage stays bounded regardless of the
+/// size of the sample. There is always at least one chunk.
+pub(crate) struct Sample<'a> {
+    chunks: Box<dyn Iterator<Item = Result<SamplerOutput>> + 'a>,
+}
+
+impl<'a> Sample<'a> {
+    pub(crate) fn new<I>(chunks: I) -> Self
+    where
+        I: Iterator<Item = Result<SamplerOutput>> + 'a,
+    {
+        Self {
+            chunks: Box::new(chunks),
+        }
+    }
+
+    /// Generate the whole sample as a single chunk, for exporters which cannot write it in parts.
+    pub(crate) fn into_output(self) -> Result<SamplerOutput> {
+        let mut output: Option<SamplerOutput> = None;
+        for chunk in self {
+            let chunk = chunk?;
+            match &mut output {
+                Some(output) => output.extend(chunk),
+                None => output = Some(chunk),
+            }
+        }
+        Ok(output.expect("a sample always has at least one chunk"))
+    }
+}
+
+impl Iterator for Sample<'_> {
+    type Item = Result<SamplerOutput>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next()
+    }
 }
 
 fn sampler_progress_bar(target: u64) -> ProgressBar {
@@ -38,15 +129,109 @@ fn sampler_progress_bar(target: u64) -> ProgressBar {
 }
 
 impl Sampler {
+    /// Generate values on `threads` threads at once.
+    ///
+    /// With more than one thread, the sample is split into chunks of at most [`CHUNK_SIZE`]
+    /// values, each generated from its own seed derived from the seed of the sample. The output
+    /// is therefore reproducible for a given seed and number of threads, but differs from the
+    /// output generated with another number of threads.
+    pub(crate) fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Generate exactly `size` elements of each `collection` in `sizes`.
+    ///
+    /// The model is completed once per chunk, with the elements of each collection which is an
+    /// array split evenly between the chunks, so that references between collections (e.g. with
+    /// `same_as`) stay valid within each chunk. Collections without a size are generated once,
+    /// with a length drawn from their schema, and split in the same way. The smallest collections
+    /// are generated once in a first chunk of their own instead, and stay visible to the
+    /// references of all the following chunks (see [`shared_collections`]). The `target` given to
+    /// [`Sampler::sample_seeded`] is then ignored.
+    pub(crate) fn with_collection_sizes(mut self, sizes: &BTreeMap<String, usize>) -> Result<Self> {
+        for collection in sizes.keys() {
+            match self.namespace.get_collection(collection)? {
+                Content::Array(array) if array.has_relation()? => {
+                    return Err(anyhow!(
+                        "cannot set the size of collection '{}': its length is given by the cardinality of its parents",
+                        collection
+                    ))
+                }
+                Content::Array(_) => {}
+                _ => {
+                    return Err(anyhow!(
+                        "cannot set the size of collection '{}': it is not an array",
+                        collection
+                    ))
+                }
+            }
+        }
+
+        self.sizes = Some(sizes.clone());
+        Ok(self)
+    }
+
     pub(crate) fn sample_seeded(
         self,
         collection_name: Option<String>,
         target: usize,
         seed: u64,
-    ) -> Result<SamplerOutput> {
-        let rng = rand::rngs::StdRng::seed_from_u64(seed);
-        let sample_strategy = SampleStrategy::new(collection_name, target);
-        sample_strategy.sample(self.graph, rng)
+    ) -> Result<Sample<'static>> {
+        let (target, progress_len) = match &self.sizes {
+            Some(sizes) => plan_chunks(&self.namespace, sizes, seed)?,
+            None => (Target::Values(target), target),
+        };
+
+        let ordered: Vec<_> = self
+            .graph
+            .iter_ordered()
+            .map(|iter| iter.map(|s| s.to_string()).collect())
+            .unwrap_or_default();
+
+        let progress_bar = sampler_progress_bar(progress_len as u64);
+        let assembler = ChunkAssembler {
+            collection_name: collection_name.clone(),
+            ordered,
+            target: progress_len,
+            singletons: BTreeMap::new(),
+            progress_bar: progress_bar.clone(),
+        };
+
+        let source = if self.threads == 1 {
+            let model = match &target {
+                Target::Values(_) => self.graph,
+                Target::Chunks { shared, .. } => compile_model(
+                    &self.namespace,
+                    Partition::default(),
+                    target.lengths(0..target.n_chunks()),
+                    shared.clone(),
+                )?,
+            };
+            RoundsSource::Sequential {
+                model: model.aggregate(),
+                rng: Box::new(StdRng::seed_from_u64(seed)),
+                collection_name,
+                target,
+                done: 0,
+                progress_bar,
+            }
+        } else {
+            RoundsSource::parallel(
+                self.namespace,
+                self.threads,
+                collection_name,
+                target,
+                seed,
+                progress_bar,
+            )
+        };
+
+        Ok(Sample::new(Chunks {
+            source,
+            assembler,
+            is_done: false,
+        }))
     }
 }
 
@@ -54,144 +239,553 @@ impl TryFrom<&Namespace> for Sampler {
     type Error = anyhow::Error;
     fn try_from(namespace: &Namespace) -> Result<Self> {
         Ok(Self {
+            namespace: namespace.clone(),
             graph: Graph::from_namespace(namespace)?,
+            threads: 1,
+            sizes: None,
         })
     }
 }
 
-enum SampleStrategy {
-    Namespace(NamespaceSampleStrategy),
-    Collection(CollectionSampleStrategy),
+/// What a sample generates.
+enum Target {
+    /// At least this many values, in chunks of about [`CHUNK_SIZE`] values.
+    Values(usize),
+    /// A single completion of the model per chunk, in which each collection in `lengths` has the
+    /// length given for the chunk. When there are `shared` collections, they are only generated
+    /// in the first chunk, which the following chunks refer to.
+    Chunks {
+        n_chunks: usize,
+        lengths: BTreeMap<String, Vec<u64>>,
+        shared: BTreeSet<String>,
+    },
 }
 
-impl SampleStrategy {
-    fn new(collection_name: Option<String>, target: usize) -> Self {
-        match collection_name {
-            None => SampleStrategy::Namespace(NamespaceSampleStrategy { target }),
-            Some(name) => SampleStrategy::Collection(CollectionSampleStrategy { name, target }),
+impl Target {
+    fn n_chunks(&self) -> usize {
+        match self {
+            Self::Values(target) => target.div_ceil(CHUNK_SIZE).max(1),
+            Self::Chunks { n_chunks, .. } => *n_chunks,
+        }
+    }
+
+    /// The number of values to generate in chunk `chunk`, when chunks are generated separately.
+    fn chunk_target(&self, chunk: usize) -> usize {
+        match self {
+            Self::Values(target) => CHUNK_SIZE.min(target - chunk * CHUNK_SIZE),
+            // Generating a single value is enough to complete the model once
+            Self::Chunks { .. } => 1,
         }
     }
 
-    fn sample<R: Rng>(self, model: Graph, rng: R) -> Result<SamplerOutput> {
+    /// Whether the first chunk generates the shared collections, which every model generating
+    /// later chunks must also generate first.
+    fn has_shared_chunk(&self) -> bool {
+        matches!(self, Self::Chunks { shared, .. } if !shared.is_empty())
+    }
+
+    fn shared(&self) -> BTreeSet<String> {
         match self {
-            SampleStrategy::Namespace(nss) => Ok(SamplerOutput::Namespace(nss.sample(model, rng)?)),
-            SampleStrategy::Collection(css) => Ok(SamplerOutput::Collection(
-                css.name.clone(),
-                css.sample(model, rng)?,
-            )),
+            Self::Values(_) => BTreeSet::new(),
+            Self::Chunks { shared, .. } => shared.clone(),
         }
     }
-}
 
-struct NamespaceSampleStrategy {
-    target: usize,
+    /// The lengths of the collections in the completions of `chunks`, in order.
+    fn lengths<I: Iterator<Item = usize> + Clone>(&self, chunks: I) -> BTreeMap<String, Vec<u64>> {
+        match self {
+            Self::Values(_) => BTreeMap::new(),
+            Self::Chunks { lengths, .. } => lengths
+                .iter()
+                .map(|(collection, lengths)| {
+                    let lengths = chunks.clone().map(|chunk| lengths[chunk]).collect();
+                    (collection.clone(), lengths)
+                })
+                .collect(),
+        }
+    }
 }
 
-impl NamespaceSampleStrategy {
-    fn sample<R: Rng>(self, model: Graph, mut rng: R) -> Result<Vec<(String, Value)>> {
-        let mut generated = 0;
-        let mut out = BTreeMap::<String, Value>::new();
-        let progress_bar = sampler_progress_bar(self.target as u64);
+/// Split the elements of each collection which is an array evenly between chunks of about
+/// [`CHUNK_SIZE`] values, and return the number of values to generate.
+///
+/// Collections without a size get a length drawn from their schema, except those whose length is
+/// given by the cardinality of their parents. When the sample does not fit in a single chunk, the
+/// [`shared_collections`] are generated whole in a first chunk instead. A collection referred to
+/// by other collections still has at least one element in every other chunk, so there are no more
+/// of them than elements in the smallest such collection.
+fn plan_chunks(
+    namespace: &Namespace,
+    sizes: &BTreeMap<String, usize>,
+    seed: u64,
+) -> Result<(Target, usize)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut totals = BTreeMap::new();
+    let mut singletons = 0;
+    for (collection, content) in namespace.iter() {
+        let array = match content {
+            Content::Array(array) if !array.has_relation()? => array,
+            Content::Array(_) => continue,
+            _ => {
+                singletons += 1;
+                continue;
+            }
+        };
+        let total = match sizes.get(collection) {
+            Some(size) => *size as u64,
+            None => Graph::from_content(&array.length)
+                .and_then(|mut length| Ok(unsigned_from_ok(length.complete(&mut rng))?))
+                .with_context(|| {
+                    anyhow!(
+                        "cannot draw the length of collection '{}': try giving it a size with --size",
+                        collection
+                    )
+                })?,
+        };
+        totals.insert(collection.to_string(), total);
+    }
 
-        let ordered: Vec<_> = model
-            .iter_ordered()
-            .map(|iter| iter.map(|s| s.to_string()).collect())
-            .unwrap_or_default();
+    let n_values = totals.values().sum::<u64>() as usize;
+    let references = NamespaceCompiler::new(namespace)
+        .collection_references()
+        .context("cannot compile the namespace")?;
+    let shared = if n_values > CHUNK_SIZE {
+        shared_collections(namespace, &totals, &references)
+    } else {
+        BTreeSet::new()
+    };
 
-        let mut model = model.aggregate();
+    let n_spread = totals
+        .iter()
+        .filter(|(collection, _)| !shared.contains(*collection))
+        .map(|(_, total)| *total)
+        .sum::<u64>() as usize;
+    let smallest_referred = totals
+        .iter()
+        .filter(|(collection, total)| {
+            **total > 0
+                && !shared.contains(*collection)
+                && references
+                    .values()
+                    .any(|targets| targets.contains(*collection))
+        })
+        .map(|(_, total)| *total)
+        .min();
+    let n_spread_chunks = n_spread
+        .div_ceil(CHUNK_SIZE)
+        .min(smallest_referred.unwrap_or(u64::MAX) as usize)
+        .max(1);
+    let n_shared_chunks = usize::from(!shared.is_empty());
+    let n_chunks = n_shared_chunks + n_spread_chunks;
 
-        while generated < self.target {
-            // We populate `out` by walking through the collections in the generated
-            // namespace. We also keep track of the number of `Values` generated
-            // for the progress bar.
-            let round_start = generated;
-            let next = model.complete(&mut rng)?;
-            as_object(next)?
-                .into_iter()
-                .for_each(|(collection, value)| match value {
-                    Value::Array(elements) => {
-                        generated += elements.len();
+    let lengths = totals
+        .into_iter()
+        .map(|(collection, total)| {
+            let lengths = if shared.contains(&collection) {
+                let mut lengths = vec![0; n_chunks];
+                lengths[0] = total;
+                lengths
+            } else {
+                let start =
+                    |chunk: usize| (total as u128 * chunk as u128 / n_spread_chunks as u128) as u64;
+                let spread = (0..n_spread_chunks).map(|chunk| start(chunk + 1) - start(chunk));
+                std::iter::repeat(0)
+                    .take(n_shared_chunks)
+                    .chain(spread)
+                    .collect()
+            };
+            (collection, lengths)
+        })
+        .collect();
 
-                        let entry = out
-                            .entry(collection)
-                            .or_insert_with(|| Value::Array(vec![]));
+    let target = Target::Chunks {
+        n_chunks,
+        lengths,
+        shared,
+    };
+    Ok((target, n_values + n_chunks * singletons))
+}
 
-                        if let Value::Array(to_extend) = entry {
-                            to_extend.extend(elements);
-                        }
-                    }
-                    non_array => {
-                        generated += 1;
-                        //out[&collection] = non_array;
-                        out.insert(collection, non_array);
-                    }
-                });
-            progress_bar.set_position(generated as u64);
-            if round_start == generated {
-                warn!("could not generate {} values: try modifying the schema to generate more data instead of the --size flag", self.target);
-                break;
+/// The collections generated whole in the first chunk, whose elements are then referred to by
+/// the following chunks.
+///
+/// Sharing the smallest collections keeps them from limiting the number of chunks, and spreads
+/// the references to them over all of their elements. They are taken by increasing size as long
+/// as they fit in a single chunk together, and as long as they only refer to collections which
+/// are themselves shared or are not arrays, since their elements can only refer to values
+/// generated in the same chunk.
+fn shared_collections(
+    namespace: &Namespace,
+    totals: &BTreeMap<String, u64>,
+    references: &BTreeMap<String, BTreeSet<String>>,
+) -> BTreeSet<String> {
+    let mut candidates: Vec<_> = totals
+        .iter()
+        .filter(|(_, total)| **total > 0)
+        .map(|(collection, total)| (*total, collection))
+        .collect();
+    candidates.sort();
+
+    let mut shared = BTreeSet::new();
+    let mut n_shared = 0;
+    // A collection may only become shareable once the collections it refers to are shared
+    loop {
+        let n_before = shared.len();
+        for (total, collection) in &candidates {
+            if shared.contains(*collection) || n_shared + total > CHUNK_SIZE as u64 {
+                continue;
             }
+            let refers_to_shared =
+                references
+                    .get(*collection)
+                    .into_iter()
+                    .flatten()
+                    .all(|target| {
+                        shared.contains(target)
+                            || !matches!(namespace.get_collection(target), Ok(Content::Array(_)))
+                    });
+            if refers_to_shared {
+                shared.insert((*collection).clone());
+                n_shared += total;
+            }
+        }
+        if shared.len() == n_before {
+            return shared;
         }
+    }
+}
 
-        progress_bar.finish_and_clear();
+/// Compile the model generating `partition`, where the collections in `lengths` take the given
+/// lengths in successive completions and the `shared` collections stay visible to later ones.
+fn compile_model(
+    namespace: &Namespace,
+    partition: Partition,
+    lengths: BTreeMap<String, Vec<u64>>,
+    shared: BTreeSet<String>,
+) -> Result<Graph> {
+    NamespaceCompiler::new(namespace)
+        .with_partition(partition)
+        .with_lengths(lengths)
+        .with_shared(shared)
+        .compile()
+        .context("cannot compile the namespace")
+}
 
-        let mut ordered_out = Vec::new();
+/// The values generated for a chunk by completing the model a number of times.
+#[derive(Default)]
+struct Rounds {
+    /// The elements generated for the collections which are arrays.
+    arrays: BTreeMap<String, Vec<Value>>,
+    /// The last value generated for the collections which are not arrays.
+    singletons: BTreeMap<String, Value>,
+    /// The number of values generated, including the values of singletons.
+    generated: usize,
+    /// Whether a completion of the model generated nothing, in which case there is no point in
+    /// generating more.
+    is_exhausted: bool,
+}
+
+/// Complete the model until `target` values or [`CHUNK_SIZE`] array elements are generated.
+fn generate_rounds<R: Rng>(
+    model: &mut Aggregate<Graph>,
+    rng: &mut R,
+    collection_name: Option<&str>,
+    target: usize,
+    progress_bar: &ProgressBar,
+) -> Result<Rounds> {
+    let mut rounds = Rounds::default();
+    let mut chunk_len = 0;
 
-        for name in ordered {
-            let value = out.remove(&name).unwrap();
-            ordered_out.push((name, value));
+    while rounds.generated < target && chunk_len < CHUNK_SIZE {
+        // We populate `rounds` by walking through the collections in the generated
+        // namespace. We also keep track of the number of `Values` generated
+        // for the progress bar.
+        let round_start = rounds.generated;
+        let next = model.complete(rng)?;
+        let mut collections = as_object(next)?;
+
+        if let Some(name) = collection_name {
+            let collection_value = collections.remove(name).ok_or_else(|| {
+                anyhow!("generated namespace does not have a collection '{}'", name)
+            })?;
+            collections = BTreeMap::from([(name.to_string(), collection_value)]);
         }
 
-        ordered_out.extend(out);
+        for (collection, value) in collections {
+            match value {
+                Value::Array(elements) => {
+                    rounds.generated += elements.len();
+                    chunk_len += elements.len();
+                    rounds
+                        .arrays
+                        .entry(collection)
+                        .or_default()
+                        .extend(elements);
+                }
+                non_array => {
+                    rounds.generated += 1;
+                    rounds.singletons.insert(collection, non_array);
+                }
+            }
+        }
+
+        progress_bar.inc((rounds.generated - round_start) as u64);
+        if round_start == rounds.generated {
+            rounds.is_exhausted = true;
+            break;
+        }
+    }
+
+    Ok(rounds)
+}
+
+/// Where the [`Rounds`] of each chunk come from.
+enum RoundsSource {
+    /// All chunks are generated on the current thread, from the same model and RNG.
+    Sequential {
+        model: Aggregate<Graph>,
+        rng: Box<StdRng>,
+        collection_name: Option<String>,
+        target: Target,
+        /// The number of values generated so far, or of chunks when generating
+        /// [`Target::Chunks`].
+        done: usize,
+        progress_bar: ProgressBar,
+    },
+    /// Chunks are generated by worker threads, each from its own seed. Chunk `i` is generated by
+    /// the worker `i % receivers.len()` and they are received in order. Every worker generates
+    /// the shared chunk, if any, before its own chunks, but only the first one sends it.
+    Parallel {
+        receivers: Vec<Receiver<Result<Rounds>>>,
+        n_chunks: usize,
+        next_chunk: usize,
+    },
+}
+
+impl RoundsSource {
+    fn parallel(
+        namespace: Namespace,
+        threads: usize,
+        collection_name: Option<String>,
+        target: Target,
+        seed: u64,
+        progress_bar: ProgressBar,
+    ) -> Self {
+        let n_chunks = target.n_chunks();
+        // There is no point in having workers with nothing to do, and using fewer partitions
+        // keeps identifiers denser.
+        let workers = threads.min(n_chunks);
+
+        let mut seeds = StdRng::seed_from_u64(seed);
+        let chunk_seeds: Vec<u64> = (0..n_chunks).map(|_| seeds.gen()).collect();
 
-        Ok(ordered_out)
+        let receivers = (0..workers)
+            .map(|worker| {
+                let (sender, receiver) = sync_channel(1);
+                let namespace = namespace.clone();
+                let collection_name = collection_name.clone();
+                let progress_bar = progress_bar.clone();
+                let replays_shared = target.has_shared_chunk() && worker != 0;
+                let worker_chunks: Vec<_> = replays_shared
+                    .then_some(0)
+                    .into_iter()
+                    .chain((worker..n_chunks).step_by(workers))
+                    .collect();
+                let lengths = target.lengths(worker_chunks.iter().copied());
+                let shared = target.shared();
+                let chunks: Vec<_> = worker_chunks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, chunk)| {
+                        let is_sent = !(replays_shared && i == 0);
+                        (target.chunk_target(*chunk), chunk_seeds[*chunk], is_sent)
+                    })
+                    .collect();
+
+                thread::spawn(move || {
+                    let partition =
+                        Partition::new(worker as u64, workers as u64).and_then(|partition| {
+                            compile_model(&namespace, partition.with_seed(seed), lengths, shared)
+                        });
+                    let hidden_progress_bar = ProgressBar::hidden();
+                    let mut model = match partition {
+                        Ok(graph) => graph.aggregate(),
+                        Err(err) => {
+                            let _ = sender.send(Err(err));
+                            return;
+                        }
+                    };
+                    for (chunk_target, chunk_seed, is_sent) in chunks {
+                        let mut rng = StdRng::seed_from_u64(chunk_seed);
+                        let rounds = generate_rounds(
+                            &mut model,
+                            &mut rng,
+                            collection_name.as_deref(),
+                            chunk_target,
+                            if is_sent {
+                                &progress_bar
+                            } else {
+                                &hidden_progress_bar
+                            },
+                        );
+                        let is_err = rounds.is_err();
+                        if !is_sent && !is_err {
+                            continue;
+                        }
+                        // Sending only fails when the sample is dropped before it is done.
+                        if sender.send(rounds).is_err() || is_err {
+                            return;
+                        }
+                    }
+                });
+
+                receiver
+            })
+            .collect();
+
+        Self::Parallel {
+            receivers,
+            n_chunks,
+            next_chunk: 0,
+        }
+    }
+
+    /// The rounds of the next chunk, and whether it is the last one.
+    fn next_rounds(&mut self) -> Result<(Rounds, bool)> {
+        match self {
+            Self::Sequential {
+                model,
+                rng,
+                collection_name,
+                target,
+                done,
+                progress_bar,
+            } => {
+                let chunk_target = match target {
+                    Target::Values(target) => *target - *done,
+                    Target::Chunks { .. } => target.chunk_target(*done),
+                };
+                let rounds = generate_rounds(
+                    model,
+                    rng.as_mut(),
+                    collection_name.as_deref(),
+                    chunk_target,
+                    progress_bar,
+                )?;
+                let is_last = match target {
+                    Target::Values(target) => {
+                        *done += rounds.generated;
+                        *done >= *target
+                    }
+                    Target::Chunks { n_chunks, .. } => {
+                        *done += 1;
+                        *done >= *n_chunks
+                    }
+                };
+                let is_last = is_last || rounds.is_exhausted;
+                Ok((rounds, is_last))
+            }
+            Self::Parallel {
+                receivers,
+                n_chunks,
+                next_chunk,
+            } => {
+                let receiver = &receivers[*next_chunk % receivers.len()];
+                let rounds = receiver
+                    .recv()
+                    .map_err(|_| anyhow!("a generation thread stopped unexpectedly"))??;
+                *next_chunk += 1;
+                let is_last = *next_chunk == *n_chunks || rounds.is_exhausted;
+                Ok((rounds, is_last))
+            }
+        }
     }
 }
 
-struct CollectionSampleStrategy {
-    name: String,
+/// Turns the [`Rounds`] of each chunk into a [`SamplerOutput`].
+///
+/// The elements of collections that are arrays are output with the chunk they were generated
+/// in, whereas collections that are not arrays only keep their last value. Those are held back
+/// until the last chunk, so that they are exported once.
+struct ChunkAssembler {
+    collection_name: Option<String>,
+    ordered: Vec<String>,
     target: usize,
+    singletons: BTreeMap<String, Value>,
+    progress_bar: ProgressBar,
 }
 
-impl CollectionSampleStrategy {
-    fn sample<R: Rng>(self, model: Graph, mut rng: R) -> Result<Value> {
-        let mut out = Value::Array(vec![]);
-        let mut generated = 0;
-        let progress_bar = sampler_progress_bar(self.target as u64);
+impl ChunkAssembler {
+    fn assemble(&mut self, rounds: Rounds, is_last: bool) -> SamplerOutput {
+        if rounds.is_exhausted {
+            match &self.collection_name {
+                Some(name) => warn!("could not generate {} values for collection {}: try modifying the schema to generate more instead of using the --size flag", self.target, name),
+                None => warn!("could not generate {} values: try modifying the schema to generate more data instead of the --size flag", self.target),
+            }
+        }
 
-        let mut model = model.aggregate();
+        let mut chunk: BTreeMap<String, Value> = rounds
+            .arrays
+            .into_iter()
+            .map(|(collection, elements)| (collection, Value::Array(elements)))
+            .collect();
 
-        while generated < self.target {
-            let round_start = generated;
-            let next = model.complete(&mut rng)?;
-            let collection_value = as_object(next)?.remove(&self.name).ok_or_else(|| {
-                anyhow!(
-                    "generated namespace does not have a collection '{}'",
-                    self.name
-                )
-            })?;
-            match collection_value {
-                Value::Array(vec) => {
-                    generated += vec.len();
-                    if let Value::Array(to_extend) = &mut out {
-                        to_extend.extend(vec);
+        self.singletons.extend(rounds.singletons);
+        if is_last {
+            self.progress_bar.finish_and_clear();
+            chunk.append(&mut self.singletons);
+        }
+
+        match &self.collection_name {
+            Some(name) => {
+                let value = chunk
+                    .remove(name)
+                    .unwrap_or_else(|| Value::Array(Vec::new()));
+                SamplerOutput::Collection(name.clone(), value)
+            }
+            None => {
+                let mut ordered_out = Vec::new();
+
+                for name in &self.ordered {
+                    if let Some(value) = chunk.remove(name) {
+                        ordered_out.push((name.clone(), value));
                     }
                 }
-                non_array => {
-                    generated += 1;
-                    out = non_array;
-                }
-            }
-            progress_bar.set_position(generated as u64);
-            if round_start == generated {
-                warn!("could not generate {} values for collection {}: try modifying the schema to generate more instead of using the --size flag", self.target, self.name);
-                break;
+
+                ordered_out.extend(chunk);
+
+                SamplerOutput::Namespace(ordered_out)
             }
         }
+    }
+}
 
-        progress_bar.finish_and_clear();
+/// Generates a sample, one chunk of at most [`CHUNK_SIZE`] values at a time when generating on
+/// more than one thread, or of at least [`CHUNK_SIZE`] values otherwise.
+struct Chunks {
+    source: RoundsSource,
+    assembler: ChunkAssembler,
+    is_done: bool,
+}
+
+impl Iterator for Chunks {
+    type Item = Result<SamplerOutput>;
 
-        Ok(out)
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+        match self.source.next_rounds() {
+            Ok((rounds, is_last)) => {
+                self.is_done = is_last;
+                Some(Ok(self.assembler.assemble(rounds, is_last)))
+            }
+            Err(err) => {
+                self.is_done = true;
+                self.assembler.progress_bar.finish_and_clear();
+                Some(Err(err))
+            }
+        }
     }
 }
 
@@ -204,3 +798,319 @@ fn as_object(sample: Value) -> Result<BTreeMap<String, Value>> {
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(collection_name: Option<String>, target: usize) -> Sample<'static> {
+        let namespace = serde_json::from_value::<Content>(serde_json::json!({
+            "type": "object",
+            "ids": {
+                "type": "array",
+                "length": 100,
+                "content": {
+                    "type": "number",
+                    "subtype": "u64",
+                    "id": {}
+                }
+            },
+            "settings": {
+                "type": "object",
+                "version": {
+                    "type": "number",
+                    "constant": 1
+                }
+            }
+        }))
+        .unwrap()
+        .into_namespace()
+        .unwrap();
+
+        Sampler::try_from(&namespace)
+            .unwrap()
+            .sample_seeded(collection_name, target, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn sample_in_chunks() {
+        let chunks = sample(None, 25_000)
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .into_iter()
+            .map(SamplerOutput::into_collections)
+            .collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 3);
+
+        let mut ids = Vec::new();
+        for (idx, chunk) in chunks.into_iter().enumerate() {
+            let is_last = idx == 2;
+            let names: Vec<_> = chunk.iter().map(|(name, _)| name.as_str()).collect();
+            if is_last {
+                assert_eq!(names, ["ids", "settings"]);
+            } else {
+                assert_eq!(names, ["ids"]);
+            }
+            if let Value::Array(elements) = &chunk[0].1 {
+                assert!(is_last || elements.len() >= CHUNK_SIZE);
+                ids.extend(elements.iter().cloned());
+            }
+        }
+
+        let expected: Vec<_> = (1..=ids.len() as u64)
+            .map(|id| Value::Number(id.into()))
+            .collect();
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn sample_into_output() {
+        let output = sample(Some("ids".to_string()), 25_000)
+            .into_output()
+            .unwrap();
+        match output {
+            SamplerOutput::Collection(name, Value::Array(elements)) => {
+                assert_eq!(name, "ids");
+                assert_eq!(elements.len(), 25_000);
+            }
+            _ => panic!("expected the elements of a single collection"),
+        }
+
+        let output = sample(None, 0).into_output().unwrap();
+        assert!(output.into_collections().is_empty());
+    }
+
+    #[test]
+    fn sample_on_threads() {
+        let namespace = serde_json::from_value::<Content>(serde_json::json!({
+            "type": "object",
+            "users": {
+                "type": "array",
+                "length": 100,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "id": {}
+                    },
+                    "code": {
+                        "type": "unique",
+                        "content": {
+                            "type": "number",
+                            "range": {
+                                "low": 0,
+                                "high": 1000000,
+                                "step": 1
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap()
+        .into_namespace()
+        .unwrap();
+
+        let sample = |threads| {
+            let chunks = Sampler::try_from(&namespace)
+                .unwrap()
+                .with_threads(threads)
+                .sample_seeded(Some("users".to_string()), 35_000, 7)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(chunks.len(), 4);
+            chunks
+                .into_iter()
+                .map(SamplerOutput::into_json)
+                .collect::<Vec<_>>()
+        };
+
+        let chunks = sample(3);
+        assert_eq!(chunks, sample(3));
+        assert_ne!(chunks, sample(2));
+
+        let users: Vec<_> = chunks
+            .iter()
+            .flat_map(|chunk| chunk.as_array().unwrap())
+            .collect();
+        assert_eq!(users.len(), 35_000);
+        for field in ["id", "code"] {
+            let values: std::collections::HashSet<_> = users
+                .iter()
+                .map(|user| user[field].as_u64().unwrap())
+                .collect();
+            assert_eq!(values.len(), users.len());
+        }
+    }
+
+    #[test]
+    fn sample_collection_sizes() {
+        let namespace = serde_json::from_value::<Content>(serde_json::json!({
+            "type": "object",
+            "users": {
+                "type": "array",
+                "length": 5,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "id": {}
+                    }
+                }
+            },
+            "events": {
+                "type": "array",
+                "length": 3,
+                "content": {
+                    "type": "object",
+                    "user_id": "@users.content.id"
+                }
+            },
+            "tags": {
+                "type": "array",
+                "length": 4,
+                "content": {
+                    "type": "string",
+                    "pattern": "[a-z]{8}"
+                }
+            },
+            "settings": {
+                "type": "object",
+                "version": {
+                    "type": "number",
+                    "constant": 1
+                }
+            }
+        }))
+        .unwrap()
+        .into_namespace()
+        .unwrap();
+
+        let sizes = BTreeMap::from([("users".to_string(), 10), ("events".to_string(), 25_000)]);
+        for threads in [1, 3] {
+            let chunks = Sampler::try_from(&namespace)
+                .unwrap()
+                .with_threads(threads)
+                .with_collection_sizes(&sizes)
+                .unwrap()
+                .sample_seeded(None, 1_000_000, 0)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            // Users and tags are shared, in a chunk before the 3 chunks of events
+            assert_eq!(chunks.len(), 4);
+
+            let mut users = Vec::new();
+            let mut events = Vec::new();
+            let mut tags = 0;
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                let chunk = chunk.into_json();
+                let chunk_users = chunk["users"].as_array().unwrap();
+                let chunk_events = chunk["events"].as_array().unwrap();
+                assert_eq!(chunk_users.is_empty(), i != 0);
+                assert_eq!(chunk_events.is_empty(), i == 0);
+                assert!(chunk_events.len() <= CHUNK_SIZE);
+
+                tags += chunk["tags"].as_array().unwrap().len();
+                users.extend(chunk_users.iter().cloned());
+                events.extend(chunk_events.iter().cloned());
+                assert_eq!(chunk["settings"].is_object(), events.len() == 25_000);
+            }
+            assert_eq!(users.len(), 10);
+            assert_eq!(events.len(), 25_000);
+            assert_eq!(tags, 4);
+
+            let ids: std::collections::HashSet<_> =
+                users.iter().map(|user| user["id"].as_u64()).collect();
+            assert_eq!(ids.len(), 10);
+            assert!(events
+                .iter()
+                .all(|event| ids.contains(&event["user_id"].as_u64())));
+        }
+
+        let sizes = BTreeMap::from([("settings".to_string(), 10)]);
+        assert!(Sampler::try_from(&namespace)
+            .unwrap()
+            .with_collection_sizes(&sizes)
+            .is_err());
+    }
+
+    #[test]
+    fn sample_collection_sizes_references() {
+        let namespace = serde_json::from_value::<Content>(serde_json::json!({
+            "type": "object",
+            "users": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "id": {
+                        "type": "number",
+                        "subtype": "u64",
+                        "id": {}
+                    }
+                }
+            },
+            "events": {
+                "type": "array",
+                "length": 1,
+                "content": {
+                    "type": "object",
+                    "user_id": "@users.content.id"
+                }
+            }
+        }))
+        .unwrap()
+        .into_namespace()
+        .unwrap();
+
+        let sizes = BTreeMap::from([("users".to_string(), 10), ("events".to_string(), 100_000)]);
+        for threads in [1, 3] {
+            let chunks = Sampler::try_from(&namespace)
+                .unwrap()
+                .with_threads(threads)
+                .with_collection_sizes(&sizes)
+                .unwrap()
+                .sample_seeded(None, 0, 0)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(chunks.len(), 11);
+
+            let mut ids = Vec::new();
+            let mut references = BTreeMap::new();
+            for chunk in chunks {
+                let chunk = chunk.into_json();
+                let chunk_events = chunk["events"].as_array().unwrap();
+                assert!(chunk_events.len() <= CHUNK_SIZE);
+                ids.extend(
+                    chunk["users"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|user| user["id"].as_u64()),
+                );
+
+                // The events of every chunk refer to all the users
+                let chunk_references: std::collections::HashSet<_> = chunk_events
+                    .iter()
+                    .map(|event| event["user_id"].as_u64())
+                    .collect();
+                assert!(chunk_events.is_empty() || chunk_references.len() == 10);
+                for event in chunk_events {
+                    *references.entry(event["user_id"].as_u64()).or_insert(0) += 1;
+                }
+            }
+            assert_eq!(ids.len(), 10);
+            assert_eq!(references.values().sum::<usize>(), 100_000);
+            assert!(references.keys().all(|id| ids.contains(id)));
+            assert!(references
+                .values()
+                .all(|count| (9_000..=11_000).contains(count)));
+        }
+    }
+}
@@ -12,9 +12,11 @@ pub async fn generate_scenario(namespace: &str, scenario: Option<String>) -> Res
         collection: None,
         scenario,
         random: false,
+        threads: 1,
+        create_tables: false,
         schema: None,
         seed: Some(5),
-        size: 10,
+        size: vec![10.into()],
         to: "json:".to_string(),
     }))
     .await